@@ -104,4 +104,18 @@ pub enum CryptoOutput {
     Random(Vec<u8>),
 }
 
+impl CryptoOutput {
+    pub const fn variant_name(&self) -> &'static str {
+        match self {
+            CryptoOutput::KeyPair { .. } => "KeyPair",
+            CryptoOutput::Signature(_) => "Signature",
+            CryptoOutput::Verified(_) => "Verified",
+            CryptoOutput::Encrypted(_) => "Encrypted",
+            CryptoOutput::Decrypted(_) => "Decrypted",
+            CryptoOutput::Hash(_) => "Hash",
+            CryptoOutput::Random(_) => "Random",
+        }
+    }
+}
+
 pub type CryptoResult = Result<CryptoOutput, CryptoError>;
@@ -436,7 +436,6 @@ pub struct RetryConfig {
     pub max_retries: u32,
     pub initial_backoff_ms: u64,
     pub max_backoff_ms: u64,
-    pub retryable_status_codes: Vec<u16>,
 }
 
 impl Default for RetryConfig {
@@ -445,7 +444,6 @@ impl Default for RetryConfig {
             max_retries: 3,
             initial_backoff_ms: 1000,
             max_backoff_ms: 30000,
-            retryable_status_codes: vec![408, 429, 500, 502, 503, 504],
         }
     }
 }
@@ -458,8 +456,10 @@ impl RetryConfig {
         }
     }
 
+    /// Uses the same classification as the outbox so HTTP-level retries agree with it.
     pub fn is_retryable_status(&self, status: u16) -> bool {
-        self.retryable_status_codes.contains(&status)
+        crate::classify_retryability(Some(status), crate::ErrorKind::from_http_status(status))
+            .is_retryable()
     }
 }
 
@@ -721,7 +721,6 @@ pub enum HttpError {
         status: u16,
         message: String,
         request_id: String,
-        retryable: bool,
     },
 
     #[error("request cancelled")]
@@ -740,7 +739,11 @@ impl HttpError {
             HttpError::Timeout { .. } => true,
             HttpError::ConnectionError { .. } => true,
             HttpError::DnsError { .. } => true,
-            HttpError::HttpStatus { retryable, .. } => *retryable,
+            HttpError::HttpStatus { status, .. } => crate::classify_retryability(
+                Some(*status),
+                crate::ErrorKind::from_http_status(*status),
+            )
+            .is_retryable(),
             HttpError::TooManyRedirects { .. } => false,
             HttpError::Cancelled { .. } => false,
             _ => false,
@@ -851,6 +854,56 @@ impl HttpResponse {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+impl HttpResponse {
+    pub fn ok_json<T: serde::Serialize>(value: &T) -> Self {
+        let body = serde_json::to_vec(value).expect("test fixture must serialize");
+        Self::from_status(200, body).with_header("content-type", "application/json")
+    }
+
+    pub fn from_status(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(
+            status,
+            HttpHeaders::new(),
+            body.into(),
+            "test-request".into(),
+            0,
+        )
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .insert(name, value)
+            .expect("test fixture header must be valid");
+        self
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl HttpError {
+    pub fn timeout() -> Self {
+        HttpError::Timeout {
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+            request_id: "test-request".into(),
+        }
+    }
+
+    pub fn connection_failed(host: &str) -> Self {
+        HttpError::ConnectionError {
+            host: host.into(),
+            message: "connection refused".into(),
+        }
+    }
+
+    pub fn status(status: u16, message: &str) -> Self {
+        HttpError::HttpStatus {
+            status,
+            message: message.into(),
+            request_id: "test-request".into(),
+        }
+    }
+}
+
 pub type HttpOutput = HttpResponse;
 pub type HttpResult = Result<HttpResponse, HttpError>;
 
@@ -996,6 +1049,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_fixture_builders() {
+        let ok = HttpResponse::ok_json(&vec![1, 2, 3]);
+        assert!(ok.is_success());
+        assert_eq!(ok.body(), b"[1,2,3]");
+        assert_eq!(ok.header("Content-Type"), Some("application/json"));
+
+        let limited = HttpResponse::from_status(429, "slow down").with_header("Retry-After", "5");
+        assert!(limited.is_client_error());
+        assert_eq!(limited.header("retry-after"), Some("5"));
+
+        assert!(HttpError::timeout().is_retryable());
+        assert!(HttpError::status(503, "unavailable").is_server_error());
+        assert!(!HttpError::status(404, "missing").is_retryable());
+    }
+
     #[test]
     fn test_url_validation_valid() {
         let result = ValidatedUrl::new("https://api.example.com/v1/users");
@@ -1137,6 +1206,27 @@ mod tests {
         assert!(request.retry_config().is_some());
     }
 
+    #[test]
+    fn test_status_retryability_matches_shared_classification() {
+        assert!(!HttpError::status(409, "conflict").is_retryable());
+        assert!(HttpError::status(429, "slow down").is_retryable());
+        assert!(!HttpError::status(400, "bad").is_retryable());
+        assert!(HttpError::status(500, "oops").is_retryable());
+        assert!(HttpError::connection_failed("api.example.com").is_retryable());
+    }
+
+    #[test]
+    fn test_retry_config_status_retryability_matches_shared_classification() {
+        let config = RetryConfig::default();
+
+        for status in [408, 429, 500, 501, 503] {
+            assert!(config.is_retryable_status(status), "{status}");
+        }
+        for status in [400, 401, 404, 409] {
+            assert!(!config.is_retryable_status(status), "{status}");
+        }
+    }
+
     #[test]
     fn test_error_retryable() {
         assert!(HttpError::Timeout {
@@ -1154,16 +1244,14 @@ mod tests {
         assert!(HttpError::HttpStatus {
             status: 503,
             message: "x".into(),
-            request_id: "y".into(),
-            retryable: true
+            request_id: "y".into()
         }
         .is_retryable());
 
         assert!(!HttpError::HttpStatus {
             status: 400,
             message: "x".into(),
-            request_id: "y".into(),
-            retryable: false
+            request_id: "y".into()
         }
         .is_retryable());
 
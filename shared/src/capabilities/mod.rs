@@ -89,6 +89,34 @@ pub struct Capabilities {
 #[cfg(any(test, feature = "test-utils"))]
 pub mod testing {
     use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct FakeServer {
+        routes: HashMap<String, HttpResult>,
+    }
+
+    impl FakeServer {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn route(mut self, path: impl Into<String>, response: HttpResult) -> Self {
+            self.routes.insert(path.into(), response);
+            self
+        }
+
+        pub fn respond(&self, url: &str) -> HttpResult {
+            let path = url
+                .split_once("://")
+                .map_or(url, |(_, rest)| rest.find('/').map_or("/", |i| &rest[i..]));
+            let path = path.split(['?', '#']).next().unwrap_or(path);
+            self.routes
+                .get(path)
+                .cloned()
+                .unwrap_or_else(|| Ok(HttpOutput::from_status(404, format!("no route for {path}"))))
+        }
+    }
 
     pub fn mock_capabilities() -> Capabilities {
         Capabilities {
@@ -307,6 +307,22 @@ impl NormalizedBbox {
     pub fn height(&self) -> f32 {
         self.y2 - self.y1
     }
+
+    pub fn area(&self) -> f32 {
+        self.width() * self.height()
+    }
+
+    pub fn iou(&self, other: &NormalizedBbox) -> f32 {
+        let inter_w = (self.x2.min(other.x2) - self.x1.max(other.x1)).max(0.0);
+        let inter_h = (self.y2.min(other.y2) - self.y1.max(other.y1)).max(0.0);
+        let inter = inter_w * inter_h;
+        let union = self.area() + other.area() - inter;
+        if union <= 0.0 {
+            0.0
+        } else {
+            inter / union
+        }
+    }
 }
 
 impl TryFrom<&Detection> for NormalizedBbox {
@@ -317,22 +333,64 @@ impl TryFrom<&Detection> for NormalizedBbox {
     }
 }
 
-pub fn merge_bboxes(detections: &[Detection]) -> Result<NormalizedBbox, ImageProcessingError> {
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MergeStrategy {
+    UnionAll,
+    LargestBox,
+    #[default]
+    HighestConfidence,
+    NmsClusters {
+        iou: f32,
+    },
+}
+
+// The first box is the primary one; `NmsClusters` keeps descending confidence order.
+pub fn merge_bboxes(
+    detections: &[Detection],
+    strategy: MergeStrategy,
+) -> Result<Vec<NormalizedBbox>, ImageProcessingError> {
     if detections.is_empty() {
-        return Ok(NormalizedBbox::full());
+        return Ok(vec![NormalizedBbox::full()]);
+    }
+
+    let boxes = detections
+        .iter()
+        .map(|det| NormalizedBbox::try_from(det).map(|bbox| (bbox, det.confidence)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match strategy {
+        MergeStrategy::UnionAll => union_all(&boxes).map(|bbox| vec![bbox]),
+        MergeStrategy::LargestBox => Ok(boxes
+            .iter()
+            .max_by(|(a, _), (b, _)| a.area().total_cmp(&b.area()))
+            .map(|(bbox, _)| vec![bbox.clone()])
+            .unwrap_or_default()),
+        MergeStrategy::HighestConfidence => Ok(boxes
+            .iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(bbox, _)| vec![bbox.clone()])
+            .unwrap_or_default()),
+        MergeStrategy::NmsClusters { iou } => Ok(nms_clusters(boxes, iou)),
     }
+}
 
-    if detections.len() == 1 {
-        return NormalizedBbox::try_from(&detections[0]);
-    }
+pub fn primary_bbox(
+    detections: &[Detection],
+    strategy: MergeStrategy,
+) -> Result<NormalizedBbox, ImageProcessingError> {
+    merge_bboxes(detections, strategy)?
+        .into_iter()
+        .next()
+        .ok_or(ImageProcessingError::ZeroCrop)
+}
 
+fn union_all(boxes: &[(NormalizedBbox, f32)]) -> Result<NormalizedBbox, ImageProcessingError> {
     let mut x1 = f64::MAX;
     let mut y1 = f64::MAX;
     let mut x2 = f64::MIN;
     let mut y2 = f64::MIN;
 
-    for det in detections {
-        let bbox = NormalizedBbox::try_from(det)?;
+    for (bbox, _) in boxes {
         x1 = x1.min(bbox.x1() as f64);
         y1 = y1.min(bbox.y1() as f64);
         x2 = x2.max(bbox.x2() as f64);
@@ -342,6 +400,92 @@ pub fn merge_bboxes(detections: &[Detection]) -> Result<NormalizedBbox, ImagePro
     NormalizedBbox::new(x1 as f32, y1 as f32, x2 as f32, y2 as f32)
 }
 
+fn nms_clusters(mut boxes: Vec<(NormalizedBbox, f32)>, iou_threshold: f32) -> Vec<NormalizedBbox> {
+    boxes.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let mut kept: Vec<NormalizedBbox> = Vec::new();
+    for (bbox, _) in boxes {
+        if kept
+            .iter()
+            .all(|existing| existing.iou(&bbox) <= iou_threshold)
+        {
+            kept.push(bbox);
+        }
+    }
+    kept
+}
+
+pub fn pad_bbox(bbox: NormalizedBbox, pad_frac: f32) -> NormalizedBbox {
+    if !pad_frac.is_finite() || pad_frac <= 0.0 {
+        return bbox;
+    }
+
+    let dx = bbox.width() * pad_frac;
+    let dy = bbox.height() * pad_frac;
+    NormalizedBbox {
+        x1: (bbox.x1 - dx).max(0.0),
+        y1: (bbox.y1 - dy).max(0.0),
+        x2: (bbox.x2 + dx).min(1.0),
+        y2: (bbox.y2 + dy).min(1.0),
+    }
+}
+
+pub fn pad_bbox_aspect(
+    bbox: NormalizedBbox,
+    pad_frac: f32,
+    target_aspect: f32,
+    img_w: u32,
+    img_h: u32,
+) -> NormalizedBbox {
+    if img_w == 0 || img_h == 0 {
+        return bbox;
+    }
+    let padded = pad_bbox(bbox, pad_frac);
+    if !target_aspect.is_finite() || target_aspect <= 0.0 {
+        return padded;
+    }
+
+    let (img_w, img_h) = (img_w as f64, img_h as f64);
+    let aspect = target_aspect as f64;
+    let cx = (padded.x1 as f64 + padded.x2 as f64) / 2.0 * img_w;
+    let cy = (padded.y1 as f64 + padded.y2 as f64) / 2.0 * img_h;
+    let mut w = padded.width() as f64 * img_w;
+    let mut h = padded.height() as f64 * img_h;
+
+    if w / h < aspect {
+        w = h * aspect;
+    } else {
+        h = w / aspect;
+    }
+    // When the frame can't fit the widened side, shrink the other one to keep the ratio.
+    if w > img_w {
+        w = img_w;
+        h = w / aspect;
+    }
+    if h > img_h {
+        h = img_h;
+        w = h * aspect;
+    }
+
+    let x1 = (cx - w / 2.0).clamp(0.0, img_w - w);
+    let y1 = (cy - h / 2.0).clamp(0.0, img_h - h);
+    NormalizedBbox {
+        x1: (x1 / img_w) as f32,
+        y1: (y1 / img_h) as f32,
+        x2: (((x1 + w) / img_w) as f32).min(1.0),
+        y2: (((y1 + h) / img_h) as f32).min(1.0),
+    }
+}
+
+pub fn crop_image(img: &DynamicImage, bbox: NormalizedBbox) -> DynamicImage {
+    let (w, h) = img.dimensions();
+    let x1 = safe_coord(bbox.x1() as f64, w);
+    let y1 = safe_coord(bbox.y1() as f64, h);
+    let x2 = safe_coord(bbox.x2() as f64, w).max(x1 + 1).min(w);
+    let y2 = safe_coord(bbox.y2() as f64, h).max(y1 + 1).min(h);
+    img.crop_imm(x1, y1, x2 - x1, y2 - y1)
+}
+
 fn validate_expand(expand: f32, max: f32) -> Result<(), ImageProcessingError> {
     if expand.is_nan() || expand.is_infinite() || expand < 0.0 || expand > max {
         return Err(ImageProcessingError::InvalidExpand { value: expand, max });
@@ -484,29 +628,179 @@ mod tests {
 
     #[test]
     fn merge_bboxes_empty_returns_full() {
-        let result = merge_bboxes(&[]).unwrap();
-        assert_eq!(result, NormalizedBbox::full());
+        let result = merge_bboxes(&[], MergeStrategy::UnionAll).unwrap();
+        assert_eq!(result, vec![NormalizedBbox::full()]);
     }
 
     #[test]
     fn merge_bboxes_single_passthrough() {
         let det = make_detection([0.1, 0.2, 0.5, 0.6]);
-        let result = merge_bboxes(&[det]).unwrap();
-        assert_eq!(result, NormalizedBbox::new(0.1, 0.2, 0.5, 0.6).unwrap());
+        let result = merge_bboxes(&[det], MergeStrategy::UnionAll).unwrap();
+        assert_eq!(
+            result,
+            vec![NormalizedBbox::new(0.1, 0.2, 0.5, 0.6).unwrap()]
+        );
     }
 
     #[test]
     fn merge_bboxes_multiple_encompasses_all() {
         let d1 = make_detection([0.1, 0.2, 0.3, 0.4]);
         let d2 = make_detection([0.5, 0.6, 0.7, 0.8]);
-        let result = merge_bboxes(&[d1, d2]).unwrap();
-        assert_eq!(result, NormalizedBbox::new(0.1, 0.2, 0.7, 0.8).unwrap());
+        let result = merge_bboxes(&[d1, d2], MergeStrategy::UnionAll).unwrap();
+        assert_eq!(
+            result,
+            vec![NormalizedBbox::new(0.1, 0.2, 0.7, 0.8).unwrap()]
+        );
     }
 
     #[test]
     fn merge_bboxes_rejects_nan() {
         let det = make_detection([f32::NAN, 0.1, 0.5, 0.5]);
-        assert!(merge_bboxes(&[det]).is_err());
+        assert!(merge_bboxes(&[det.clone()], MergeStrategy::UnionAll).is_err());
+        assert!(merge_bboxes(&[det], MergeStrategy::HighestConfidence).is_err());
+    }
+
+    fn scored(bbox: [f32; 4], confidence: f32) -> Detection {
+        Detection {
+            bbox,
+            confidence,
+            class_id: 15,
+        }
+    }
+
+    fn far_apart() -> Vec<Detection> {
+        vec![
+            scored([0.0, 0.0, 0.2, 0.2], 0.6),
+            scored([0.5, 0.5, 1.0, 1.0], 0.4),
+            scored([0.7, 0.0, 0.9, 0.3], 0.9),
+        ]
+    }
+
+    #[test]
+    fn merge_strategy_defaults_to_highest_confidence() {
+        assert_eq!(MergeStrategy::default(), MergeStrategy::HighestConfidence);
+    }
+
+    #[test]
+    fn merge_bboxes_largest_box_picks_biggest_area() {
+        let result = merge_bboxes(&far_apart(), MergeStrategy::LargestBox).unwrap();
+        assert_eq!(
+            result,
+            vec![NormalizedBbox::new(0.5, 0.5, 1.0, 1.0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn merge_bboxes_highest_confidence_picks_strongest() {
+        let result = merge_bboxes(&far_apart(), MergeStrategy::HighestConfidence).unwrap();
+        assert_eq!(
+            result,
+            vec![NormalizedBbox::new(0.7, 0.0, 0.9, 0.3).unwrap()]
+        );
+    }
+
+    #[test]
+    fn merge_bboxes_nms_keeps_strongest_per_cluster() {
+        let dets = vec![
+            scored([0.1, 0.1, 0.4, 0.4], 0.7),
+            scored([0.12, 0.1, 0.42, 0.4], 0.8),
+            scored([0.6, 0.6, 0.9, 0.9], 0.5),
+            scored([0.61, 0.6, 0.9, 0.88], 0.3),
+        ];
+
+        let result = merge_bboxes(&dets, MergeStrategy::NmsClusters { iou: 0.5 }).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                NormalizedBbox::new(0.12, 0.1, 0.42, 0.4).unwrap(),
+                NormalizedBbox::new(0.6, 0.6, 0.9, 0.9).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_bboxes_nms_keeps_disjoint_boxes() {
+        let result = merge_bboxes(&far_apart(), MergeStrategy::NmsClusters { iou: 0.5 }).unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0], NormalizedBbox::new(0.7, 0.0, 0.9, 0.3).unwrap());
+    }
+
+    #[test]
+    fn primary_bbox_takes_first_box() {
+        let primary = primary_bbox(&far_apart(), MergeStrategy::NmsClusters { iou: 0.5 }).unwrap();
+        assert_eq!(primary, NormalizedBbox::new(0.7, 0.0, 0.9, 0.3).unwrap());
+        assert_eq!(
+            primary_bbox(&[], MergeStrategy::LargestBox).unwrap(),
+            NormalizedBbox::full()
+        );
+    }
+
+    fn pixel_aspect(bbox: &NormalizedBbox, img_w: u32, img_h: u32) -> f32 {
+        (bbox.width() * img_w as f32) / (bbox.height() * img_h as f32)
+    }
+
+    fn assert_in_frame(bbox: &NormalizedBbox) {
+        assert!(bbox.x1() >= 0.0 && bbox.y1() >= 0.0, "{bbox:?}");
+        assert!(bbox.x2() <= 1.0 && bbox.y2() <= 1.0, "{bbox:?}");
+        assert!(bbox.x2() > bbox.x1() && bbox.y2() > bbox.y1(), "{bbox:?}");
+    }
+
+    #[test]
+    fn pad_bbox_grows_and_clamps() {
+        let bbox = NormalizedBbox::new(0.0, 0.4, 0.2, 0.6).unwrap();
+        let padded = pad_bbox(bbox, 0.5);
+        assert_eq!(padded.x1(), 0.0);
+        assert!((padded.y1() - 0.3).abs() < 1e-6);
+        assert!((padded.x2() - 0.3).abs() < 1e-6);
+        assert!((padded.y2() - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pad_bbox_aspect_matches_target_ratio() {
+        let bbox = NormalizedBbox::new(0.4, 0.3, 0.5, 0.7).unwrap();
+        let padded = pad_bbox_aspect(bbox, 0.1, 4.0 / 3.0, 1000, 800);
+
+        assert!((pixel_aspect(&padded, 1000, 800) - 4.0 / 3.0).abs() < 0.01);
+        assert_in_frame(&padded);
+        assert!(padded.x1() <= 0.4 && padded.x2() >= 0.5);
+    }
+
+    #[test]
+    fn pad_bbox_aspect_recenters_at_edges() {
+        let frames = [(1000, 800), (800, 1000), (640, 640)];
+        let boxes = [
+            NormalizedBbox::new(0.0, 0.0, 0.1, 0.5).unwrap(),
+            NormalizedBbox::new(0.9, 0.5, 1.0, 1.0).unwrap(),
+            NormalizedBbox::new(0.0, 0.95, 0.6, 1.0).unwrap(),
+            NormalizedBbox::new(0.3, 0.0, 0.7, 1.0).unwrap(),
+        ];
+
+        for (img_w, img_h) in frames {
+            for bbox in &boxes {
+                let padded = pad_bbox_aspect(bbox.clone(), 0.15, 4.0 / 3.0, img_w, img_h);
+                assert_in_frame(&padded);
+                assert!(
+                    (pixel_aspect(&padded, img_w, img_h) - 4.0 / 3.0).abs() < 0.01,
+                    "{bbox:?} in {img_w}x{img_h} -> {padded:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pad_bbox_aspect_ignores_invalid_ratio() {
+        let bbox = NormalizedBbox::new(0.4, 0.3, 0.5, 0.7).unwrap();
+        let padded = pad_bbox_aspect(bbox.clone(), 0.1, 0.0, 1000, 800);
+        assert_eq!(padded, pad_bbox(bbox, 0.1));
+    }
+
+    #[test]
+    fn iou_of_identical_and_disjoint_boxes() {
+        let a = NormalizedBbox::new(0.0, 0.0, 0.5, 0.5).unwrap();
+        let b = NormalizedBbox::new(0.5, 0.5, 1.0, 1.0).unwrap();
+        assert!((a.iou(&a) - 1.0).abs() < 1e-6);
+        assert_eq!(a.iou(&b), 0.0);
     }
 
     #[test]
@@ -681,7 +975,7 @@ mod tests {
         ) {
             let d1 = make_detection([x1a, y1a, x2a, y2a]);
             let d2 = make_detection([x1b, y1b, x2b, y2b]);
-            let merged = merge_bboxes(&[d1, d2]).unwrap();
+            let merged = primary_bbox(&[d1, d2], MergeStrategy::UnionAll).unwrap();
 
             prop_assert!(merged.x1() >= 0.0);
             prop_assert!(merged.y1() >= 0.0);
@@ -11,7 +11,7 @@ pub mod vision;
 pub mod image_processing;
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
@@ -24,6 +24,12 @@ pub const CURRENT_KEY_VERSION: u32 = 1;
 pub const DEFAULT_RADIUS_M: u32 = 5000;
 pub const MIN_RADIUS_M: u32 = 500;
 pub const MAX_RADIUS_M: u32 = 50000;
+pub const DEFAULT_HIGHLIGHT_RADIUS_M: u32 = 500;
+pub const MIN_UTC_OFFSET_MINUTES: i32 = -12 * 60;
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+pub const MIN_WOUND_SEVERITY: u8 = 1;
+pub const MAX_WOUND_SEVERITY: u8 = 5;
+pub const MIN_LOCATION_ACCURACY_M: f64 = 250.0;
 pub const DEFAULT_MAP_ZOOM: f64 = 14.0;
 pub const MIN_ZOOM: f64 = 5.0;
 pub const MAX_ZOOM: f64 = 20.0;
@@ -34,6 +40,7 @@ pub const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
 pub const MAX_IMAGE_DIMENSION: u32 = 4096;
 pub const MAX_IMAGE_ALLOC: usize = 100 * 1024 * 1024;
 pub const MAX_PROCESSED_DIMENSION: u32 = 1920;
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 256;
 pub const MAX_PENDING_LOCAL_CASES: usize = 100;
 pub const MAX_OUTBOX_ENTRIES: usize = 50;
 pub const MAX_CACHED_SERVER_CASES: usize = 500;
@@ -47,6 +54,46 @@ pub const MAX_RETRY_ATTEMPTS: u32 = 5;
 pub const BASE_RETRY_DELAY_MS: u64 = 1000;
 pub const MAX_RETRY_DELAY_MS: u64 = 60000;
 pub const JITTER_MAX_MS: u64 = 1000;
+pub const DEFAULT_RETRY_AFTER_MS: u64 = 60_000;
+pub const DEFAULT_FOREGROUND_REFRESH_COOLDOWN_MS: u64 = 30_000;
+pub const OPTIMISTIC_MUTATION_TIMEOUT_MS: u64 = 30_000;
+pub const PERSIST_DEBOUNCE_MS: u64 = 500;
+pub const MAP_SETTLE_DEBOUNCE_MS: u64 = 400;
+pub const MAX_AUDIT_LOG_ENTRIES: usize = 500;
+pub const SLOW_LINK_TIMEOUT_THRESHOLD: u32 = 3;
+pub const SLOW_LINK_BACKOFF_MULTIPLIER: u64 = 4;
+pub const MAX_VIEWED_CASE_IDS: usize = 500;
+pub const COORDINATE_DECIMAL_PLACES: usize = 5;
+pub const DEFAULT_MIN_CROP_CONFIDENCE: f32 = 0.6;
+pub const DEFAULT_CROP_PADDING_FRACTION: f32 = 0.15;
+pub const JWT_EXPIRY_SKEW_MS: u64 = 30_000;
+pub const ONBOARDING_STEP_COUNT: u8 = 2;
+pub const DUPLICATE_SUBMISSION_WINDOW_MS: u64 = 2_000;
+pub const DEFAULT_RADIUS_TOLERANCE_FRACTION: f64 = 0.1;
+pub const PIN_CLUSTER_RADIUS_FRACTION: f64 = 0.05;
+pub const PROCESSED_PHOTO_CACHE_CAPACITY: usize = 4;
+pub const CIRCUIT_BREAKER_WINDOW: usize = 10;
+pub const CIRCUIT_BREAKER_MIN_SAMPLES: usize = 5;
+pub const CIRCUIT_BREAKER_FAILURE_RATE: f64 = 0.5;
+pub const CIRCUIT_BREAKER_COOLDOWN_MS: u64 = 30_000;
+pub const OUTBOX_SIBLING_RESET_WINDOW_MS: u64 = 5 * 60 * 1000;
+pub const MAX_TOTAL_IMAGE_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+pub const PUSH_PROMPT_COOLDOWN_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+pub const LOCATION_SMOOTHING_ALPHA: f64 = 0.3;
+pub const MAX_PLAUSIBLE_SPEED_MPS: f64 = 70.0;
+pub const LOCATION_JUMP_MIN_M: f64 = 500.0;
+pub const LOCATION_JUMP_CONFIRMATIONS: u32 = 3;
+pub const MAX_PENDING_PUSHES: usize = 20;
+pub const MAX_CANCELLED_REQUEST_IDS: usize = 100;
+
+pub const PROCESSED_IMAGE_MIME_TYPE: &str = "image/webp";
+
+pub const UNSUPPORTED_IMAGE_MIME_TYPES: &[&str] = &[
+    "image/heic",
+    "image/heif",
+    "image/heic-sequence",
+    "image/heif-sequence",
+];
 
 pub const RADIUS_ZOOM_MAP: &[(u32, f64)] = &[
     (1000, 16.0),
@@ -90,6 +137,7 @@ pub enum ErrorKind {
     CryptoKeyNotFound,
     FeatureUnavailable,
     InvalidState,
+    Configuration,
     Internal,
     Unknown,
 }
@@ -122,6 +170,7 @@ impl ErrorKind {
             Self::CryptoKeyNotFound => "CRYPTO_KEY_NOT_FOUND",
             Self::FeatureUnavailable => "FEATURE_UNAVAILABLE",
             Self::InvalidState => "INVALID_STATE",
+            Self::Configuration => "CONFIGURATION_ERROR",
             Self::Internal => "INTERNAL_ERROR",
             Self::Unknown => "UNKNOWN_ERROR",
         }
@@ -143,7 +192,8 @@ impl ErrorKind {
             | Self::Crypto
             | Self::CryptoKeyNotFound
             | Self::Internal
-            | Self::InvalidState => ErrorSeverity::Fatal,
+            | Self::InvalidState
+            | Self::Configuration => ErrorSeverity::Fatal,
 
             Self::Authentication
             | Self::Authorization
@@ -163,16 +213,23 @@ impl ErrorKind {
 
     #[must_use]
     pub const fn is_retryable(self) -> bool {
-        matches!(
-            self,
-            Self::Network
-                | Self::Timeout
-                | Self::RateLimited
-                | Self::Storage
-                | Self::Conflict
-                | Self::Camera
-                | Self::Location
-        )
+        classify_retryability(None, self).is_retryable()
+    }
+
+    #[must_use]
+    pub const fn from_http_status(status: u16) -> Self {
+        match status {
+            400 => Self::Validation,
+            401 => Self::Authentication,
+            403 => Self::Authorization,
+            404 => Self::NotFound,
+            409 => Self::Conflict,
+            429 => Self::RateLimited,
+            402 => Self::QuotaExceeded,
+            408 => Self::Timeout,
+            500..=599 => Self::Internal,
+            _ => Self::Unknown,
+        }
     }
 
     #[must_use]
@@ -191,6 +248,36 @@ impl ErrorKind {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Retryability {
+    Retryable,
+    Permanent,
+}
+
+impl Retryability {
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Retryable)
+    }
+}
+
+#[must_use]
+pub const fn classify_retryability(status: Option<u16>, kind: ErrorKind) -> Retryability {
+    match status {
+        Some(408 | 429 | 500..=599) => Retryability::Retryable,
+        Some(400..=499) => Retryability::Permanent,
+        _ => match kind {
+            ErrorKind::Network
+            | ErrorKind::Timeout
+            | ErrorKind::RateLimited
+            | ErrorKind::Storage
+            | ErrorKind::Camera
+            | ErrorKind::Location => Retryability::Retryable,
+            _ => Retryability::Permanent,
+        },
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AppError {
     pub kind: ErrorKind,
@@ -244,8 +331,16 @@ impl AppError {
     }
 
     #[must_use]
-    pub const fn is_retryable(&self) -> bool {
-        self.kind.is_retryable() && !matches!(self.severity, ErrorSeverity::Fatal)
+    pub fn http_status(&self) -> Option<u16> {
+        self.context.get("http_status").and_then(|s| s.parse().ok())
+    }
+
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self.http_status() {
+            Some(status) => classify_retryability(Some(status), self.kind).is_retryable(),
+            None => self.kind.is_retryable() && !matches!(self.severity, ErrorSeverity::Fatal),
+        }
     }
 
     #[must_use]
@@ -320,6 +415,10 @@ impl AppError {
             ErrorKind::InvalidState => {
                 "The app is in an invalid state. Please restart the app.".into()
             }
+            ErrorKind::Configuration => {
+                "The app could not reach the service correctly. Please update the app or contact support."
+                    .into()
+            }
             ErrorKind::Internal | ErrorKind::Unknown => {
                 "An unexpected error occurred. Please try again or contact support.".into()
             }
@@ -328,18 +427,7 @@ impl AppError {
 
     #[must_use]
     pub fn from_http_status(status: u16, body: Option<&[u8]>) -> Self {
-        let kind = match status {
-            400 => ErrorKind::Validation,
-            401 => ErrorKind::Authentication,
-            403 => ErrorKind::Authorization,
-            404 => ErrorKind::NotFound,
-            409 => ErrorKind::Conflict,
-            429 => ErrorKind::RateLimited,
-            402 => ErrorKind::QuotaExceeded,
-            408 => ErrorKind::Timeout,
-            500..=599 => ErrorKind::Internal,
-            _ => ErrorKind::Unknown,
-        };
+        let kind = ErrorKind::from_http_status(status);
 
         let message = body
             .and_then(|b| serde_json::from_slice::<ApiErrorResponse>(b).ok())
@@ -374,6 +462,229 @@ struct ApiErrorResponse {
 
 pub type AppResult<T> = Result<T, AppError>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+    Es,
+    De,
+}
+
+impl Locale {
+    #[must_use]
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+            Self::Es => "es",
+            Self::De => "de",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastKey {
+    CaseCreated,
+    SavedOffline,
+    CaseClaimed,
+    ClaimedByOther,
+    StatusUpdated,
+    StatusChangedByOther,
+    NoConnection,
+    /// Carries a `{seconds}` placeholder; see [`Model::show_rate_limited_toast`].
+    RateLimited,
+    OnlyFailedDiscardable,
+    DiscardWhileSyncing,
+    CaseRemoved,
+    SlowConnection,
+    LocationImprecise,
+    AlreadySubmitted,
+    NotClaimable,
+    /// Carries `{shown}` and `{total}` placeholders; see [`Model::list_showing_text`].
+    ListShowing,
+}
+
+impl ToastKey {
+    #[must_use]
+    pub const fn english(self) -> &'static str {
+        match self {
+            Self::CaseCreated => "Case created",
+            Self::SavedOffline => "Saved offline, will sync when connected",
+            Self::CaseClaimed => "Case claimed successfully",
+            Self::ClaimedByOther => "Case was claimed by another rescuer",
+            Self::StatusUpdated => "Status updated",
+            Self::StatusChangedByOther => "Status was changed by someone else",
+            Self::NoConnection => "No internet connection",
+            Self::RateLimited => "Please wait {seconds}s",
+            Self::OnlyFailedDiscardable => "Only failed items can be discarded",
+            Self::DiscardWhileSyncing => "This item is syncing. Try again in a moment.",
+            Self::CaseRemoved => "This case was removed",
+            Self::SlowConnection => {
+                "Your connection is very slow. We'll keep retrying in the background."
+            }
+            Self::LocationImprecise => {
+                "Your location is imprecise. Drag the pin to confirm where you are."
+            }
+            Self::AlreadySubmitted => "Report already submitted",
+            Self::NotClaimable => "Case cannot be claimed",
+            Self::ListShowing => "Showing {shown} of {total}",
+        }
+    }
+
+    #[must_use]
+    pub const fn french(self) -> &'static str {
+        match self {
+            Self::CaseCreated => "Signalement créé",
+            Self::SavedOffline => "Enregistré hors ligne, synchronisation dès la connexion",
+            Self::CaseClaimed => "Signalement pris en charge",
+            Self::ClaimedByOther => "Un autre sauveteur a pris en charge ce signalement",
+            Self::StatusUpdated => "Statut mis à jour",
+            Self::StatusChangedByOther => "Le statut a été modifié par quelqu'un d'autre",
+            Self::NoConnection => "Pas de connexion Internet",
+            Self::RateLimited => "Veuillez patienter {seconds} s",
+            Self::OnlyFailedDiscardable => "Seuls les éléments en échec peuvent être supprimés",
+            Self::DiscardWhileSyncing => "Synchronisation en cours. Réessayez dans un instant.",
+            Self::CaseRemoved => "Ce signalement a été supprimé",
+            Self::SlowConnection => {
+                "Votre connexion est très lente. Nous réessaierons en arrière-plan."
+            }
+            Self::LocationImprecise => {
+                "Votre position est imprécise. Déplacez l'épingle pour confirmer où vous êtes."
+            }
+            Self::AlreadySubmitted => "Signalement déjà envoyé",
+            Self::NotClaimable => "Ce signalement ne peut pas être pris en charge",
+            Self::ListShowing => "{shown} sur {total} affichés",
+        }
+    }
+
+    #[must_use]
+    pub const fn spanish(self) -> &'static str {
+        match self {
+            Self::CaseCreated => "Caso creado",
+            Self::SavedOffline => "Guardado sin conexión, se sincronizará al conectarse",
+            Self::CaseClaimed => "Caso asignado correctamente",
+            Self::ClaimedByOther => "Otro rescatista ya tomó este caso",
+            Self::StatusUpdated => "Estado actualizado",
+            Self::StatusChangedByOther => "Otra persona cambió el estado",
+            Self::NoConnection => "Sin conexión a Internet",
+            Self::RateLimited => "Espera {seconds} s",
+            Self::OnlyFailedDiscardable => "Solo se pueden descartar los elementos fallidos",
+            Self::DiscardWhileSyncing => "Sincronizando. Inténtalo de nuevo en un momento.",
+            Self::CaseRemoved => "Este caso fue eliminado",
+            Self::SlowConnection => {
+                "Tu conexión es muy lenta. Seguiremos reintentando en segundo plano."
+            }
+            Self::LocationImprecise => {
+                "Tu ubicación es imprecisa. Arrastra el marcador para confirmar dónde estás."
+            }
+            Self::AlreadySubmitted => "El reporte ya se envió",
+            Self::NotClaimable => "Este caso no se puede asignar",
+            Self::ListShowing => "Mostrando {shown} de {total}",
+        }
+    }
+
+    #[must_use]
+    pub const fn german(self) -> &'static str {
+        match self {
+            Self::CaseCreated => "Fall erstellt",
+            Self::SavedOffline => "Offline gespeichert, wird bei Verbindung synchronisiert",
+            Self::CaseClaimed => "Fall erfolgreich übernommen",
+            Self::ClaimedByOther => "Der Fall wurde von einem anderen Helfer übernommen",
+            Self::StatusUpdated => "Status aktualisiert",
+            Self::StatusChangedByOther => "Der Status wurde von jemand anderem geändert",
+            Self::NoConnection => "Keine Internetverbindung",
+            Self::RateLimited => "Bitte {seconds} s warten",
+            Self::OnlyFailedDiscardable => "Nur fehlgeschlagene Einträge können verworfen werden",
+            Self::DiscardWhileSyncing => {
+                "Wird gerade synchronisiert. Bitte gleich erneut versuchen."
+            }
+            Self::CaseRemoved => "Dieser Fall wurde entfernt",
+            Self::SlowConnection => {
+                "Deine Verbindung ist sehr langsam. Wir versuchen es im Hintergrund weiter."
+            }
+            Self::LocationImprecise => {
+                "Dein Standort ist ungenau. Ziehe die Nadel, um deinen Standort zu bestätigen."
+            }
+            Self::AlreadySubmitted => "Meldung bereits gesendet",
+            Self::NotClaimable => "Dieser Fall kann nicht übernommen werden",
+            Self::ListShowing => "{shown} von {total} angezeigt",
+        }
+    }
+}
+
+/// Supplies the user-facing strings the core builds into views and toasts.
+/// Every method defaults to the built-in English text, so a translation only
+/// overrides what it covers.
+pub trait MessageCatalog: Send + Sync {
+    fn locale(&self) -> Locale {
+        Locale::En
+    }
+
+    fn error_message(&self, error: &AppError) -> String {
+        error.user_facing_message()
+    }
+
+    fn status_name(&self, status: CaseStatus) -> String {
+        status.display_name().to_string()
+    }
+
+    fn toast(&self, key: ToastKey) -> String {
+        key.english().to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnglishCatalog;
+
+impl MessageCatalog for EnglishCatalog {}
+
+/// Built-in catalogs. Error messages stay in English until they are translated.
+impl MessageCatalog for Locale {
+    fn locale(&self) -> Locale {
+        *self
+    }
+
+    fn status_name(&self, status: CaseStatus) -> String {
+        let name = match (self, status) {
+            (Self::En, _) => status.display_name(),
+            (Self::Fr, CaseStatus::Pending) => "En attente",
+            (Self::Fr, CaseStatus::Claimed) => "Pris en charge",
+            (Self::Fr, CaseStatus::EnRoute) => "En route",
+            (Self::Fr, CaseStatus::Arrived) => "Sur place",
+            (Self::Fr, CaseStatus::Resolved) => "Résolu",
+            (Self::Fr, CaseStatus::Cancelled) => "Annulé",
+            (Self::Fr, CaseStatus::Expired) => "Expiré",
+            (Self::Es, CaseStatus::Pending) => "Pendiente",
+            (Self::Es, CaseStatus::Claimed) => "Asignado",
+            (Self::Es, CaseStatus::EnRoute) => "En camino",
+            (Self::Es, CaseStatus::Arrived) => "En el lugar",
+            (Self::Es, CaseStatus::Resolved) => "Resuelto",
+            (Self::Es, CaseStatus::Cancelled) => "Cancelado",
+            (Self::Es, CaseStatus::Expired) => "Caducado",
+            (Self::De, CaseStatus::Pending) => "Ausstehend",
+            (Self::De, CaseStatus::Claimed) => "Übernommen",
+            (Self::De, CaseStatus::EnRoute) => "Unterwegs",
+            (Self::De, CaseStatus::Arrived) => "Vor Ort",
+            (Self::De, CaseStatus::Resolved) => "Gelöst",
+            (Self::De, CaseStatus::Cancelled) => "Abgebrochen",
+            (Self::De, CaseStatus::Expired) => "Abgelaufen",
+        };
+        name.to_string()
+    }
+
+    fn toast(&self, key: ToastKey) -> String {
+        let text = match self {
+            Self::En => key.english(),
+            Self::Fr => key.french(),
+            Self::Es => key.spanish(),
+            Self::De => key.german(),
+        };
+        text.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Error)]
 pub enum CoordinateError {
     #[error("Latitude {0} is out of valid range [-90, 90]")]
@@ -390,10 +701,36 @@ impl From<CoordinateError> for AppError {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxKind {
+    LocalCases,
+    OutboxEntries,
+}
+
+impl OutboxKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::LocalCases => "local_cases",
+            Self::OutboxEntries => "outbox_entries",
+        }
+    }
+
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "local_cases" => Some(Self::LocalCases),
+            "outbox_entries" => Some(Self::OutboxEntries),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Error)]
 pub enum OutboxError {
     #[error("Outbox is full (maximum {max} entries)")]
-    Full { max: usize },
+    Full { max: usize, kind: OutboxKind },
     #[error("Duplicate operation ID: {0}")]
     DuplicateOpId(String),
     #[error("Entry not found: {0}")]
@@ -405,8 +742,9 @@ pub enum OutboxError {
 impl From<OutboxError> for AppError {
     fn from(e: OutboxError) -> Self {
         match e {
-            OutboxError::Full { .. } => {
+            OutboxError::Full { kind, .. } => {
                 AppError::new(ErrorKind::QuotaExceeded, "Too many pending operations")
+                    .with_context("outbox_kind", kind.as_str())
             }
             OutboxError::DuplicateOpId(_) => {
                 AppError::new(ErrorKind::Conflict, "Operation already exists")
@@ -527,6 +865,59 @@ impl ValidatedCoordinate {
     pub fn distance_to(self, other: Self) -> f64 {
         haversine_distance(self, other)
     }
+
+    /// Initial great-circle bearing towards `other`, in degrees clockwise
+    /// from north within `[0, 360)`. Identical points yield `0.0`.
+    #[must_use]
+    pub fn bearing_to(self, other: Self) -> f64 {
+        const EPSILON: f64 = 1e-10;
+
+        if (self.lat - other.lat).abs() < EPSILON && (self.lon - other.lon).abs() < EPSILON {
+            return 0.0;
+        }
+
+        let lat1_rad = self.lat.to_radians();
+        let lat2_rad = other.lat.to_radians();
+        let delta_lon = (other.lon - self.lon).to_radians();
+
+        let y = delta_lon.sin() * lat2_rad.cos();
+        let x = lat1_rad.cos() * lat2_rad.sin() - lat1_rad.sin() * lat2_rad.cos() * delta_lon.cos();
+
+        normalize_bearing(y.atan2(x).to_degrees())
+    }
+}
+
+fn normalize_bearing(degrees: f64) -> f64 {
+    if !degrees.is_finite() {
+        return 0.0;
+    }
+    let normalized = degrees.rem_euclid(360.0);
+    if normalized >= 360.0 {
+        0.0
+    } else {
+        normalized
+    }
+}
+
+pub const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+#[must_use]
+pub fn format_bearing(degrees: f64) -> String {
+    if !degrees.is_finite() {
+        return "Unknown".to_string();
+    }
+
+    let sector = 360.0 / 16.0;
+    let shifted = (normalize_bearing(degrees) + sector / 2.0) % 360.0;
+    COMPASS_POINTS
+        .iter()
+        .zip(1u32..)
+        .find(|(_, index)| shifted < f64::from(*index) * sector)
+        .map_or("N", |(point, _)| point)
+        .to_string()
 }
 
 impl Default for ValidatedCoordinate {
@@ -589,6 +980,112 @@ pub fn haversine_distance(p1: ValidatedCoordinate, p2: ValidatedCoordinate) -> f
     }
 }
 
+#[must_use]
+pub fn case_distance(case: &ServerCase, from: ValidatedCoordinate) -> f64 {
+    if let Some(distance) = case.distance_meters.filter(|d| d.is_finite()) {
+        return distance;
+    }
+
+    ValidatedCoordinate::new(case.location.lat, case.location.lon)
+        .map(|coord| haversine_distance(from, coord))
+        .unwrap_or(f64::MAX)
+}
+
+#[must_use]
+pub fn exceeds_radius(distance_m: f64, radius_m: u32, tolerance_fraction: f64) -> bool {
+    let tolerance = if tolerance_fraction.is_finite() {
+        tolerance_fraction.max(0.0)
+    } else {
+        0.0
+    };
+    distance_m > f64::from(radius_m) * (1.0 + tolerance)
+}
+
+#[must_use]
+pub fn smooth_location(
+    prev: Option<ValidatedCoordinate>,
+    new: ValidatedCoordinate,
+    alpha: f64,
+) -> ValidatedCoordinate {
+    let prev = match prev {
+        Some(prev) => prev,
+        None => return new,
+    };
+    let alpha = if alpha.is_finite() {
+        alpha.clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    let mut delta_lon = new.lon() - prev.lon();
+    if delta_lon > 180.0 {
+        delta_lon -= 360.0;
+    } else if delta_lon < -180.0 {
+        delta_lon += 360.0;
+    }
+
+    let lat = prev.lat() + alpha * (new.lat() - prev.lat());
+    let mut lon = prev.lon() + alpha * delta_lon;
+    if lon > 180.0 {
+        lon -= 360.0;
+    } else if lon < -180.0 {
+        lon += 360.0;
+    }
+
+    ValidatedCoordinate::new(lat, lon).unwrap_or(new)
+}
+
+#[must_use]
+pub fn is_location_accurate(accuracy_m: Option<f64>, min_accuracy_m: f64) -> bool {
+    match accuracy_m {
+        Some(accuracy) => accuracy.is_finite() && accuracy >= 0.0 && accuracy <= min_accuracy_m,
+        None => true,
+    }
+}
+
+fn mime_essence(mime_type: &str) -> String {
+    mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Canonicalizes the MIME types the photo pipeline can carry end to end,
+/// folding common aliases and casing. Returns `None` for anything else.
+#[must_use]
+pub fn normalize_mime(mime_type: &str) -> Option<&'static str> {
+    match mime_essence(mime_type).as_str() {
+        "image/jpeg" | "image/jpg" | "image/pjpeg" => Some("image/jpeg"),
+        "image/png" | "image/x-png" => Some("image/png"),
+        "image/webp" | "image/x-webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+pub fn check_image_mime_type(mime_type: &str) -> Result<(), AppError> {
+    let essence = mime_essence(mime_type);
+    if !UNSUPPORTED_IMAGE_MIME_TYPES.contains(&essence.as_str()) {
+        return Ok(());
+    }
+
+    Err(AppError::new(
+        ErrorKind::ImageFormatUnsupported,
+        "HEIC photos aren't supported. Switch to JPEG (Most Compatible) in camera settings.",
+    )
+    .with_context("mime_type", essence)
+    .with_context(
+        "suggestion",
+        "Switch the camera format to JPEG in camera settings",
+    ))
+}
+
+#[must_use]
+pub fn is_within_highlight_radius(distance_m: f64, highlight_radius_m: u32) -> bool {
+    distance_m.is_finite() && distance_m >= 0.0 && distance_m <= f64::from(highlight_radius_m)
+}
+
 #[must_use]
 pub fn format_distance(meters: f64) -> String {
     if !meters.is_finite() || meters < 0.0 {
@@ -607,8 +1104,65 @@ pub fn format_distance(meters: f64) -> String {
     }
 }
 
+pub const FEET_PER_METER: f64 = 3.280_84;
+pub const METERS_PER_MILE: f64 = 1_609.344;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceUnit {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+/// Like [`format_distance`], but in feet and miles for [`DistanceUnit::Imperial`].
+#[must_use]
+pub fn format_distance_unit(meters: f64, unit: DistanceUnit) -> String {
+    if unit == DistanceUnit::Metric {
+        return format_distance(meters);
+    }
+    if !meters.is_finite() || meters < 0.0 {
+        return "Unknown".to_string();
+    }
+
+    let feet = meters * FEET_PER_METER;
+    let miles = meters / METERS_PER_MILE;
+    if feet < 1000.0 {
+        format!("{feet:.0} ft")
+    } else if miles < 10.0 {
+        format!("{miles:.1} mi")
+    } else {
+        format!("{miles:.0} mi")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeAgoStyle {
+    /// A single abbreviated unit, e.g. "5m ago".
+    #[default]
+    Compact,
+    /// The two largest units spelled out, e.g. "5 minutes 30 seconds ago".
+    Verbose,
+}
+
+const TIME_AGO_UNITS: &[(u64, &str)] = &[
+    (365 * 86_400, "year"),
+    (30 * 86_400, "month"),
+    (7 * 86_400, "week"),
+    (86_400, "day"),
+    (3_600, "hour"),
+    (60, "minute"),
+    (1, "second"),
+];
+
 #[must_use]
 pub fn format_time_ago(timestamp_ms: u64, now_ms: u64) -> String {
+    format_time_ago_styled(timestamp_ms, now_ms, TimeAgoStyle::Compact)
+}
+
+#[must_use]
+pub fn format_time_ago_styled(timestamp_ms: u64, now_ms: u64, style: TimeAgoStyle) -> String {
     if timestamp_ms > now_ms {
         let future_diff_secs = (timestamp_ms.saturating_sub(now_ms)) / 1000;
         return if future_diff_secs < 60 {
@@ -623,6 +1177,14 @@ pub fn format_time_ago(timestamp_ms: u64, now_ms: u64) -> String {
     if diff_secs < 5 {
         return "Just now".into();
     }
+
+    match style {
+        TimeAgoStyle::Compact => compact_time_ago(diff_secs),
+        TimeAgoStyle::Verbose => verbose_time_ago(diff_secs),
+    }
+}
+
+fn compact_time_ago(diff_secs: u64) -> String {
     if diff_secs < 60 {
         return format!("{diff_secs}s ago");
     }
@@ -651,64 +1213,361 @@ pub fn format_time_ago(timestamp_ms: u64, now_ms: u64) -> String {
     format!("{}y ago", diff_days / 365)
 }
 
-#[must_use]
-pub fn calculate_retry_delay(attempt: u32, jitter_ms: u64) -> u64 {
-    let base = BASE_RETRY_DELAY_MS;
-    let exponential = base.saturating_mul(2u64.saturating_pow(attempt));
-    let capped = exponential.min(MAX_RETRY_DELAY_MS);
-    capped.saturating_add(jitter_ms)
+fn verbose_time_ago(diff_secs: u64) -> String {
+    let plural = |count: u64, unit: &str| {
+        if count == 1 {
+            format!("1 {unit}")
+        } else {
+            format!("{count} {unit}s")
+        }
+    };
+
+    let index = TIME_AGO_UNITS
+        .iter()
+        .position(|(secs, _)| diff_secs >= *secs)
+        .unwrap_or(TIME_AGO_UNITS.len() - 1);
+    let (major_secs, major_unit) = TIME_AGO_UNITS[index];
+    let mut text = plural(diff_secs / major_secs, major_unit);
+
+    if let Some((minor_secs, minor_unit)) = TIME_AGO_UNITS.get(index + 1) {
+        let minor = (diff_secs % major_secs) / minor_secs;
+        if minor > 0 {
+            text.push(' ');
+            text.push_str(&plural(minor, minor_unit));
+        }
+    }
+
+    format!("{text} ago")
 }
 
-#[must_use]
-pub fn generate_jitter() -> u64 {
-    use std::collections::hash_map::RandomState;
-    use std::hash::{BuildHasher, Hasher};
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayBucket {
+    Today,
+    Yesterday,
+    Earlier,
+}
 
-    let state = RandomState::new();
-    let mut hasher = state.build_hasher();
-    hasher.write_u64(
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_nanos() as u64)
-            .unwrap_or(0),
-    );
-    hasher.finish() % JITTER_MAX_MS
+fn local_day_index(timestamp_ms: u64, utc_offset_minutes: i32) -> i64 {
+    const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+    let utc_ms = i64::try_from(timestamp_ms).unwrap_or(i64::MAX);
+    let offset_ms = i64::from(utc_offset_minutes) * 60 * 1000;
+    utc_ms.saturating_add(offset_ms).div_euclid(MS_PER_DAY)
 }
 
 #[must_use]
-pub fn get_current_time_ms() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0)
+pub fn day_bucket(created_at_ms: u64, now_ms: u64, utc_offset_minutes: i32) -> DayBucket {
+    let days_ago = local_day_index(now_ms, utc_offset_minutes)
+        - local_day_index(created_at_ms, utc_offset_minutes);
+
+    match days_ago {
+        i64::MIN..=0 => DayBucket::Today,
+        1 => DayBucket::Yesterday,
+        _ => DayBucket::Earlier,
+    }
 }
 
 #[must_use]
-pub fn zoom_for_radius(radius_m: u32) -> f64 {
-    RADIUS_ZOOM_MAP
-        .iter()
-        .find(|(r, _)| *r >= radius_m)
-        .map(|(_, z)| *z)
-        .unwrap_or(FALLBACK_ZOOM)
+pub fn sync_status_text(status: LocalCaseStatus, retry_count: u32) -> Option<String> {
+    let attempts = match retry_count {
+        0 => String::new(),
+        1 => " (1 attempt)".to_string(),
+        n => format!(" ({n} attempts)"),
+    };
+
+    match status {
+        LocalCaseStatus::PendingUpload => Some("Pending sync".into()),
+        LocalCaseStatus::Uploading => Some("Syncing...".into()),
+        LocalCaseStatus::UploadingPhoto => Some("Uploading photo...".into()),
+        LocalCaseStatus::Failed => Some(format!("Sync failed{attempts} \u{2014} tap to retry")),
+        LocalCaseStatus::PermanentlyFailed => Some(format!("Sync failed permanently{attempts}")),
+        LocalCaseStatus::Synced => None,
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
-#[serde(rename_all = "snake_case")]
-pub enum CaseStatus {
-    #[default]
-    Pending,
-    Claimed,
-    EnRoute,
-    Arrived,
-    Resolved,
-    Cancelled,
-    Expired,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoordFormat {
+    DecimalDegrees,
+    DegreesMinutesSeconds,
 }
 
-impl CaseStatus {
-    #[must_use]
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().replace('-', "_").as_str() {
+fn format_coordinate_component(
+    value: f64,
+    positive: char,
+    negative: char,
+    fmt: CoordFormat,
+) -> String {
+    match fmt {
+        CoordFormat::DecimalDegrees => format!("{value:.COORDINATE_DECIMAL_PLACES$}"),
+        CoordFormat::DegreesMinutesSeconds => {
+            let hemisphere = if value < 0.0 { negative } else { positive };
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let tenths_of_seconds = (value.abs() * 36_000.0).round() as u64;
+            let degrees = tenths_of_seconds / 36_000;
+            let minutes = (tenths_of_seconds % 36_000) / 600;
+            let seconds = tenths_of_seconds % 600;
+
+            format!(
+                "{degrees}\u{b0}{minutes}'{}.{}\"{hemisphere}",
+                seconds / 10,
+                seconds % 10
+            )
+        }
+    }
+}
+
+#[must_use]
+pub fn format_latitude(lat: f64, fmt: CoordFormat) -> String {
+    format_coordinate_component(lat, 'N', 'S', fmt)
+}
+
+#[must_use]
+pub fn format_longitude(lon: f64, fmt: CoordFormat) -> String {
+    format_coordinate_component(lon, 'E', 'W', fmt)
+}
+
+#[must_use]
+pub fn format_coordinate(coord: ValidatedCoordinate, fmt: CoordFormat) -> String {
+    format!(
+        "{}, {}",
+        format_latitude(coord.lat(), fmt),
+        format_longitude(coord.lon(), fmt)
+    )
+}
+
+#[must_use]
+pub fn calculate_retry_delay(attempt: u32, jitter_ms: u64) -> u64 {
+    let base = BASE_RETRY_DELAY_MS;
+    let exponential = base.saturating_mul(2u64.saturating_pow(attempt));
+    let capped = exponential.min(MAX_RETRY_DELAY_MS);
+    capped.saturating_add(jitter_ms)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackoffStrategy {
+    /// Capped exponential delay plus up to `JITTER_MAX_MS` of jitter.
+    #[default]
+    ExponentialAdditive,
+    /// Anywhere between zero and the capped exponential delay.
+    FullJitter,
+    /// Between the base delay and three times the previous delay, capped.
+    Decorrelated,
+}
+
+/// Delay before retry `attempt` under `strategy`. `prev_delay_ms` is only
+/// read by [`BackoffStrategy::Decorrelated`]; pass 0 before the first retry.
+#[must_use]
+pub fn calculate_retry_delay_strategy(
+    attempt: u32,
+    prev_delay_ms: u64,
+    rng_seed: u64,
+    strategy: BackoffStrategy,
+) -> u64 {
+    let random = splitmix64(rng_seed);
+    match strategy {
+        BackoffStrategy::ExponentialAdditive => {
+            calculate_retry_delay(attempt, uniform_below(random, JITTER_MAX_MS))
+        }
+        BackoffStrategy::FullJitter => {
+            let ceiling = calculate_retry_delay(attempt, 0);
+            uniform_below(random, ceiling.saturating_add(1))
+        }
+        BackoffStrategy::Decorrelated => {
+            let low = BASE_RETRY_DELAY_MS;
+            let high = prev_delay_ms.saturating_mul(3).max(low);
+            let span = (high - low).saturating_add(1);
+            low.saturating_add(uniform_below(random, span))
+                .min(MAX_RETRY_DELAY_MS)
+        }
+    }
+}
+
+#[must_use]
+pub fn projected_retry_schedule(attempt_count: u32, max_attempts: u32, now_ms: u64) -> Vec<u64> {
+    let mut schedule = Vec::new();
+    let mut at_ms = now_ms;
+
+    for attempt in attempt_count.saturating_add(1)..max_attempts {
+        at_ms = at_ms.saturating_add(calculate_retry_delay(attempt, 0));
+        schedule.push(at_ms);
+    }
+
+    schedule
+}
+
+#[must_use]
+pub fn generate_jitter() -> u64 {
+    generate_jitter_from(time_seed())
+}
+
+/// Uniform jitter in `0..JITTER_MAX_MS`, fully determined by `seed`.
+#[must_use]
+pub fn generate_jitter_from(seed: u64) -> u64 {
+    uniform_below(splitmix64(seed), JITTER_MAX_MS)
+}
+
+/// One splitmix64 step; scrambles sequential seeds into well-spread output.
+#[must_use]
+pub const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Maps a uniform `u64` onto `0..bound` by multiply-shift, which avoids the
+/// bias of taking a remainder.
+fn uniform_below(random: u64, bound: u64) -> u64 {
+    let scaled = (u128::from(random) * u128::from(bound)) >> 64;
+    u64::try_from(scaled).unwrap_or(0)
+}
+
+fn time_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0),
+    );
+    hasher.finish()
+}
+
+#[must_use]
+pub fn get_current_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.into_iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[must_use]
+pub fn stable_id_hash(id: &str) -> u64 {
+    fnv1a(id.bytes())
+}
+
+/// Content hash of a report submission, used to recognise a double-tapped
+/// "Report" button as one submission rather than two. The description is
+/// compared case- and whitespace-insensitively so two different animals
+/// reported from the same spot stay separate.
+#[must_use]
+pub fn submission_fingerprint(
+    location: (f64, f64),
+    description: Option<&str>,
+    photo_data: Option<&[u8]>,
+) -> u64 {
+    let description = description
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let bytes = location
+        .0
+        .to_bits()
+        .to_le_bytes()
+        .into_iter()
+        .chain(location.1.to_bits().to_le_bytes())
+        .chain(fnv1a(description.bytes()).to_le_bytes())
+        .chain(photo_data.unwrap_or_default().iter().copied());
+    fnv1a(bytes)
+}
+
+#[must_use]
+pub fn zoom_for_radius(radius_m: u32) -> f64 {
+    RADIUS_ZOOM_MAP
+        .iter()
+        .find(|(r, _)| *r >= radius_m)
+        .map(|(_, z)| *z)
+        .unwrap_or(FALLBACK_ZOOM)
+}
+
+/// Zoom that keeps two points `meters` apart on screen when centered on
+/// their midpoint, down to `MIN_ZOOM` for pairs beyond `RADIUS_ZOOM_MAP`.
+#[must_use]
+pub fn zoom_for_distance(meters: f64) -> f64 {
+    if !meters.is_finite() {
+        return FALLBACK_ZOOM;
+    }
+    let zoom = match RADIUS_ZOOM_MAP
+        .iter()
+        .find(|(r, _)| f64::from(*r) >= meters)
+    {
+        Some((_, z)) => *z,
+        // Past the table, zoom out one level per doubling of distance.
+        None => RADIUS_ZOOM_MAP
+            .last()
+            .map_or(FALLBACK_ZOOM, |(r, z)| z - (meters / f64::from(*r)).log2()),
+    };
+    zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+}
+
+/// Inverse of [`zoom_for_radius`]: the visible radius at `zoom`, scaled from
+/// the nearest `RADIUS_ZOOM_MAP` entry (one zoom level doubles the radius).
+#[must_use]
+pub fn radius_for_zoom(zoom: f64) -> f64 {
+    let zoom = if zoom.is_finite() {
+        zoom
+    } else {
+        FALLBACK_ZOOM
+    };
+    let (radius_m, anchor_zoom) = RADIUS_ZOOM_MAP
+        .iter()
+        .min_by(|a, b| (a.1 - zoom).abs().total_cmp(&(b.1 - zoom).abs()))
+        .copied()
+        .unwrap_or((DEFAULT_RADIUS_M, zoom_for_radius(DEFAULT_RADIUS_M)));
+    f64::from(radius_m) * 2f64.powf(anchor_zoom - zoom)
+}
+
+#[must_use]
+pub fn fit_zoom(a: ValidatedCoordinate, b: ValidatedCoordinate) -> (ValidatedCoordinate, f64) {
+    let lat1 = a.lat.to_radians();
+    let lat2 = b.lat.to_radians();
+    let lon1 = a.lon.to_radians();
+    let delta_lon = (b.lon - a.lon).to_radians();
+
+    let bx = lat2.cos() * delta_lon.cos();
+    let by = lat2.cos() * delta_lon.sin();
+    let mid_lat = (lat1.sin() + lat2.sin()).atan2(((lat1.cos() + bx).powi(2) + by.powi(2)).sqrt());
+    let mid_lon = lon1 + by.atan2(lat1.cos() + bx);
+    let mid_lon = (mid_lon.to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+
+    let center = ValidatedCoordinate::new(mid_lat.to_degrees(), mid_lon).unwrap_or(a);
+
+    (center, zoom_for_distance(haversine_distance(a, b)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    #[default]
+    Pending,
+    Claimed,
+    EnRoute,
+    Arrived,
+    Resolved,
+    Cancelled,
+    Expired,
+}
+
+impl CaseStatus {
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
             "pending" | "open" => Some(Self::Pending),
             "claimed" | "assigned" => Some(Self::Claimed),
             "en_route" | "enroute" | "on_way" | "onway" => Some(Self::EnRoute),
@@ -777,7 +1636,44 @@ impl CaseStatus {
         self.valid_transitions().contains(&to)
     }
 
-    pub fn validate_transition(self, to: Self) -> Result<(), TransitionError> {
+    /// Shortest sequence of statuses leading from `self` to `to`, excluding
+    /// `self` and ending with `to`. `None` when `to` is unreachable or equal
+    /// to `self`.
+    #[must_use]
+    pub fn transition_path(self, to: Self) -> Option<Vec<Self>> {
+        if self == to || self.is_terminal() {
+            return None;
+        }
+
+        let mut came_from: HashMap<Self, Self> = HashMap::new();
+        let mut queue = VecDeque::from([self]);
+        while let Some(current) = queue.pop_front() {
+            for next in current.valid_transitions() {
+                if next == self || came_from.contains_key(&next) {
+                    continue;
+                }
+                came_from.insert(next, current);
+                if next == to {
+                    let mut path = vec![to];
+                    let mut step = current;
+                    while step != self {
+                        path.push(step);
+                        step = came_from[&step];
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    pub fn validate_transition(
+        self,
+        to: Self,
+        outcome: Option<ResolutionOutcome>,
+    ) -> Result<(), TransitionError> {
         if self == to {
             return Err(TransitionError::SameStatus);
         }
@@ -787,6 +1683,9 @@ impl CaseStatus {
         if !self.can_transition_to(to) {
             return Err(TransitionError::InvalidTransition { from: self, to });
         }
+        if to == Self::Resolved && outcome.is_none() {
+            return Err(TransitionError::MissingOutcome);
+        }
         Ok(())
     }
 }
@@ -805,6 +1704,31 @@ pub enum TransitionError {
     FromTerminalStatus { status: CaseStatus },
     #[error("Invalid transition from {from} to {to}")]
     InvalidTransition { from: CaseStatus, to: CaseStatus },
+    #[error("Choose an outcome before marking this case resolved")]
+    MissingOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionOutcome {
+    Rescued,
+    DeceasedOnArrival,
+    NotFound,
+    FalseAlarm,
+    ReferredToVet,
+}
+
+impl ResolutionOutcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Rescued => "rescued",
+            Self::DeceasedOnArrival => "deceased_on_arrival",
+            Self::NotFound => "not_found",
+            Self::FalseAlarm => "false_alarm",
+            Self::ReferredToVet => "referred_to_vet",
+        }
+    }
 }
 
 impl From<TransitionError> for AppError {
@@ -813,6 +1737,93 @@ impl From<TransitionError> for AppError {
     }
 }
 
+/// Lossy: `Claimed`, `EnRoute` and `Arrived` all collapse to `InProgress`, and
+/// `Cancelled` and `Expired` both collapse to `Closed`.
+impl From<CaseStatus> for crate::capabilities::ServerCaseStatus {
+    fn from(status: CaseStatus) -> Self {
+        match status {
+            CaseStatus::Pending => Self::Open,
+            CaseStatus::Claimed | CaseStatus::EnRoute | CaseStatus::Arrived => Self::InProgress,
+            CaseStatus::Resolved => Self::Resolved,
+            CaseStatus::Cancelled | CaseStatus::Expired => Self::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StatusMappingError {
+    #[error("Server status {0:?} does not map to a single app status")]
+    Ambiguous(crate::capabilities::ServerCaseStatus),
+}
+
+/// `InProgress` maps to the representative `Claimed`; `Closed` is rejected
+/// because it cannot distinguish `Cancelled` from `Expired`.
+impl TryFrom<crate::capabilities::ServerCaseStatus> for CaseStatus {
+    type Error = StatusMappingError;
+
+    fn try_from(status: crate::capabilities::ServerCaseStatus) -> Result<Self, Self::Error> {
+        use crate::capabilities::ServerCaseStatus;
+
+        match status {
+            ServerCaseStatus::Open => Ok(Self::Pending),
+            ServerCaseStatus::InProgress => Ok(Self::Claimed),
+            ServerCaseStatus::Resolved => Ok(Self::Resolved),
+            ServerCaseStatus::Closed => Err(StatusMappingError::Ambiguous(status)),
+        }
+    }
+}
+
+#[must_use]
+pub fn resolve_redirect_location(base_url: Option<&str>, location: &str) -> Option<String> {
+    let resolved = match base_url.and_then(|base| url::Url::parse(base).ok()) {
+        Some(base) => base.join(location),
+        None => url::Url::parse(location),
+    };
+    resolved.ok().map(String::from)
+}
+
+pub fn validate_jwt_structure(token: &str) -> Result<(), &'static str> {
+    if token.trim().is_empty() {
+        return Err("Sign-in returned an empty session token");
+    }
+    if token.chars().any(char::is_whitespace) {
+        return Err("Sign-in returned a session token containing whitespace");
+    }
+
+    let segments: Vec<&str> = token.split('.').collect();
+    if segments.len() != 3 {
+        return Err("Sign-in returned a malformed session token");
+    }
+
+    let is_base64url = |segment: &str| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    if !segments.iter().all(|s| is_base64url(s)) {
+        return Err("Sign-in returned a malformed session token");
+    }
+
+    Ok(())
+}
+
+/// Reads the `exp` claim without verifying the signature.
+#[must_use]
+pub fn jwt_expiry_ms(token: &str) -> Option<u64> {
+    use base64::Engine as _;
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    claims
+        .get("exp")?
+        .as_u64()
+        .map(|secs| secs.saturating_mul(1000))
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UserId(pub String);
 
@@ -1027,6 +2038,128 @@ impl FeedView {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MapFollowMode {
+    #[default]
+    Follow,
+    Free,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ListSortMode {
+    #[default]
+    Distance,
+    SeverityThenDistance,
+    Newest,
+}
+
+impl ListSortMode {
+    #[must_use]
+    pub fn compare(self, a: &CaseListItem, b: &CaseListItem) -> std::cmp::Ordering {
+        let by_distance = a.distance_meters.total_cmp(&b.distance_meters);
+        match self {
+            Self::Distance => by_distance,
+            Self::SeverityThenDistance => b.wound_severity.cmp(&a.wound_severity).then(by_distance),
+            Self::Newest => b.created_at_ms.cmp(&a.created_at_ms).then(by_distance),
+        }
+    }
+}
+
+pub const PREFERENCES_KV_KEY: &str = "user_preferences";
+// Offline store keys written on this device, so a purge can find them after logout.
+pub const STORE_KEYS_KV_KEY: &str = "offline_store_keys";
+
+/// Device-local settings. Stored as plain JSON under
+/// [`PREFERENCES_KV_KEY`], outside the encrypted offline store, so they
+/// survive logout and never carry PII.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPreferences {
+    pub highlight_radius_m: u32,
+    pub auto_refresh_interval_ms: Option<u64>,
+    pub auto_select_nearest: bool,
+    pub list_sort: ListSortMode,
+    pub distance_unit: DistanceUnit,
+    pub max_image_alloc: usize,
+    pub clustering_enabled: bool,
+    pub outbox_tuning: OutboxTuning,
+    pub locale: Locale,
+    pub photo_output_format: OutputImageFormat,
+    pub map_drift_refresh_m: Option<u32>,
+    pub min_crop_confidence: f32,
+    pub foreground_refresh_cooldown_ms: u64,
+    pub drop_out_of_radius_cases: bool,
+    pub radius_tolerance_fraction: f64,
+    pub require_resolution_evidence: bool,
+    pub utc_offset_minutes: i32,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            highlight_radius_m: DEFAULT_HIGHLIGHT_RADIUS_M,
+            auto_refresh_interval_ms: None,
+            auto_select_nearest: false,
+            list_sort: ListSortMode::Distance,
+            distance_unit: DistanceUnit::Metric,
+            max_image_alloc: MAX_IMAGE_ALLOC,
+            clustering_enabled: false,
+            outbox_tuning: OutboxTuning::default(),
+            locale: Locale::En,
+            photo_output_format: OutputImageFormat::default(),
+            map_drift_refresh_m: None,
+            min_crop_confidence: DEFAULT_MIN_CROP_CONFIDENCE,
+            foreground_refresh_cooldown_ms: DEFAULT_FOREGROUND_REFRESH_COOLDOWN_MS,
+            drop_out_of_radius_cases: true,
+            radius_tolerance_fraction: DEFAULT_RADIUS_TOLERANCE_FRACTION,
+            require_resolution_evidence: false,
+            utc_offset_minutes: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Preference {
+    HighlightRadius { meters: u32 },
+    AutoRefreshInterval { ms: Option<u64> },
+    AutoSelectNearest { enabled: bool },
+    ListSort { mode: ListSortMode },
+    DistanceUnit { unit: DistanceUnit },
+    MaxImageAlloc { bytes: usize },
+    Clustering { enabled: bool },
+    OutboxTuning { tuning: OutboxTuning },
+    Locale { locale: Locale },
+    PhotoOutputFormat { format: OutputImageFormat },
+    MapDriftRefresh { meters: Option<u32> },
+    MinCropConfidence { confidence: f32 },
+    ForegroundRefreshCooldown { ms: u64 },
+    DropOutOfRadiusCases { enabled: bool },
+    RadiusTolerance { fraction: f64 },
+    RequireResolutionEvidence { required: bool },
+    UtcOffset { minutes: i32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyFeedReason {
+    NoCasesNearby,
+    OfflineNoCache,
+    FilteredOut,
+    LocationMissing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecommendedAction {
+    CompleteOnboarding,
+    ResumeCase { case_id: String, status: CaseStatus },
+    RetryFailedSyncs { count: usize },
+    ClaimNearest { case_id: String },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum AppState {
@@ -1063,13 +2196,24 @@ impl AppState {
         )
     }
 
+    /// Returns `(current, total)` for the onboarding step indicator. Pin drop
+    /// is part of the location step, since a precise fix skips it entirely.
+    #[must_use]
+    pub const fn onboarding_step(self) -> Option<(u8, u8)> {
+        match self {
+            Self::OnboardingLocation | Self::PinDrop => Some((1, ONBOARDING_STEP_COUNT)),
+            Self::OnboardingRadius => Some((2, ONBOARDING_STEP_COUNT)),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub const fn can_capture_photo(self) -> bool {
         matches!(self, Self::Ready | Self::CameraCapture)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LocalCaseStatus {
     PendingUpload,
@@ -1113,7 +2257,12 @@ pub struct LocalCase {
     pub created_at_ms_utc: UnixTimeMs,
     pub updated_at_ms_utc: UnixTimeMs,
     pub photo_data: Option<Vec<u8>>,
+    pub photo_mime_type: Option<String>,
+    /// Kept locally for the list and detail views; never uploaded.
+    pub thumbnail_data: Option<Vec<u8>>,
     pub photo_upload_url: Option<String>,
+    #[serde(default)]
+    pub photo_upload_headers: HashMap<String, String>,
     pub server_id: Option<CaseId>,
     pub sync_error: Option<String>,
     pub retry_count: u32,
@@ -1133,7 +2282,10 @@ impl LocalCase {
             created_at_ms_utc: now,
             updated_at_ms_utc: now,
             photo_data: None,
+            photo_mime_type: None,
+            thumbnail_data: None,
             photo_upload_url: None,
+            photo_upload_headers: HashMap::new(),
             server_id: None,
             sync_error: None,
             retry_count: 0,
@@ -1203,6 +2355,8 @@ pub struct ServerCase {
     pub gemini_diagnosis: Option<String>,
     pub species_guess: Option<String>,
     pub distance_meters: Option<f64>,
+    #[serde(default)]
+    pub resolution_outcome: Option<ResolutionOutcome>,
 }
 
 impl ServerCase {
@@ -1216,6 +2370,35 @@ impl ServerCase {
         &self.reporter_id == user_id
     }
 
+    /// Applies a server response for this case. Status, assignment and the
+    /// required fields always come from `other`; optional details it leaves
+    /// out (e.g. `distance_meters`, which claim/transition responses don't
+    /// recompute) keep their current value.
+    pub fn merge_from(&mut self, other: ServerCase) {
+        fn keep_if_missing<T>(current: &mut Option<T>, incoming: Option<T>) {
+            if incoming.is_some() {
+                *current = incoming;
+            }
+        }
+
+        self.id = other.id;
+        self.location = other.location;
+        self.status = other.status;
+        self.created_at_ms_utc = other.created_at_ms_utc;
+        self.updated_at_ms_utc = other.updated_at_ms_utc;
+        self.reporter_id = other.reporter_id;
+        self.assigned_rescuer_id = other.assigned_rescuer_id;
+        keep_if_missing(&mut self.description, other.description);
+        keep_if_missing(&mut self.landmark_hint, other.landmark_hint);
+        keep_if_missing(&mut self.wound_severity, other.wound_severity);
+        keep_if_missing(&mut self.photo_url, other.photo_url);
+        keep_if_missing(&mut self.thumbnail_url, other.thumbnail_url);
+        keep_if_missing(&mut self.gemini_diagnosis, other.gemini_diagnosis);
+        keep_if_missing(&mut self.species_guess, other.species_guess);
+        keep_if_missing(&mut self.distance_meters, other.distance_meters);
+        keep_if_missing(&mut self.resolution_outcome, other.resolution_outcome);
+    }
+
     #[must_use]
     pub fn description_preview(&self, max_len: usize) -> String {
         self.description
@@ -1266,6 +2449,8 @@ pub struct TransitionCaseRequest {
     pub next_status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ResolutionOutcome>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1285,6 +2470,48 @@ pub struct ListCasesResponse {
     pub total_count: Option<u64>,
 }
 
+#[derive(Debug, Error)]
+pub enum ResponseParseError {
+    #[error("Malformed response: {0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("Response contains an unknown field: {0}")]
+    UnknownField(String),
+}
+
+pub fn parse_response<T>(body: &[u8], strict: bool) -> Result<T, ResponseParseError>
+where
+    T: serde::de::DeserializeOwned + Serialize,
+{
+    let parsed: T = serde_json::from_slice(body)?;
+    if strict {
+        let received: serde_json::Value = serde_json::from_slice(body)?;
+        if let Some(field) = first_unknown_field(&received, &serde_json::to_value(&parsed)?) {
+            return Err(ResponseParseError::UnknownField(field));
+        }
+    }
+    Ok(parsed)
+}
+
+// Anything the typed value doesn't serialize back out was dropped on the way in.
+fn first_unknown_field(received: &serde_json::Value, known: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+    match (received, known) {
+        (Value::Object(received), Value::Object(known)) => {
+            received
+                .iter()
+                .find_map(|(key, value)| match known.get(key) {
+                    Some(known) => first_unknown_field(value, known),
+                    None => Some(key.clone()),
+                })
+        }
+        (Value::Array(received), Value::Array(known)) => received
+            .iter()
+            .zip(known)
+            .find_map(|(received, known)| first_unknown_field(received, known)),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingClaim {
     pub case_id: CaseId,
@@ -1327,6 +2554,8 @@ pub struct OptimisticMutation {
     pub original_assignee: Option<UserId>,
     pub new_status: CaseStatus,
     pub created_at_ms: u64,
+    /// Sent with every attempt of this transition so the server can dedupe.
+    pub idempotency_key: IdempotencyKey,
 }
 
 impl OptimisticMutation {
@@ -1344,6 +2573,7 @@ impl OptimisticMutation {
             original_assignee,
             new_status,
             created_at_ms: get_current_time_ms(),
+            idempotency_key: IdempotencyKey::generate(),
         }
     }
 }
@@ -1371,6 +2601,10 @@ pub enum OutboxIntent {
         case_id: CaseId,
         next_status: CaseStatus,
         notes: Option<String>,
+        #[serde(default)]
+        outcome: Option<ResolutionOutcome>,
+        #[serde(default)]
+        idempotency_key: Option<IdempotencyKey>,
     },
     SyncFcmToken {
         token: String,
@@ -1389,6 +2623,24 @@ impl OutboxIntent {
         }
     }
 
+    #[must_use]
+    pub const fn local_id(&self) -> Option<&LocalOpId> {
+        match self {
+            Self::CreateCase { local_id, .. } | Self::UploadPhoto { local_id, .. } => {
+                Some(local_id)
+            }
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn case_id(&self) -> Option<&CaseId> {
+        match self {
+            Self::ClaimCase { case_id } | Self::TransitionCase { case_id, .. } => Some(case_id),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub const fn default_timeout(&self) -> Duration {
         match self {
@@ -1401,8 +2653,17 @@ impl OutboxIntent {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OutboxEntryError {
+#[must_use]
+pub const fn max_attempts_for(intent: &OutboxIntent) -> u32 {
+    match intent {
+        OutboxIntent::ClaimCase { .. } | OutboxIntent::TransitionCase { .. } => 3,
+        OutboxIntent::SyncFcmToken { .. } => 2,
+        OutboxIntent::CreateCase { .. } | OutboxIntent::UploadPhoto { .. } => MAX_RETRY_ATTEMPTS,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntryError {
     pub code: String,
     pub message: Option<String>,
     pub http_status: Option<u16>,
@@ -1429,7 +2690,9 @@ impl OutboxEntryError {
     #[must_use]
     pub fn with_http_status(mut self, status: u16) -> Self {
         self.http_status = Some(status);
-        self.is_permanent = matches!(status, 400..=499) && status != 429 && status != 408;
+        self.is_permanent =
+            !classify_retryability(Some(status), ErrorKind::from_http_status(status))
+                .is_retryable();
         self
     }
 
@@ -1439,6 +2702,12 @@ impl OutboxEntryError {
         self
     }
 
+    #[must_use]
+    pub fn is_client_error(&self) -> bool {
+        self.http_status
+            .is_some_and(|status| (400..500).contains(&status))
+    }
+
     #[must_use]
     pub fn network_error(message: impl Into<String>) -> Self {
         Self::new("NETWORK_ERROR").with_message(message)
@@ -1469,12 +2738,16 @@ pub enum RetryState {
     Failed,
     PermanentlyFailed,
     RateLimited,
+    Discarded,
 }
 
 impl RetryState {
     #[must_use]
     pub const fn is_terminal(self) -> bool {
-        matches!(self, Self::Completed | Self::PermanentlyFailed)
+        matches!(
+            self,
+            Self::Completed | Self::PermanentlyFailed | Self::Discarded
+        )
     }
 
     #[must_use]
@@ -1494,6 +2767,9 @@ pub struct OutboxEntry {
     pub attempt_count: u32,
     pub last_attempt_at: Option<UnixTimeMs>,
     pub next_retry_at: Option<UnixTimeMs>,
+    /// Most recent backoff delay; seeds the next decorrelated-jitter delay.
+    #[serde(default)]
+    pub last_retry_delay_ms: u64,
     pub last_error: Option<OutboxEntryError>,
 }
 
@@ -1511,6 +2787,7 @@ impl OutboxEntry {
             attempt_count: 0,
             last_attempt_at: None,
             next_retry_at: None,
+            last_retry_delay_ms: 0,
             last_error: None,
         }
     }
@@ -1547,6 +2824,32 @@ impl OutboxEntry {
         matches!(self.retry_state, RetryState::InFlight)
     }
 
+    #[must_use]
+    pub fn projected_retry_schedule(&self, now_ms: u64) -> Vec<u64> {
+        let max_attempts = max_attempts_for(&self.intent);
+        match self.retry_state {
+            RetryState::Completed | RetryState::PermanentlyFailed | RetryState::Discarded => {
+                Vec::new()
+            }
+            RetryState::Failed | RetryState::RateLimited => {
+                let next_ms = self.next_retry_at.map_or(now_ms, |t| t.0.max(now_ms));
+                let mut schedule = vec![next_ms];
+                schedule.extend(projected_retry_schedule(
+                    self.attempt_count,
+                    max_attempts,
+                    next_ms,
+                ));
+                schedule
+            }
+            RetryState::Pending => {
+                projected_retry_schedule(self.attempt_count, max_attempts, now_ms)
+            }
+            RetryState::InFlight => {
+                projected_retry_schedule(self.attempt_count.saturating_sub(1), max_attempts, now_ms)
+            }
+        }
+    }
+
     pub fn mark_in_flight(&mut self) {
         let now = UnixTimeMs::now();
         self.retry_state = RetryState::InFlight;
@@ -1562,17 +2865,46 @@ impl OutboxEntry {
         self.next_retry_at = None;
     }
 
+    pub fn mark_discarded(&mut self) {
+        self.retry_state = RetryState::Discarded;
+        self.updated_at = UnixTimeMs::now();
+        self.next_retry_at = None;
+    }
+
     pub fn mark_failed(&mut self, error: OutboxEntryError) {
+        self.mark_failed_with_strategy(error, BackoffStrategy::default(), time_seed());
+    }
+
+    pub fn mark_failed_with_jitter(&mut self, error: OutboxEntryError, jitter_ms: u64) {
+        let delay = calculate_retry_delay(self.attempt_count, jitter_ms);
+        self.fail_with_delay(error, delay);
+    }
+
+    pub fn mark_failed_with_strategy(
+        &mut self,
+        error: OutboxEntryError,
+        strategy: BackoffStrategy,
+        seed: u64,
+    ) {
+        let delay = calculate_retry_delay_strategy(
+            self.attempt_count,
+            self.last_retry_delay_ms,
+            seed,
+            strategy,
+        );
+        self.fail_with_delay(error, delay);
+    }
+
+    fn fail_with_delay(&mut self, error: OutboxEntryError, delay_ms: u64) {
         let now = UnixTimeMs::now();
         self.updated_at = now;
         
-        if error.is_permanent || self.attempt_count >= MAX_RETRY_ATTEMPTS {
+        if error.is_permanent || self.attempt_count >= max_attempts_for(&self.intent) {
             self.retry_state = RetryState::PermanentlyFailed;
         } else {
             self.retry_state = RetryState::Failed;
-            let jitter = generate_jitter();
-            let delay = calculate_retry_delay(self.attempt_count, jitter);
-            self.next_retry_at = Some(now.add_millis(delay));
+            self.next_retry_at = Some(now.add_millis(delay_ms));
+            self.last_retry_delay_ms = delay_ms;
         }
         
         self.last_error = Some(error);
@@ -1592,6 +2924,279 @@ impl OutboxEntry {
         self.last_error = Some(error.permanent());
         self.next_retry_at = None;
     }
+
+    /// Returns a permanently failed entry to `Pending` with a fresh retry
+    /// ladder. `last_error` is kept so the UI can still explain the failure.
+    pub fn revive(&mut self) {
+        self.retry_state = RetryState::Pending;
+        self.reset_backoff();
+    }
+
+    /// Returns a failed entry to `Pending` so the next flush sends it right away.
+    pub fn retry_now(&mut self) {
+        self.retry_state = RetryState::Pending;
+        self.next_retry_at = None;
+        self.updated_at = UnixTimeMs::now();
+    }
+
+    #[must_use]
+    pub fn failed_with_client_error(&self) -> bool {
+        self.last_error
+            .as_ref()
+            .is_some_and(OutboxEntryError::is_client_error)
+    }
+
+    pub fn reset_backoff(&mut self) {
+        self.attempt_count = 0;
+        self.next_retry_at = None;
+        self.last_retry_delay_ms = 0;
+        self.updated_at = UnixTimeMs::now();
+    }
+
+    pub fn mark_timed_out(
+        &mut self,
+        consecutive_timeouts: u32,
+        threshold: u32,
+        strategy: BackoffStrategy,
+    ) {
+        self.mark_failed_with_strategy(OutboxEntryError::timeout_error(), strategy, time_seed());
+
+        if consecutive_timeouts < threshold || self.retry_state != RetryState::Failed {
+            return;
+        }
+
+        if let Some(next) = self.next_retry_at {
+            let delay = next
+                .elapsed_since(self.updated_at)
+                .saturating_mul(SLOW_LINK_BACKOFF_MULTIPLIER)
+                .min(MAX_RETRY_DELAY_MS);
+            self.last_retry_delay_ms = delay;
+            self.next_retry_at = Some(self.updated_at.add_millis(delay));
+        }
+    }
+}
+
+fn keep_newest_by_id<T, K>(
+    items: &mut Vec<T>,
+    id: impl Fn(&T) -> &K,
+    updated_at: impl Fn(&T) -> UnixTimeMs,
+) -> usize
+where
+    K: Eq + std::hash::Hash + Clone,
+{
+    let mut newest: HashMap<K, usize> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        newest
+            .entry(id(item).clone())
+            .and_modify(|best| {
+                if updated_at(item) >= updated_at(&items[*best]) {
+                    *best = i;
+                }
+            })
+            .or_insert(i);
+    }
+
+    let before = items.len();
+    let mut index = 0;
+    items.retain(|item| {
+        let keep = newest.get(id(item)) == Some(&index);
+        index += 1;
+        keep
+    });
+    before - items.len()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    Dismissed,
+    Discarded,
+}
+
+impl RemovalReason {
+    #[must_use]
+    pub const fn accepts(self, state: RetryState) -> bool {
+        match self {
+            Self::Dismissed => matches!(state, RetryState::PermanentlyFailed),
+            Self::Discarded => matches!(state, RetryState::Failed | RetryState::PermanentlyFailed),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dismissed => "dismissed",
+            Self::Discarded => "discarded",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub op_id: OpId,
+    pub from_state: RetryState,
+    pub to_state: RetryState,
+    pub at: UnixTimeMs,
+    pub error_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeoutTracker {
+    consecutive: HashMap<String, u32>,
+}
+
+impl TimeoutTracker {
+    pub fn record_timeout(&mut self, endpoint: &str) -> u32 {
+        let streak = self.consecutive.entry(endpoint.to_string()).or_insert(0);
+        *streak = streak.saturating_add(1);
+        *streak
+    }
+
+    pub fn record_response(&mut self, endpoint: &str) {
+        self.consecutive.remove(endpoint);
+    }
+
+    #[must_use]
+    pub fn streak(&self, endpoint: &str) -> u32 {
+        self.consecutive.get(endpoint).copied().unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn is_escalated(&self, endpoint: &str, threshold: u32) -> bool {
+        self.streak(endpoint) >= threshold
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreaker {
+    outcomes: VecDeque<bool>,
+    window: usize,
+    min_samples: usize,
+    failure_rate_threshold: f64,
+    cooldown_ms: u64,
+    opened_at_ms: Option<u64>,
+    probe_in_flight: bool,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            outcomes: VecDeque::new(),
+            window: CIRCUIT_BREAKER_WINDOW,
+            min_samples: CIRCUIT_BREAKER_MIN_SAMPLES,
+            failure_rate_threshold: CIRCUIT_BREAKER_FAILURE_RATE,
+            cooldown_ms: CIRCUIT_BREAKER_COOLDOWN_MS,
+            opened_at_ms: None,
+            probe_in_flight: false,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn with_failure_rate_threshold(mut self, threshold: f64) -> Self {
+        self.failure_rate_threshold = threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn with_cooldown_ms(mut self, cooldown_ms: u64) -> Self {
+        self.cooldown_ms = cooldown_ms;
+        self
+    }
+
+    #[must_use]
+    pub fn with_window(mut self, window: usize, min_samples: usize) -> Self {
+        self.window = window.max(1);
+        self.min_samples = min_samples.clamp(1, self.window);
+        self
+    }
+
+    #[must_use]
+    pub fn state(&self, now_ms: u64) -> CircuitState {
+        match self.opened_at_ms {
+            Some(opened_at) if now_ms.saturating_sub(opened_at) < self.cooldown_ms => {
+                CircuitState::Open
+            }
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    #[must_use]
+    pub fn failure_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|&&failed| failed).count();
+        // Window sizes are tiny, so the usize -> f64 conversions are exact.
+        let failures = f64::from(u32::try_from(failures).unwrap_or(u32::MAX));
+        let total = f64::from(u32::try_from(self.outcomes.len()).unwrap_or(u32::MAX));
+        failures / total
+    }
+
+    #[must_use]
+    pub fn probe_due(&self, now_ms: u64) -> bool {
+        self.state(now_ms) == CircuitState::HalfOpen && !self.probe_in_flight
+    }
+
+    /// In the half-open state only the first caller is admitted, as the probe.
+    pub fn allow_request(&mut self, now_ms: u64) -> bool {
+        match self.state(now_ms) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen if self.probe_in_flight => false,
+            CircuitState::HalfOpen => {
+                self.probe_in_flight = true;
+                true
+            }
+        }
+    }
+
+    /// Records a successful response, returning `true` if it closed the circuit.
+    pub fn record_success(&mut self) -> bool {
+        if self.opened_at_ms.take().is_some() {
+            self.probe_in_flight = false;
+            self.outcomes.clear();
+            return true;
+        }
+        self.push_outcome(false);
+        false
+    }
+
+    /// Records a failed response, returning `true` if it opened the circuit.
+    pub fn record_failure(&mut self, now_ms: u64) -> bool {
+        if self.opened_at_ms.is_some() {
+            self.opened_at_ms = Some(now_ms);
+            self.probe_in_flight = false;
+            return false;
+        }
+
+        self.push_outcome(true);
+        if self.outcomes.len() >= self.min_samples
+            && self.failure_rate() >= self.failure_rate_threshold
+        {
+            self.opened_at_ms = Some(now_ms);
+            self.outcomes.clear();
+            return true;
+        }
+        false
+    }
+
+    fn push_outcome(&mut self, failed: bool) {
+        self.outcomes.push_back(failed);
+        while self.outcomes.len() > self.window {
+            self.outcomes.pop_front();
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -1601,6 +3206,18 @@ pub struct OfflineStore {
     pub last_sync_ms: Option<u64>,
     pub last_cases_refresh_ms: Option<u64>,
     pub schema_version: u32,
+    #[serde(default)]
+    pub viewed_case_ids: Vec<String>,
+    #[serde(default)]
+    pub last_session_ms: Option<u64>,
+    #[serde(default)]
+    pub last_push_prompt_ms: Option<u64>,
+    #[serde(default)]
+    pub push_denied_at_ms: Option<u64>,
+    #[serde(default)]
+    pub draft: Option<CaseDraft>,
+    #[serde(default)]
+    audit_log: Vec<AuditEntry>,
 }
 
 impl OfflineStore {
@@ -1614,6 +3231,12 @@ impl OfflineStore {
             last_sync_ms: None,
             last_cases_refresh_ms: None,
             schema_version: Self::CURRENT_SCHEMA_VERSION,
+            viewed_case_ids: Vec::new(),
+            last_session_ms: None,
+            last_push_prompt_ms: None,
+            push_denied_at_ms: None,
+            draft: None,
+            audit_log: Vec::new(),
         }
     }
 
@@ -1623,6 +3246,7 @@ impl OfflineStore {
             if self.pending_local_cases.len() >= MAX_PENDING_LOCAL_CASES {
                 return Err(OutboxError::Full {
                     max: MAX_PENDING_LOCAL_CASES,
+                    kind: OutboxKind::LocalCases,
                 });
             }
         }
@@ -1635,12 +3259,21 @@ impl OfflineStore {
         Ok(())
     }
 
+    /// The unfinished claim or transition queued for `case_id`, if any.
+    #[must_use]
+    pub fn queued_case_action(&self, case_id: &CaseId) -> Option<&OutboxEntry> {
+        self.outbox
+            .iter()
+            .find(|e| e.intent.case_id() == Some(case_id) && !e.retry_state.is_terminal())
+    }
+
     pub fn push_outbox(&mut self, entry: OutboxEntry) -> Result<(), OutboxError> {
         if self.outbox.len() >= MAX_OUTBOX_ENTRIES {
             self.cleanup_completed_outbox();
             if self.outbox.len() >= MAX_OUTBOX_ENTRIES {
                 return Err(OutboxError::Full {
                     max: MAX_OUTBOX_ENTRIES,
+                    kind: OutboxKind::OutboxEntries,
                 });
             }
         }
@@ -1671,22 +3304,278 @@ impl OfflineStore {
         self.pending_local_cases.iter_mut().find(|c| &c.local_id == local_id)
     }
 
-    pub fn mark_entry_completed(&mut self, op_id: &OpId) {
-        if let Some(entry) = self.get_entry_mut(op_id) {
-            entry.mark_completed();
+    #[must_use]
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit_log
+    }
+
+    fn transition_entry(&mut self, op_id: &OpId, apply: impl FnOnce(&mut OutboxEntry)) {
+        let entry = match self.get_entry_mut(op_id) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let from_state = entry.retry_state;
+        apply(entry);
+        if entry.retry_state == from_state {
+            return;
+        }
+
+        let to_state = entry.retry_state;
+        let failed = matches!(
+            to_state,
+            RetryState::Failed | RetryState::PermanentlyFailed | RetryState::RateLimited
+        );
+        let record = AuditEntry {
+            op_id: op_id.clone(),
+            from_state,
+            to_state,
+            at: entry.updated_at,
+            error_code: entry
+                .last_error
+                .as_ref()
+                .filter(|_| failed)
+                .map(|e| e.code.clone()),
+        };
+
+        self.audit_log.push(record);
+        if self.audit_log.len() > MAX_AUDIT_LOG_ENTRIES {
+            let excess = self.audit_log.len() - MAX_AUDIT_LOG_ENTRIES;
+            self.audit_log.drain(..excess);
         }
     }
 
+    pub fn mark_entry_in_flight(&mut self, op_id: &OpId) {
+        self.transition_entry(op_id, OutboxEntry::mark_in_flight);
+    }
+
+    pub fn mark_entry_completed(&mut self, op_id: &OpId) {
+        self.transition_entry(op_id, OutboxEntry::mark_completed);
+    }
+
     pub fn mark_entry_failed(&mut self, op_id: &OpId, error: OutboxEntryError) {
-        if let Some(entry) = self.get_entry_mut(op_id) {
-            entry.mark_failed(error);
-        }
+        self.mark_entry_failed_with_strategy(op_id, error, BackoffStrategy::default());
+    }
+
+    pub fn mark_entry_failed_with_strategy(
+        &mut self,
+        op_id: &OpId,
+        error: OutboxEntryError,
+        strategy: BackoffStrategy,
+    ) {
+        self.transition_entry(op_id, |entry| {
+            entry.mark_failed_with_strategy(error, strategy, time_seed());
+        });
     }
 
     pub fn mark_entry_permanently_failed(&mut self, op_id: &OpId, error: OutboxEntryError) {
-        if let Some(entry) = self.get_entry_mut(op_id) {
-            entry.mark_permanently_failed(error);
+        self.transition_entry(op_id, |entry| entry.mark_permanently_failed(error));
+    }
+
+    pub fn mark_entry_rate_limited(&mut self, op_id: &OpId, retry_after_ms: u64) {
+        self.transition_entry(op_id, |entry| entry.mark_rate_limited(retry_after_ms));
+    }
+
+    pub fn mark_entry_timed_out(
+        &mut self,
+        op_id: &OpId,
+        consecutive_timeouts: u32,
+        threshold: u32,
+        strategy: BackoffStrategy,
+    ) {
+        self.transition_entry(op_id, |entry| {
+            entry.mark_timed_out(consecutive_timeouts, threshold, strategy)
+        });
+    }
+
+    pub fn retry_failed_entries(&mut self) -> usize {
+        let failed: Vec<OpId> = self
+            .outbox
+            .iter()
+            .filter(|e| e.retry_state == RetryState::Failed)
+            .map(|e| e.op_id.clone())
+            .collect();
+        for op_id in &failed {
+            self.transition_entry(op_id, OutboxEntry::retry_now);
+        }
+        failed.len()
+    }
+
+    /// Clears accumulated backoff on `Failed` entries whose last failure is
+    /// no older than `since_ms`. Rate-limited entries keep the server's delay.
+    pub fn reset_failed_backoff(&mut self, since_ms: u64) -> usize {
+        let mut reset = 0;
+        for entry in &mut self.outbox {
+            if entry.retry_state == RetryState::Failed && entry.updated_at.0 >= since_ms {
+                entry.reset_backoff();
+                reset += 1;
+            }
+        }
+        reset
+    }
+
+    pub fn repair_missing_outbox_entries(&mut self) {
+        let missing: Vec<OutboxEntry> = self
+            .pending_local_cases
+            .iter()
+            .filter(|c| c.status.is_pending() && c.server_id.is_none())
+            .filter(|c| {
+                !self.outbox.iter().any(|e| {
+                    !e.is_completed()
+                        && matches!(&e.intent, OutboxIntent::CreateCase { local_id, .. } if local_id == &c.local_id)
+                })
+            })
+            .map(|c| {
+                OutboxEntry::new(OutboxIntent::CreateCase {
+                    local_id: c.local_id.clone(),
+                    location: c.location,
+                    description: c.description.clone(),
+                    landmark_hint: c.landmark_hint.clone(),
+                    wound_severity: c.wound_severity,
+                    has_photo: c.photo_data.is_some(),
+                    created_at_ms_utc: c.created_at_ms_utc,
+                })
+                .with_idempotency_key(IdempotencyKey::new(c.local_id.0.clone()))
+            })
+            .collect();
+
+        for entry in missing {
+            if self.push_outbox(entry).is_err() {
+                break;
+            }
+        }
+    }
+
+    pub fn dedup_local_cases(&mut self) -> usize {
+        keep_newest_by_id(
+            &mut self.pending_local_cases,
+            |c| &c.local_id,
+            |c| c.updated_at_ms_utc,
+        )
+    }
+
+    pub fn dedup_outbox(&mut self) -> usize {
+        keep_newest_by_id(&mut self.outbox, |e| &e.op_id, |e| e.updated_at)
+    }
+
+    pub fn promote_entry(&mut self, op_id: &OpId) -> Result<(), OutboxError> {
+        let index = self
+            .outbox
+            .iter()
+            .position(|e| &e.op_id == op_id)
+            .ok_or_else(|| OutboxError::NotFound(op_id.0.clone()))?;
+
+        let entry = &self.outbox[index];
+        if entry.is_completed() || entry.is_permanently_failed() || entry.is_in_flight() {
+            return Err(OutboxError::InvalidState);
+        }
+
+        let mut entry = self.outbox.remove(index);
+        entry.next_retry_at = None;
+        self.outbox.insert(0, entry);
+        Ok(())
+    }
+
+    /// Revives permanently failed entries and local cases. Those rejected with
+    /// a 4xx are left alone unless `include_client_errors` is set, since
+    /// resending the same request will usually be rejected again.
+    pub fn revive_permanently_failed(&mut self, include_client_errors: bool) -> (usize, usize) {
+        let revivable: Vec<OpId> = self
+            .outbox
+            .iter()
+            .filter(|e| e.is_permanently_failed())
+            .filter(|e| include_client_errors || !e.failed_with_client_error())
+            .map(|e| e.op_id.clone())
+            .collect();
+        let blocked_locals: HashSet<LocalOpId> = self
+            .outbox
+            .iter()
+            .filter(|e| e.is_permanently_failed() && !revivable.contains(&e.op_id))
+            .filter_map(|e| e.intent.local_id().cloned())
+            .collect();
+
+        for op_id in &revivable {
+            self.transition_entry(op_id, OutboxEntry::revive);
+        }
+
+        let mut cases = 0;
+        for case in &mut self.pending_local_cases {
+            if case.status == LocalCaseStatus::PermanentlyFailed
+                && !blocked_locals.contains(&case.local_id)
+            {
+                case.status = LocalCaseStatus::PendingUpload;
+                case.retry_count = 0;
+                case.sync_error = None;
+                case.updated_at_ms_utc = UnixTimeMs::now();
+                cases += 1;
+            }
+        }
+
+        (revivable.len(), cases)
+    }
+
+    /// Drops a permanently failed entry the user has acknowledged.
+    pub fn dismiss_failed_entry(&mut self, op_id: &OpId) -> Result<OutboxEntry, OutboxError> {
+        self.remove_failed_entry(op_id, RemovalReason::Dismissed)
+    }
+
+    /// Drops a failed or permanently failed entry without retrying it.
+    pub fn discard_entry(&mut self, op_id: &OpId) -> Result<(), OutboxError> {
+        self.remove_failed_entry(op_id, RemovalReason::Discarded)
+            .map(|_| ())
+    }
+
+    /// Refused while other work for the same local case is in flight.
+    pub(crate) fn remove_failed_entry(
+        &mut self,
+        op_id: &OpId,
+        reason: RemovalReason,
+    ) -> Result<OutboxEntry, OutboxError> {
+        let entry = self
+            .outbox
+            .iter()
+            .find(|e| &e.op_id == op_id)
+            .ok_or_else(|| OutboxError::NotFound(op_id.0.clone()))?;
+
+        if !reason.accepts(entry.retry_state) || self.has_in_flight_sibling(entry) {
+            return Err(OutboxError::InvalidState);
+        }
+
+        // A failed create takes its local case and photo upload with it.
+        let removed: Vec<OpId> = match &entry.intent {
+            OutboxIntent::CreateCase { local_id, .. } => {
+                let local_id = local_id.clone();
+                self.pending_local_cases.retain(|c| c.local_id != local_id);
+                self.outbox
+                    .iter()
+                    .filter(|e| e.intent.local_id() == Some(&local_id))
+                    .map(|e| e.op_id.clone())
+                    .collect()
+            }
+            _ => vec![op_id.clone()],
+        };
+
+        for id in &removed {
+            self.transition_entry(id, OutboxEntry::mark_discarded);
         }
+        let index = self
+            .outbox
+            .iter()
+            .position(|e| &e.op_id == op_id)
+            .ok_or_else(|| OutboxError::NotFound(op_id.0.clone()))?;
+        let entry = self.outbox.remove(index);
+        self.outbox.retain(|e| !removed.contains(&e.op_id));
+
+        Ok(entry)
+    }
+
+    #[must_use]
+    pub fn has_in_flight_sibling(&self, entry: &OutboxEntry) -> bool {
+        entry.intent.local_id().is_some_and(|local_id| {
+            self.outbox.iter().any(|e| {
+                e.op_id != entry.op_id && e.is_in_flight() && e.intent.local_id() == Some(local_id)
+            })
+        })
     }
 
     #[must_use]
@@ -1748,35 +3637,146 @@ impl OfflineStore {
     pub fn update_last_refresh(&mut self) {
         self.last_cases_refresh_ms = Some(get_current_time_ms());
     }
-}
 
-pub struct Model {
-    pub state: AppState,
+    #[must_use]
+    pub fn debug_snapshot(&self, now_ms: u64) -> Vec<OutboxDebugEntry> {
+        self.outbox
+            .iter()
+            .map(|entry| OutboxDebugEntry {
+                op_id: entry.op_id.0.clone(),
+                retry_state: entry.retry_state,
+                attempt_count: entry.attempt_count,
+                max_attempts: max_attempts_for(&entry.intent),
+                next_retry_at_ms: entry.next_retry_at.map(|t| t.0),
+                projected_retries_ms: entry.projected_retry_schedule(now_ms),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxDebugEntry {
+    pub op_id: String,
+    pub retry_state: RetryState,
+    pub attempt_count: u32,
+    pub max_attempts: u32,
+    pub next_retry_at_ms: Option<u64>,
+    pub projected_retries_ms: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutboxTuning {
+    pub reset_siblings_on_success: bool,
+    pub sibling_reset_window_ms: u64,
+    pub backoff_strategy: BackoffStrategy,
+}
+
+impl Default for OutboxTuning {
+    fn default() -> Self {
+        Self {
+            reset_siblings_on_success: false,
+            sibling_reset_window_ms: OUTBOX_SIBLING_RESET_WINDOW_MS,
+            backoff_strategy: BackoffStrategy::default(),
+        }
+    }
+}
+
+pub struct Model {
+    pub state: AppState,
     pub user_id: Option<UserId>,
     pub jwt_token: Option<String>,
+    /// The shell has been asked for a new token and none has arrived yet.
+    pub token_refresh_pending: bool,
+    pub refresh_deferred_for_token: bool,
     pub area_center: Option<ValidatedCoordinate>,
     pub area_radius_m: u32,
     pub map_center: Option<ValidatedCoordinate>,
     pub map_zoom: f64,
+    pub last_map_move_at_ms: Option<u64>,
+    pub map_settle_debounce_ms: u64,
+    /// Once the settled map center is this far from `area_center`, the area
+    /// moves there and is refreshed. `None` leaves the area alone.
+    pub map_drift_refresh_m: Option<u32>,
     pub feed_view: FeedView,
+    pub status_filter: Option<CaseStatus>,
+    pub list_sort: ListSortMode,
+    pub distance_unit: DistanceUnit,
+    pub store_key_ids: Vec<String>,
+    pub clustering_enabled: bool,
+    pub outbox_capacity_reached: Option<OutboxKind>,
+    pub capture_config: CaptureConfig,
     pub cases: Vec<ServerCase>,
     pub cases_cursor: Option<String>,
+    /// `ETag` of the last full first-page refresh, sent back as `If-None-Match`.
+    pub cases_etag: Option<String>,
     pub selected_case_id: Option<CaseId>,
     pub offline_store: OfflineStore,
     pub network_online: bool,
     pub is_refreshing: bool,
+    pub refresh_generation: u64,
     pub is_loading: bool,
     pub push_permission_granted: bool,
     pub push_token: Option<String>,
     pub staged_photo: Option<StagedPhoto>,
     pub yolo_detector: Option<crate::vision::YoloDetector>,
+    pub photo_cache: Option<ProcessedPhotoCache>,
     pub active_error: Option<AppError>,
     pub active_toast: Option<ToastMessage>,
+    /// Translations for view and toast text; English when unset.
+    pub locale: Locale,
     pub pending_claims: HashMap<CaseId, PendingClaim>,
     pub pending_mutations: HashMap<String, OptimisticMutation>,
     pub view_timestamp_ms: u64,
     pub location_permission_state: PermissionState,
     pub camera_permission_state: PermissionState,
+    pub optimistic_mutation_timeout_ms: u64,
+    pub store_dirty: bool,
+    pub last_persisted_at_ms: Option<u64>,
+    pub persist_debounce_ms: u64,
+    pub timeout_tracker: TimeoutTracker,
+    pub circuit_breaker: CircuitBreaker,
+    pub outbox_tuning: OutboxTuning,
+    pub slow_link_timeout_threshold: u32,
+    pub recenter_on_select: bool,
+    pub map_follow_mode: MapFollowMode,
+    pub restore_before_refresh: bool,
+    pub restore_in_flight: bool,
+    pub refresh_after_restore: bool,
+    pub viewed_case_ids: HashSet<String>,
+    pub previous_session_ms: Option<u64>,
+    pub detector_config: crate::vision::DetectorConfig,
+    pub min_crop_confidence: f32,
+    pub crop_merge_strategy: crate::image_processing::MergeStrategy,
+    pub crop_padding_fraction: f32,
+    pub image_memory_cap_bytes: usize,
+    pub highlight_radius_m: u32,
+    pub utc_offset_minutes: i32,
+    pub rate_limited_until_ms: HashMap<String, u64>,
+    pub removed_selection_behavior: RemovedSelectionBehavior,
+    pub selected_case_unavailable: bool,
+    pub auto_refresh_interval_ms: Option<u64>,
+    pub foreground_refresh_cooldown_ms: u64,
+    pub location_accuracy_m: Option<f64>,
+    pub min_location_accuracy_m: f64,
+    pub location_smoothing_alpha: f64,
+    pub last_gps_fix: Option<ValidatedCoordinate>,
+    pub last_location_fix_ms: Option<u64>,
+    pub location_jump_candidate: Option<ValidatedCoordinate>,
+    pub location_jump_count: u32,
+    pub list_limit: Option<usize>,
+    pub auto_select_nearest: bool,
+    pub require_resolution_evidence: bool,
+    pub store_restored: bool,
+    pub auto_select_pending: bool,
+    pub max_image_alloc: usize,
+    pub pending_pushes: Vec<PushPayload>,
+    pub cancelled_request_ids: VecDeque<String>,
+    pub logout_confirmation_pending: bool,
+    pub deferred_push_patches: HashMap<String, CasePatch>,
+    pub last_submission: Option<(u64, u64)>,
+    pub radius_tolerance_fraction: f64,
+    pub drop_out_of_radius_cases: bool,
 }
 
 impl Default for Model {
@@ -1785,38 +3785,474 @@ impl Default for Model {
             state: AppState::Loading,
             user_id: None,
             jwt_token: None,
+            token_refresh_pending: false,
+            refresh_deferred_for_token: false,
             area_center: None,
             area_radius_m: DEFAULT_RADIUS_M,
             map_center: None,
             map_zoom: DEFAULT_MAP_ZOOM,
+            last_map_move_at_ms: None,
+            map_settle_debounce_ms: MAP_SETTLE_DEBOUNCE_MS,
+            map_drift_refresh_m: None,
             feed_view: FeedView::default(),
+            status_filter: None,
+            list_sort: ListSortMode::default(),
+            distance_unit: DistanceUnit::default(),
+            store_key_ids: Vec::new(),
+            clustering_enabled: false,
+            outbox_capacity_reached: None,
+            capture_config: CaptureConfig::default(),
             cases: Vec::new(),
             cases_cursor: None,
+            cases_etag: None,
             selected_case_id: None,
             offline_store: OfflineStore::new(),
             network_online: true,
             is_refreshing: false,
+            refresh_generation: 0,
             is_loading: false,
             push_permission_granted: false,
             push_token: None,
             staged_photo: None,
             yolo_detector: None,
+            photo_cache: None,
             active_error: None,
             active_toast: None,
+            locale: Locale::En,
             pending_claims: HashMap::new(),
             pending_mutations: HashMap::new(),
             view_timestamp_ms: get_current_time_ms(),
             location_permission_state: PermissionState::Unknown,
             camera_permission_state: PermissionState::Unknown,
+            optimistic_mutation_timeout_ms: OPTIMISTIC_MUTATION_TIMEOUT_MS,
+            store_dirty: false,
+            last_persisted_at_ms: None,
+            persist_debounce_ms: PERSIST_DEBOUNCE_MS,
+            timeout_tracker: TimeoutTracker::default(),
+            circuit_breaker: CircuitBreaker::default(),
+            outbox_tuning: OutboxTuning::default(),
+            slow_link_timeout_threshold: SLOW_LINK_TIMEOUT_THRESHOLD,
+            recenter_on_select: false,
+            map_follow_mode: MapFollowMode::default(),
+            restore_before_refresh: true,
+            restore_in_flight: false,
+            refresh_after_restore: false,
+            viewed_case_ids: HashSet::new(),
+            previous_session_ms: None,
+            detector_config: crate::vision::DetectorConfig::default(),
+            min_crop_confidence: DEFAULT_MIN_CROP_CONFIDENCE,
+            crop_merge_strategy: crate::image_processing::MergeStrategy::default(),
+            crop_padding_fraction: DEFAULT_CROP_PADDING_FRACTION,
+            image_memory_cap_bytes: MAX_TOTAL_IMAGE_MEMORY_BYTES,
+            highlight_radius_m: DEFAULT_HIGHLIGHT_RADIUS_M,
+            utc_offset_minutes: 0,
+            rate_limited_until_ms: HashMap::new(),
+            removed_selection_behavior: RemovedSelectionBehavior::default(),
+            selected_case_unavailable: false,
+            auto_refresh_interval_ms: None,
+            foreground_refresh_cooldown_ms: DEFAULT_FOREGROUND_REFRESH_COOLDOWN_MS,
+            location_accuracy_m: None,
+            min_location_accuracy_m: MIN_LOCATION_ACCURACY_M,
+            location_smoothing_alpha: LOCATION_SMOOTHING_ALPHA,
+            last_gps_fix: None,
+            last_location_fix_ms: None,
+            location_jump_candidate: None,
+            location_jump_count: 0,
+            list_limit: None,
+            auto_select_nearest: false,
+            require_resolution_evidence: false,
+            store_restored: false,
+            auto_select_pending: false,
+            max_image_alloc: MAX_IMAGE_ALLOC,
+            pending_pushes: Vec::new(),
+            cancelled_request_ids: VecDeque::new(),
+            logout_confirmation_pending: false,
+            deferred_push_patches: HashMap::new(),
+            last_submission: None,
+            radius_tolerance_fraction: DEFAULT_RADIUS_TOLERANCE_FRACTION,
+            drop_out_of_radius_cases: true,
         }
     }
 }
 
 impl Model {
+    pub fn mark_case_viewed(&mut self, case_id: &str) -> bool {
+        if !self.viewed_case_ids.insert(case_id.to_string()) {
+            return false;
+        }
+
+        let order = &mut self.offline_store.viewed_case_ids;
+        order.push(case_id.to_string());
+        if order.len() > MAX_VIEWED_CASE_IDS {
+            let overflow = order.len() - MAX_VIEWED_CASE_IDS;
+            for evicted in order.drain(..overflow) {
+                self.viewed_case_ids.remove(&evicted);
+            }
+        }
+
+        true
+    }
+
+    #[must_use]
+    pub fn is_case_new(&self, case_id: &str, created_at_ms: u64) -> bool {
+        if self.viewed_case_ids.contains(case_id) {
+            return false;
+        }
+
+        // Without a previous session there is no baseline, so a first launch
+        // doesn't badge every case as new.
+        self.previous_session_ms
+            .is_some_and(|session_ms| created_at_ms > session_ms)
+    }
+
+    pub fn apply_server_case_update(&mut self, requested_id: &str, updated: ServerCase) -> bool {
+        if updated.id.0 == requested_id {
+            if let Some(case) = self.cases.iter_mut().find(|c| c.id.0 == requested_id) {
+                case.merge_from(updated);
+            }
+            return false;
+        }
+
+        let old_index = self.cases.iter().position(|c| c.id.0 == requested_id);
+        if let Some(index) = old_index {
+            self.cases.remove(index);
+        }
+
+        if self
+            .selected_case_id
+            .as_ref()
+            .is_some_and(|id| id.0 == requested_id)
+        {
+            self.selected_case_id = Some(updated.id.clone());
+        }
+        self.rekey_pending_case_state(requested_id, &updated.id);
+
+        if let Some(case) = self.cases.iter_mut().find(|c| c.id == updated.id) {
+            case.merge_from(updated);
+        } else {
+            let index = old_index.unwrap_or(self.cases.len()).min(self.cases.len());
+            self.cases.insert(index, updated);
+        }
+
+        true
+    }
+
+    fn rekey_pending_case_state(&mut self, old_id: &str, new_id: &CaseId) {
+        if let Some(mut claim) = self.pending_claims.remove(&CaseId::new(old_id)) {
+            claim.case_id = new_id.clone();
+            self.pending_claims.entry(new_id.clone()).or_insert(claim);
+        }
+        for mutation in self.pending_mutations.values_mut() {
+            if mutation.case_id.0 == old_id {
+                mutation.case_id = new_id.clone();
+            }
+        }
+        if let Some(patch) = self.deferred_push_patches.remove(old_id) {
+            let merged = match self.deferred_push_patches.remove(&new_id.0) {
+                Some(existing) => patch.merged_with(existing),
+                None => patch,
+            };
+            self.deferred_push_patches.insert(new_id.0.clone(), merged);
+        }
+    }
+
+    #[must_use]
+    pub fn case_distance_m(&self, case: &ServerCase) -> Option<f64> {
+        match self.area_center {
+            Some(center) => Some(case_distance(case, center)),
+            None => case.distance_meters.filter(|d| d.is_finite()),
+        }
+    }
+
+    /// Upserts a page of cases by id: an existing case is replaced only when
+    /// the incoming copy is newer, and unseen ids are appended in order.
+    /// Returns how many incoming cases repeated an id already present.
+    pub fn merge_cases_page(&mut self, incoming: Vec<ServerCase>) -> usize {
+        let mut positions: HashMap<CaseId, usize> = self
+            .cases
+            .iter()
+            .enumerate()
+            .map(|(i, case)| (case.id.clone(), i))
+            .collect();
+
+        let mut duplicates = 0;
+        for case in incoming {
+            match positions.get(&case.id) {
+                Some(&i) => {
+                    duplicates += 1;
+                    if case.updated_at_ms_utc > self.cases[i].updated_at_ms_utc {
+                        self.cases[i] = case;
+                    }
+                }
+                None => {
+                    positions.insert(case.id.clone(), self.cases.len());
+                    self.cases.push(case);
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Whether `case` lies beyond the area radius plus the configured
+    /// tolerance. Cases with no known distance are never out of radius.
+    #[must_use]
+    pub fn is_out_of_radius(&self, case: &ServerCase) -> bool {
+        self.case_distance_m(case)
+            .is_some_and(|d| exceeds_radius(d, self.area_radius_m, self.radius_tolerance_fraction))
+    }
+
+    /// Out-of-radius cases that may be dropped. Cases assigned to this user
+    /// are kept even if they drift outside, as are cases mid-mutation.
+    fn is_droppable_out_of_radius(&self, case: &ServerCase) -> bool {
+        self.is_out_of_radius(case)
+            && !self.is_assigned_to_me(case)
+            && !self.has_pending_mutation(&case.id.0)
+    }
+
+    /// Counts cases the server returned out of radius, removing them when
+    /// `drop_out_of_radius_cases` is set.
+    pub fn screen_out_of_radius(&self, cases: &mut Vec<ServerCase>) -> usize {
+        let count = cases.iter().filter(|c| self.is_out_of_radius(c)).count();
+        if self.drop_out_of_radius_cases && count > 0 {
+            cases.retain(|c| !self.is_droppable_out_of_radius(c));
+        }
+        count
+    }
+
+    /// Removes cached cases that are out of radius when
+    /// `drop_out_of_radius_cases` is set.
+    pub fn prune_out_of_radius(&mut self) -> usize {
+        if !self.drop_out_of_radius_cases {
+            return 0;
+        }
+
+        let before = self.cases.len();
+        let keep: Vec<bool> = self
+            .cases
+            .iter()
+            .map(|case| !self.is_droppable_out_of_radius(case))
+            .collect();
+        let mut flags = keep.into_iter();
+        self.cases.retain(|_| flags.next().unwrap_or(true));
+        before - self.cases.len()
+    }
+
+    #[must_use]
+    pub fn pins_packed(&self) -> Vec<u8> {
+        pack_pins(&app::App::build_case_pins(self))
+    }
+
+    #[must_use]
+    pub fn status_counts(&self, include_terminal: bool) -> HashMap<CaseStatus, usize> {
+        let mut counts = HashMap::new();
+
+        for case in &self.cases {
+            if include_terminal || !case.status.is_terminal() {
+                *counts.entry(case.status).or_insert(0) += 1;
+            }
+        }
+
+        let pending_locals = self
+            .offline_store
+            .pending_local_cases
+            .iter()
+            .filter(|c| c.status.is_pending())
+            .count();
+        if pending_locals > 0 {
+            *counts.entry(CaseStatus::Pending).or_insert(0) += pending_locals;
+        }
+
+        counts
+    }
+
+    #[must_use]
+    pub fn severity_counts(&self) -> [usize; 6] {
+        let radius_m = f64::from(self.area_radius_m);
+        let mut counts = [0usize; 6];
+
+        for case in &self.cases {
+            match self.case_distance_m(case) {
+                Some(distance) if distance <= radius_m => {}
+                _ => continue,
+            }
+
+            let index = match case.wound_severity {
+                Some(level @ 1..=5) => usize::from(level),
+                _ => 0,
+            };
+            counts[index] += 1;
+        }
+
+        counts
+    }
+
+    #[must_use]
+    pub fn total_image_memory_bytes(&self) -> usize {
+        let staged = self
+            .staged_photo
+            .as_ref()
+            .map_or(0, StagedPhoto::memory_bytes);
+        let draft = self
+            .draft()
+            .and_then(|d| d.photo_data.as_ref())
+            .map_or(0, Vec::len);
+        let pending: usize = self
+            .offline_store
+            .pending_local_cases
+            .iter()
+            .filter_map(|c| c.photo_data.as_ref())
+            .map(Vec::len)
+            .sum();
+        staged + draft + pending
+    }
+
+    pub fn reserve_image_memory(
+        &mut self,
+        incoming: usize,
+        released: usize,
+    ) -> Result<(), AppError> {
+        let projected = |model: &Self| {
+            model
+                .total_image_memory_bytes()
+                .saturating_sub(released)
+                .saturating_add(incoming)
+        };
+
+        if projected(self) <= self.image_memory_cap_bytes {
+            return Ok(());
+        }
+
+        self.offline_store
+            .evict_synced_cases(self.offline_store.pending_local_cases.len());
+
+        let needed = projected(self);
+        if needed <= self.image_memory_cap_bytes {
+            return Ok(());
+        }
+
+        Err(AppError::new(
+            ErrorKind::QuotaExceeded,
+            "Too many photos are waiting to sync. Remove a pending photo or wait for sync to finish.",
+        )
+        .with_context("needed_bytes", needed.to_string())
+        .with_context("cap_bytes", self.image_memory_cap_bytes.to_string())
+        .with_context(
+            "suggestion",
+            "Connect to the network to sync pending cases, or discard failed cases",
+        ))
+    }
+
+    #[must_use]
+    pub fn draft(&self) -> Option<&CaseDraft> {
+        self.offline_store.draft.as_ref()
+    }
+
+    pub fn set_draft(&mut self, draft: Option<CaseDraft>) {
+        self.offline_store.draft = draft;
+        self.mark_store_dirty();
+    }
+
+    pub fn restore_viewed_cases(&mut self) {
+        self.viewed_case_ids = self.offline_store.viewed_case_ids.iter().cloned().collect();
+        self.previous_session_ms = self.offline_store.last_session_ms;
+    }
+
+    #[must_use]
+    pub fn should_prompt_push(&self, now_ms: u64) -> bool {
+        if self.push_permission_granted {
+            return false;
+        }
+
+        match self.offline_store.push_denied_at_ms {
+            Some(denied_at) => now_ms.saturating_sub(denied_at) >= PUSH_PROMPT_COOLDOWN_MS,
+            None => true,
+        }
+    }
+
+    pub fn record_push_prompt(&mut self, now_ms: u64) {
+        self.offline_store.last_push_prompt_ms = Some(now_ms);
+        self.mark_store_dirty();
+    }
+
+    #[must_use]
+    pub fn has_resolution_evidence(&self, notes: Option<&str>) -> bool {
+        if !self.require_resolution_evidence {
+            return true;
+        }
+        self.staged_photo.is_some() || notes.is_some_and(|n| !n.trim().is_empty())
+    }
+
+    pub fn accept_location_fix(
+        &mut self,
+        fix: ValidatedCoordinate,
+        now_ms: u64,
+    ) -> Option<ValidatedCoordinate> {
+        // Compare against the previous GPS fix, not the area center: a dropped pin or a
+        // settled map also moves the center, and the first fix of a session is always taken.
+        let prev = match self.last_gps_fix {
+            Some(prev) => prev,
+            None => {
+                self.last_gps_fix = Some(fix);
+                self.last_location_fix_ms = Some(now_ms);
+                return Some(fix);
+            }
+        };
+
+        let elapsed_ms = now_ms.saturating_sub(self.last_location_fix_ms.unwrap_or(now_ms));
+        let elapsed_s = f64::from(u32::try_from(elapsed_ms / 1000).unwrap_or(u32::MAX)).max(1.0);
+        let distance = prev.distance_to(fix);
+        let implausible =
+            distance > LOCATION_JUMP_MIN_M && distance / elapsed_s > MAX_PLAUSIBLE_SPEED_MPS;
+
+        if implausible {
+            let persists = self
+                .location_jump_candidate
+                .is_some_and(|candidate| candidate.distance_to(fix) <= LOCATION_JUMP_MIN_M);
+            self.location_jump_count = if persists {
+                self.location_jump_count + 1
+            } else {
+                1
+            };
+            self.location_jump_candidate = Some(fix);
+
+            if self.location_jump_count < LOCATION_JUMP_CONFIRMATIONS {
+                return None;
+            }
+
+            self.location_jump_candidate = None;
+            self.location_jump_count = 0;
+            self.last_gps_fix = Some(fix);
+            self.last_location_fix_ms = Some(now_ms);
+            return Some(fix);
+        }
+
+        self.location_jump_candidate = None;
+        self.location_jump_count = 0;
+        let smoothed = smooth_location(Some(prev), fix, self.location_smoothing_alpha);
+        self.last_gps_fix = Some(smoothed);
+        self.last_location_fix_ms = Some(now_ms);
+        Some(smoothed)
+    }
+
     pub fn update_timestamp(&mut self) {
         self.view_timestamp_ms = get_current_time_ms();
     }
 
+    #[must_use]
+    pub fn map_settle_due(&self, now_ms: u64) -> bool {
+        self.last_map_move_at_ms
+            .is_some_and(|t| now_ms.saturating_sub(t) >= self.map_settle_debounce_ms)
+    }
+
+    /// The settled map center when it has drifted past `map_drift_refresh_m`
+    /// from the current area.
+    #[must_use]
+    pub fn drifted_map_center(&self) -> Option<ValidatedCoordinate> {
+        let threshold = self.map_drift_refresh_m?;
+        let (area, map) = (self.area_center?, self.map_center?);
+        (haversine_distance(area, map) > f64::from(threshold)).then_some(map)
+    }
+
     pub fn set_error(&mut self, error: AppError) {
         self.active_error = Some(error);
     }
@@ -1829,15 +4265,92 @@ impl Model {
         self.active_toast = Some(ToastMessage::new(message, kind));
     }
 
+    pub fn show_catalog_toast(&mut self, key: ToastKey, kind: ToastKind) {
+        let message = self.catalog().toast(key);
+        self.show_toast(message, kind);
+    }
+
+    pub fn show_rate_limited_toast(&mut self, seconds: u64) {
+        let message = self
+            .catalog()
+            .toast(ToastKey::RateLimited)
+            .replace("{seconds}", &seconds.to_string());
+        self.show_toast(message, ToastKind::Warning);
+    }
+
+    #[must_use]
+    pub fn list_showing_text(&self, shown: usize, total: usize) -> String {
+        self.catalog()
+            .toast(ToastKey::ListShowing)
+            .replace("{shown}", &shown.to_string())
+            .replace("{total}", &total.to_string())
+    }
+
+    #[must_use]
+    pub fn catalog(&self) -> &dyn MessageCatalog {
+        &self.locale
+    }
+
     pub fn clear_toast(&mut self) {
         self.active_toast = None;
     }
 
+    pub fn set_rate_limited(&mut self, endpoint: &str, now_ms: u64, retry_after_ms: u64) {
+        let until = now_ms.saturating_add(retry_after_ms);
+        let entry = self
+            .rate_limited_until_ms
+            .entry(endpoint.to_string())
+            .or_insert(until);
+        *entry = (*entry).max(until);
+    }
+
+    #[must_use]
+    pub fn rate_limit_remaining_ms(&self, endpoint: &str, now_ms: u64) -> Option<u64> {
+        self.rate_limited_until_ms
+            .get(endpoint)
+            .filter(|&&until| until > now_ms)
+            .map(|until| until - now_ms)
+    }
+
+    pub fn mark_store_dirty(&mut self) {
+        self.store_dirty = true;
+    }
+
+    pub fn mark_store_persisted(&mut self, now_ms: u64) {
+        self.store_dirty = false;
+        self.last_persisted_at_ms = Some(now_ms);
+    }
+
+    #[must_use]
+    pub fn should_persist(&self, now_ms: u64) -> bool {
+        self.store_dirty
+            && self
+                .last_persisted_at_ms
+                .is_none_or(|t| now_ms.saturating_sub(t) >= self.persist_debounce_ms)
+    }
+
     #[must_use]
     pub fn is_authenticated(&self) -> bool {
         self.user_id.is_some()
     }
 
+    /// Tokens without a readable `exp` claim are assumed to be fine.
+    #[must_use]
+    pub fn token_is_expiring(&self, now_ms: u64, skew_ms: u64) -> bool {
+        self.jwt_token
+            .as_deref()
+            .and_then(jwt_expiry_ms)
+            .is_some_and(|exp_ms| now_ms.saturating_add(skew_ms) >= exp_ms)
+    }
+
+    pub fn defer_for_token_refresh(&mut self, now_ms: u64) -> bool {
+        if !self.token_is_expiring(now_ms, JWT_EXPIRY_SKEW_MS) {
+            return false;
+        }
+        self.token_refresh_pending = true;
+        true
+    }
+
     #[must_use]
     pub fn can_claim_case(&self, case: &ServerCase) -> bool {
         case.status.is_claimable()
@@ -1845,6 +4358,25 @@ impl Model {
             && case.assigned_rescuer_id.is_none()
     }
 
+    #[must_use]
+    pub fn is_assigned_to_me(&self, case: &ServerCase) -> bool {
+        self.user_id.is_some() && case.assigned_rescuer_id == self.user_id
+    }
+
+    pub fn reconcile_own_claim(&mut self, case_id: &CaseId) -> bool {
+        let owned = self
+            .cases
+            .iter()
+            .any(|c| &c.id == case_id && !c.status.is_terminal() && self.is_assigned_to_me(c));
+        if !owned {
+            return false;
+        }
+
+        self.pending_claims.remove(case_id);
+        self.pending_mutations.retain(|_, m| &m.case_id != case_id);
+        true
+    }
+
     pub fn store_optimistic_mutation(
         &mut self,
         case_id: CaseId,
@@ -1863,57 +4395,856 @@ impl Model {
             if let Some(case) = self.cases.iter_mut().find(|c| c.id == mutation.case_id) {
                 case.status = mutation.original_status;
                 case.assigned_rescuer_id = mutation.original_assignee;
+                if mutation.original_status != CaseStatus::Resolved {
+                    case.resolution_outcome = None;
+                }
                 return true;
             }
         }
         false
     }
 
+    #[must_use]
+    pub fn has_pending_mutation(&self, case_id: &str) -> bool {
+        self.pending_claims.keys().any(|id| id.0 == case_id)
+            || self
+                .pending_mutations
+                .values()
+                .any(|m| m.case_id.0 == case_id)
+    }
+
+    /// Holds the patch back while a mutation on the case is in flight.
+    pub fn apply_push_update(&mut self, case_id: &str, patch: CasePatch) -> bool {
+        if self.has_pending_mutation(case_id) {
+            let merged = match self.deferred_push_patches.remove(case_id) {
+                Some(earlier) => earlier.merged_with(patch),
+                None => patch,
+            };
+            self.deferred_push_patches
+                .insert(case_id.to_string(), merged);
+            return false;
+        }
+
+        if let Some(case) = self.cases.iter_mut().find(|c| c.id.0 == case_id) {
+            patch.apply_to(case);
+        }
+        true
+    }
+
+    pub fn apply_deferred_push_patches(&mut self) -> usize {
+        let settled: Vec<String> = self
+            .deferred_push_patches
+            .keys()
+            .filter(|case_id| !self.has_pending_mutation(case_id))
+            .cloned()
+            .collect();
+
+        for case_id in &settled {
+            if let Some(patch) = self.deferred_push_patches.remove(case_id) {
+                if let Some(case) = self.cases.iter_mut().find(|c| &c.id.0 == case_id) {
+                    patch.apply_to(case);
+                }
+            }
+        }
+        settled.len()
+    }
+
     pub fn commit_mutation(&mut self, mutation_id: &str) {
         self.pending_mutations.remove(mutation_id);
     }
 
-    pub fn enforce_collection_limits(&mut self) {
-        while self.offline_store.pending_local_cases.len() > MAX_PENDING_LOCAL_CASES {
-            self.offline_store.evict_synced_cases(1);
-            if self.offline_store.pending_local_cases.len() > MAX_PENDING_LOCAL_CASES {
-                self.offline_store.pending_local_cases.remove(0);
-            }
+    pub fn expire_stale_mutations(&mut self, now_ms: u64) -> Vec<CaseId> {
+        let timeout_ms = self.optimistic_mutation_timeout_ms;
+        let is_stale = |created_at_ms: u64| now_ms.saturating_sub(created_at_ms) > timeout_ms;
+
+        let mut affected: Vec<CaseId> = self
+            .pending_mutations
+            .values()
+            .filter(|m| is_stale(m.created_at_ms))
+            .map(|m| m.case_id.clone())
+            .chain(
+                self.pending_claims
+                    .values()
+                    .filter(|c| is_stale(c.created_at_ms))
+                    .map(|c| c.case_id.clone()),
+            )
+            .collect();
+        affected.sort_by(|a, b| a.0.cmp(&b.0));
+        affected.dedup();
+        // Actions queued while offline settle through the outbox instead.
+        affected.retain(|case_id| self.offline_store.queued_case_action(case_id).is_none());
+
+        for case_id in &affected {
+            self.expire_stale_case_mutations(case_id, &is_stale);
         }
 
-        if self.cases.len() > MAX_CACHED_SERVER_CASES {
-            self.cases.sort_by(|a, b| b.created_at_ms_utc.0.cmp(&a.created_at_ms_utc.0));
-            self.cases.truncate(MAX_CACHED_SERVER_CASES);
+        affected
+    }
+
+    fn expire_stale_case_mutations(&mut self, case_id: &CaseId, is_stale: impl Fn(u64) -> bool) {
+        let server_truth = self
+            .pending_mutations
+            .values()
+            .filter(|m| &m.case_id == case_id)
+            .min_by_key(|m| m.created_at_ms)
+            .map(|m| (m.original_status, m.original_assignee.clone()));
+
+        self.pending_mutations
+            .retain(|_, m| &m.case_id != case_id || !is_stale(m.created_at_ms));
+        if self
+            .pending_claims
+            .get(case_id)
+            .is_some_and(|c| is_stale(c.created_at_ms))
+        {
+            self.pending_claims.remove(case_id);
         }
 
-        self.offline_store.cleanup_completed_outbox();
+        let Some((status, assignee)) = server_truth else {
+            return;
+        };
+        // A newer mutation still awaiting its response keeps the case on screen; it
+        // inherits the server truth so its own rollback lands in the right place.
+        if let Some(fresh) = self
+            .pending_mutations
+            .values_mut()
+            .filter(|m| &m.case_id == case_id)
+            .min_by_key(|m| m.created_at_ms)
+        {
+            fresh.original_status = status;
+            fresh.original_assignee = assignee;
+            return;
+        }
+        if let Some(case) = self.cases.iter_mut().find(|c| &c.id == case_id) {
+            case.status = status;
+            case.assigned_rescuer_id = assignee;
+            if status != CaseStatus::Resolved {
+                case.resolution_outcome = None;
+            }
+        }
     }
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StagedPhoto {
-    pub original_data: Vec<u8>,
-    pub processed_data: Vec<u8>,
-    pub cropped_data: Option<Vec<u8>>,
-    pub width: u32,
-    pub height: u32,
-    pub mime_type: String,
-    pub detection_count: usize,
-    pub top_confidence: f32,
-    pub detections: Vec<crate::vision::Detection>,
-}
+    /// Marks every request still awaiting a response as cancelled, so its
+    /// response can be recognised and dropped once the session is torn down.
+    pub fn cancel_in_flight_requests(&mut self) -> usize {
+        let ids: Vec<String> = self
+            .offline_store
+            .outbox
+            .iter()
+            .filter(|entry| entry.is_in_flight())
+            .map(|entry| entry.op_id.0.clone())
+            .chain(
+                self.offline_store
+                    .pending_local_cases
+                    .iter()
+                    .filter(|case| case.status == LocalCaseStatus::UploadingPhoto)
+                    .map(|case| case.local_id.0.clone()),
+            )
+            .chain(self.pending_mutations.keys().cloned())
+            .collect();
+
+        let count = ids.len();
+        self.cancelled_request_ids.extend(ids);
+        while self.cancelled_request_ids.len() > MAX_CANCELLED_REQUEST_IDS {
+            self.cancelled_request_ids.pop_front();
+        }
+        count
+    }
 
-impl StagedPhoto {
-    #[must_use]
-    pub fn has_detections(&self) -> bool {
-        self.detection_count > 0
+    pub fn take_cancelled_request(&mut self, request_id: &str) -> bool {
+        match self
+            .cancelled_request_ids
+            .iter()
+            .position(|id| id == request_id)
+        {
+            Some(index) => {
+                self.cancelled_request_ids.remove(index);
+                true
+            }
+            None => false,
+        }
     }
 
-    #[must_use]
-    pub fn best_data_for_upload(&self) -> &[u8] {
-        self.cropped_data.as_ref().unwrap_or(&self.processed_data)
+    pub fn rollback_all_mutations(&mut self) -> Vec<CaseId> {
+        let mut affected: Vec<CaseId> = self
+            .pending_mutations
+            .values()
+            .map(|m| m.case_id.clone())
+            .chain(self.pending_claims.keys().cloned())
+            .collect();
+        affected.sort_by(|a, b| a.0.cmp(&b.0));
+        affected.dedup();
+
+        for case_id in &affected {
+            self.rollback_case_to_server_truth(case_id);
+        }
+
+        affected
     }
-}
+
+    fn rollback_case_to_server_truth(&mut self, case_id: &CaseId) {
+        let server_truth = self
+            .pending_mutations
+            .values()
+            .filter(|m| &m.case_id == case_id)
+            .min_by_key(|m| m.created_at_ms)
+            .map(|m| (m.original_status, m.original_assignee.clone()));
+
+        self.pending_mutations.retain(|_, m| &m.case_id != case_id);
+        self.pending_claims.remove(case_id);
+
+        if let Some((status, assignee)) = server_truth {
+            if let Some(case) = self.cases.iter_mut().find(|c| &c.id == case_id) {
+                case.status = status;
+                case.assigned_rescuer_id = assignee;
+                if status != CaseStatus::Resolved {
+                    case.resolution_outcome = None;
+                }
+            }
+        }
+    }
+
+    /// Centers the map between the user's area and the case, zoomed out far
+    /// enough to show both. Returns `false` when either location is unknown.
+    pub fn frame_case(&mut self, case_id: &CaseId) -> bool {
+        let (user_loc, case_loc) = match (self.area_center, self.case_location(&case_id.0)) {
+            (Some(user_loc), Some(case_loc)) => (user_loc, case_loc),
+            _ => return false,
+        };
+        let (center, zoom) = fit_zoom(user_loc, case_loc);
+        self.map_center = Some(center);
+        self.map_zoom = zoom;
+        true
+    }
+
+    pub fn case_location(&self, case_id: &str) -> Option<ValidatedCoordinate> {
+        self.offline_store
+            .pending_local_cases
+            .iter()
+            .find(|c| c.local_id.0 == case_id)
+            .map(|c| c.location)
+            .or_else(|| {
+                self.cases
+                    .iter()
+                    .find(|c| c.id.0 == case_id)
+                    .map(|c| c.location)
+            })
+            .and_then(|loc| loc.validate().ok())
+    }
+
+    #[must_use]
+    pub fn is_duplicate_submission(&self, fingerprint: u64, now_ms: u64) -> bool {
+        match self.last_submission {
+            Some((last_fingerprint, at_ms)) => {
+                last_fingerprint == fingerprint
+                    && now_ms.saturating_sub(at_ms) < DUPLICATE_SUBMISSION_WINDOW_MS
+            }
+            None => false,
+        }
+    }
+
+    #[must_use]
+    pub fn server_reachable(&self, now_ms: u64) -> bool {
+        self.network_online && self.circuit_breaker.state(now_ms) != CircuitState::Open
+    }
+
+    #[must_use]
+    pub fn should_defer_push(&self) -> bool {
+        self.jwt_token.is_none()
+            || matches!(
+                self.state,
+                AppState::Loading | AppState::Unauthenticated | AppState::Authenticating
+            )
+    }
+
+    /// Queues a push for replay once authenticated, returning how many of the
+    /// oldest queued pushes were dropped to stay within `MAX_PENDING_PUSHES`.
+    pub fn defer_push(&mut self, payload: PushPayload) -> usize {
+        self.pending_pushes.push(payload);
+        let overflow = self.pending_pushes.len().saturating_sub(MAX_PENDING_PUSHES);
+        self.pending_pushes.drain(..overflow);
+        overflow
+    }
+
+    #[must_use]
+    pub fn foreground_refresh_due(&self, now_ms: u64) -> bool {
+        if self.is_refreshing {
+            return false;
+        }
+        match self.offline_store.last_cases_refresh_ms {
+            Some(last_ms) => now_ms.saturating_sub(last_ms) >= self.foreground_refresh_cooldown_ms,
+            None => true,
+        }
+    }
+
+    #[must_use]
+    pub fn next_auto_refresh_in_ms(&self, now_ms: u64) -> Option<u64> {
+        let interval_ms = self.auto_refresh_interval_ms.filter(|&ms| ms > 0)?;
+        let last_refresh_ms = match self.offline_store.last_cases_refresh_ms {
+            Some(ms) => ms,
+            None => return Some(0),
+        };
+        Some(
+            last_refresh_ms
+                .saturating_add(interval_ms)
+                .saturating_sub(now_ms),
+        )
+    }
+
+    #[must_use]
+    pub fn offline_readiness(&self, now_ms: u64) -> OfflineReadiness {
+        let area_set = self.area_center.is_some();
+        let cases_cached = !self.cases.is_empty();
+        let cache_age_ms = self
+            .offline_store
+            .last_cases_refresh_ms
+            .map(|ms| now_ms.saturating_sub(ms));
+
+        OfflineReadiness {
+            ready: area_set && cases_cached && self.store_restored,
+            area_set,
+            cases_cached,
+            store_restored: self.store_restored,
+            cache_age_ms,
+        }
+    }
+
+    #[must_use]
+    pub fn passes_status_filter(&self, status: CaseStatus) -> bool {
+        !matches!(self.status_filter, Some(filter) if filter != status)
+    }
+
+    #[must_use]
+    pub fn empty_feed_reason(&self) -> Option<EmptyFeedReason> {
+        if self.area_center.is_none() {
+            return Some(EmptyFeedReason::LocationMissing);
+        }
+
+        let local_count = self.offline_store.pending_local_cases.len();
+        let total = local_count + self.cases.len();
+        let visible_locals = if self.passes_status_filter(CaseStatus::Pending) {
+            local_count
+        } else {
+            0
+        };
+        let visible = visible_locals
+            + self
+                .cases
+                .iter()
+                .filter(|c| self.passes_status_filter(c.status))
+                .count();
+
+        if visible > 0 {
+            None
+        } else if total > 0 {
+            Some(EmptyFeedReason::FilteredOut)
+        } else if !self.network_online && self.offline_store.last_cases_refresh_ms.is_none() {
+            Some(EmptyFeedReason::OfflineNoCache)
+        } else {
+            Some(EmptyFeedReason::NoCasesNearby)
+        }
+    }
+
+    #[must_use]
+    pub fn has_case(&self, case_id: &str) -> bool {
+        self.offline_store
+            .pending_local_cases
+            .iter()
+            .any(|c| c.local_id.0 == case_id)
+            || self.cases.iter().any(|c| c.id.0 == case_id)
+    }
+
+    /// Picks the single most urgent thing the current user should do next:
+    /// finish onboarding, then resume an owned case (furthest along first),
+    /// then retry failed syncs, then claim the nearest open case.
+    #[must_use]
+    pub fn recommended_action(&self) -> Option<RecommendedAction> {
+        if self.state.is_onboarding() {
+            return Some(RecommendedAction::CompleteOnboarding);
+        }
+
+        let urgency = |status: CaseStatus| match status {
+            CaseStatus::Arrived => 0,
+            CaseStatus::EnRoute => 1,
+            _ => 2,
+        };
+        let owned = self
+            .cases
+            .iter()
+            .filter(|c| !c.status.is_terminal() && self.is_assigned_to_me(c))
+            .min_by(|a, b| {
+                urgency(a.status)
+                    .cmp(&urgency(b.status))
+                    .then_with(|| a.id.0.cmp(&b.id.0))
+            });
+        if let Some(case) = owned {
+            return Some(RecommendedAction::ResumeCase {
+                case_id: case.id.0.clone(),
+                status: case.status,
+            });
+        }
+
+        let failed = self.offline_store.failed_count();
+        if failed > 0 {
+            return Some(RecommendedAction::RetryFailedSyncs { count: failed });
+        }
+
+        self.nearest_claimable_case()
+            .map(|case| RecommendedAction::ClaimNearest {
+                case_id: case.id.0.clone(),
+            })
+    }
+
+    #[must_use]
+    pub fn nearest_claimable_case(&self) -> Option<&ServerCase> {
+        self.cases
+            .iter()
+            .filter(|c| c.status.is_claimable() && c.assigned_rescuer_id.is_none())
+            .filter_map(|c| self.case_distance_m(c).map(|d| (c, d)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(c, _)| c)
+    }
+
+    #[must_use]
+    pub fn preferences(&self) -> UserPreferences {
+        UserPreferences {
+            highlight_radius_m: self.highlight_radius_m,
+            auto_refresh_interval_ms: self.auto_refresh_interval_ms,
+            auto_select_nearest: self.auto_select_nearest,
+            list_sort: self.list_sort,
+            distance_unit: self.distance_unit,
+            max_image_alloc: self.max_image_alloc,
+            clustering_enabled: self.clustering_enabled,
+            outbox_tuning: self.outbox_tuning,
+            locale: self.locale,
+            photo_output_format: self.capture_config.output_format,
+            map_drift_refresh_m: self.map_drift_refresh_m,
+            min_crop_confidence: self.min_crop_confidence,
+            foreground_refresh_cooldown_ms: self.foreground_refresh_cooldown_ms,
+            drop_out_of_radius_cases: self.drop_out_of_radius_cases,
+            radius_tolerance_fraction: self.radius_tolerance_fraction,
+            require_resolution_evidence: self.require_resolution_evidence,
+            utc_offset_minutes: self.utc_offset_minutes,
+        }
+    }
+
+    pub fn apply_preferences(&mut self, prefs: &UserPreferences) {
+        self.set_preference(Preference::HighlightRadius {
+            meters: prefs.highlight_radius_m,
+        });
+        self.set_preference(Preference::AutoRefreshInterval {
+            ms: prefs.auto_refresh_interval_ms,
+        });
+        self.set_preference(Preference::AutoSelectNearest {
+            enabled: prefs.auto_select_nearest,
+        });
+        self.set_preference(Preference::ListSort {
+            mode: prefs.list_sort,
+        });
+        self.set_preference(Preference::DistanceUnit {
+            unit: prefs.distance_unit,
+        });
+        self.set_preference(Preference::MaxImageAlloc {
+            bytes: prefs.max_image_alloc,
+        });
+        self.set_preference(Preference::Clustering {
+            enabled: prefs.clustering_enabled,
+        });
+        self.set_preference(Preference::OutboxTuning {
+            tuning: prefs.outbox_tuning,
+        });
+        self.set_preference(Preference::Locale {
+            locale: prefs.locale,
+        });
+        self.set_preference(Preference::PhotoOutputFormat {
+            format: prefs.photo_output_format,
+        });
+        self.set_preference(Preference::MapDriftRefresh {
+            meters: prefs.map_drift_refresh_m,
+        });
+        self.set_preference(Preference::MinCropConfidence {
+            confidence: prefs.min_crop_confidence,
+        });
+        self.set_preference(Preference::ForegroundRefreshCooldown {
+            ms: prefs.foreground_refresh_cooldown_ms,
+        });
+        self.set_preference(Preference::DropOutOfRadiusCases {
+            enabled: prefs.drop_out_of_radius_cases,
+        });
+        self.set_preference(Preference::RadiusTolerance {
+            fraction: prefs.radius_tolerance_fraction,
+        });
+        self.set_preference(Preference::RequireResolutionEvidence {
+            required: prefs.require_resolution_evidence,
+        });
+        self.set_preference(Preference::UtcOffset {
+            minutes: prefs.utc_offset_minutes,
+        });
+    }
+
+    /// Everything besides the image bytes that affects a processed photo.
+    #[must_use]
+    pub fn photo_processing_fingerprint(&self) -> String {
+        format!(
+            "{:?}|{}|{:?}|{}|{:?}|{}|{}",
+            self.capture_config,
+            self.yolo_detector.is_some(),
+            self.detector_config,
+            self.min_crop_confidence,
+            self.crop_merge_strategy,
+            self.crop_padding_fraction,
+            self.max_image_alloc,
+        )
+    }
+
+    /// Returns true if the key was not known yet.
+    pub fn remember_store_key(&mut self, key_id: String) -> bool {
+        if self.store_key_ids.contains(&key_id) {
+            return false;
+        }
+        self.store_key_ids.push(key_id);
+        true
+    }
+
+    pub fn set_preference(&mut self, preference: Preference) {
+        match preference {
+            Preference::HighlightRadius { meters } => {
+                self.highlight_radius_m = meters.min(MAX_RADIUS_M);
+            }
+            Preference::AutoRefreshInterval { ms } => {
+                self.auto_refresh_interval_ms = ms.filter(|&ms| ms > 0);
+            }
+            Preference::AutoSelectNearest { enabled } => {
+                self.auto_select_nearest = enabled;
+                if !enabled {
+                    self.auto_select_pending = false;
+                }
+            }
+            Preference::ListSort { mode } => self.list_sort = mode,
+            Preference::DistanceUnit { unit } => self.distance_unit = unit,
+            Preference::MaxImageAlloc { bytes } => {
+                self.max_image_alloc = bytes.min(MAX_IMAGE_ALLOC)
+            }
+            Preference::Clustering { enabled } => self.clustering_enabled = enabled,
+            Preference::OutboxTuning { tuning } => self.outbox_tuning = tuning,
+            Preference::Locale { locale } => self.locale = locale,
+            Preference::PhotoOutputFormat { format } => self.capture_config.output_format = format,
+            Preference::MapDriftRefresh { meters } => {
+                self.map_drift_refresh_m = meters.filter(|&m| m > 0);
+            }
+            Preference::MinCropConfidence { confidence } => {
+                if confidence.is_finite() {
+                    self.min_crop_confidence = confidence.clamp(0.0, 1.0);
+                }
+            }
+            Preference::ForegroundRefreshCooldown { ms } => {
+                self.foreground_refresh_cooldown_ms = ms
+            }
+            Preference::DropOutOfRadiusCases { enabled } => self.drop_out_of_radius_cases = enabled,
+            Preference::RadiusTolerance { fraction } => {
+                if fraction.is_finite() {
+                    self.radius_tolerance_fraction = fraction.max(0.0);
+                }
+            }
+            Preference::RequireResolutionEvidence { required } => {
+                self.require_resolution_evidence = required;
+            }
+            Preference::UtcOffset { minutes } => {
+                self.utc_offset_minutes =
+                    minutes.clamp(MIN_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES);
+            }
+        }
+    }
+
+    /// A success means connectivity is back, so entries that failed during
+    /// the same outage shouldn't keep their inflated delays.
+    pub fn reset_sibling_backoff(&mut self, now_ms: u64) -> usize {
+        if !self.outbox_tuning.reset_siblings_on_success {
+            return 0;
+        }
+        let since_ms = now_ms.saturating_sub(self.outbox_tuning.sibling_reset_window_ms);
+        let reset = self.offline_store.reset_failed_backoff(since_ms);
+        if reset > 0 {
+            self.mark_store_dirty();
+        }
+        reset
+    }
+
+    pub fn request_auto_select(&mut self) {
+        if self.auto_select_nearest && self.selected_case_id.is_none() {
+            self.auto_select_pending = true;
+            self.apply_pending_auto_select();
+        }
+    }
+
+    pub fn apply_pending_auto_select(&mut self) -> bool {
+        if !self.auto_select_pending {
+            return false;
+        }
+        if self.selected_case_id.is_some() {
+            self.auto_select_pending = false;
+            return false;
+        }
+
+        let nearest = match self.nearest_claimable_case() {
+            Some(case) => case.id.clone(),
+            None => return false,
+        };
+
+        self.mark_case_viewed(&nearest.0);
+        self.selected_case_id = Some(nearest);
+        self.auto_select_pending = false;
+        true
+    }
+
+    pub fn reconcile_selection(&mut self) -> bool {
+        let removed = match &self.selected_case_id {
+            Some(id) => !self.has_case(&id.0),
+            None => false,
+        };
+
+        if !removed {
+            self.selected_case_unavailable = false;
+            return false;
+        }
+
+        match self.removed_selection_behavior {
+            RemovedSelectionBehavior::ClearWithToast => {
+                self.selected_case_id = None;
+                self.selected_case_unavailable = false;
+                self.show_catalog_toast(ToastKey::CaseRemoved, ToastKind::Info);
+            }
+            RemovedSelectionBehavior::KeepUnavailable => {
+                self.selected_case_unavailable = true;
+            }
+        }
+
+        true
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if !(MIN_RADIUS_M..=MAX_RADIUS_M).contains(&self.area_radius_m) {
+            return Err(format!(
+                "area_radius_m {} outside [{MIN_RADIUS_M}, {MAX_RADIUS_M}]",
+                self.area_radius_m
+            ));
+        }
+
+        for local_case in &self.offline_store.pending_local_cases {
+            if !local_case.status.is_synced() {
+                continue;
+            }
+            if let Some(server_id) = &local_case.server_id {
+                if self.cases.iter().any(|c| &c.id == server_id) {
+                    return Err(format!(
+                        "case {server_id} appears both as synced local case {} and in cases",
+                        local_case.local_id
+                    ));
+                }
+            }
+        }
+
+        for (case_id, claim) in &self.pending_claims {
+            if !self.pending_mutations.contains_key(&claim.mutation_id) {
+                return Err(format!(
+                    "pending claim for case {case_id} references missing mutation {}",
+                    claim.mutation_id
+                ));
+            }
+        }
+
+        let mut seen_op_ids = HashSet::new();
+        for entry in &self.offline_store.outbox {
+            if !seen_op_ids.insert(&entry.op_id) {
+                return Err(format!("duplicate outbox op_id {}", entry.op_id));
+            }
+        }
+
+        let mut seen_local_ids = HashSet::new();
+        for local_case in &self.offline_store.pending_local_cases {
+            if !seen_local_ids.insert(&local_case.local_id) {
+                return Err(format!("duplicate local case id {}", local_case.local_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn enforce_collection_limits(&mut self) {
+        while self.offline_store.pending_local_cases.len() > MAX_PENDING_LOCAL_CASES {
+            self.offline_store.evict_synced_cases(1);
+            if self.offline_store.pending_local_cases.len() > MAX_PENDING_LOCAL_CASES {
+                self.offline_store.pending_local_cases.remove(0);
+            }
+        }
+
+        if self.cases.len() > MAX_CACHED_SERVER_CASES {
+            self.cases.sort_by(|a, b| b.created_at_ms_utc.0.cmp(&a.created_at_ms_utc.0));
+            self.cases.truncate(MAX_CACHED_SERVER_CASES);
+        }
+
+        self.offline_store.cleanup_completed_outbox();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StagedPhoto {
+    pub original_data: Vec<u8>,
+    pub processed_data: Vec<u8>,
+    pub cropped_data: Option<Vec<u8>>,
+    pub thumbnail_data: Option<Vec<u8>>,
+    pub width: u32,
+    pub height: u32,
+    pub mime_type: String,
+    pub detection_count: usize,
+    pub top_confidence: f32,
+    pub detections: Vec<crate::vision::Detection>,
+}
+
+impl StagedPhoto {
+    #[must_use]
+    pub fn has_detections(&self) -> bool {
+        self.detection_count > 0
+    }
+
+    #[must_use]
+    pub fn best_data_for_upload(&self) -> &[u8] {
+        self.cropped_data.as_ref().unwrap_or(&self.processed_data)
+    }
+
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        self.original_data.len()
+            + self.processed_data.len()
+            + self.cropped_data.as_ref().map_or(0, Vec::len)
+            + self.thumbnail_data.as_ref().map_or(0, Vec::len)
+    }
+
+    pub fn apply_detections(
+        &mut self,
+        detections: Vec<crate::vision::Detection>,
+        cropped_data: Option<Vec<u8>>,
+    ) {
+        self.detection_count = detections.len();
+        self.top_confidence = top_detection_confidence(&detections);
+        self.detections = detections;
+        self.cropped_data = cropped_data;
+    }
+}
+
+/// Small LRU of processed photos keyed by the blake3 hash of the original
+/// bytes and the settings that produced them. Entries are stored without
+/// `original_data`; the caller already holds those bytes.
+#[derive(Debug, Clone)]
+pub struct ProcessedPhotoCache {
+    capacity: usize,
+    entries: VecDeque<([u8; 32], StagedPhoto)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for ProcessedPhotoCache {
+    fn default() -> Self {
+        Self::new(PROCESSED_PHOTO_CACHE_CAPACITY)
+    }
+}
+
+impl ProcessedPhotoCache {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn key_for(original: &[u8], settings: &str) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(settings.as_bytes());
+        hasher.update(original);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Returns the cached photo with `original` restored as its original data.
+    pub fn get(&mut self, original: &[u8], settings: &str) -> Option<StagedPhoto> {
+        let key = Self::key_for(original, settings);
+        let position = match self.entries.iter().position(|(k, _)| *k == key) {
+            Some(position) => position,
+            None => {
+                self.misses += 1;
+                return None;
+            }
+        };
+        self.hits += 1;
+        let entry = self.entries.remove(position)?;
+        let mut photo = entry.1.clone();
+        self.entries.push_back(entry);
+        photo.original_data = original.to_vec();
+        Some(photo)
+    }
+
+    pub fn insert(&mut self, photo: &StagedPhoto, settings: &str) {
+        let key = Self::key_for(&photo.original_data, settings);
+        self.entries.retain(|(k, _)| *k != key);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        let stripped = StagedPhoto {
+            original_data: Vec::new(),
+            ..photo.clone()
+        };
+        self.entries.push_back((key, stripped));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+#[must_use]
+pub fn top_detection_confidence(detections: &[crate::vision::Detection]) -> f32 {
+    detections
+        .iter()
+        .map(|d| d.confidence)
+        .fold(0.0f32, f32::max)
+}
+
+/// Cropping to a weak detection tends to cut the animal out of frame, so a
+/// crop is only taken once the best detection clears `min_crop_confidence`.
+#[must_use]
+pub fn should_auto_crop(detections: &[crate::vision::Detection], min_crop_confidence: f32) -> bool {
+    !detections.is_empty() && top_detection_confidence(detections) > min_crop_confidence
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovedSelectionBehavior {
+    #[default]
+    ClearWithToast,
+    KeepUnavailable,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1998,13 +5329,59 @@ pub struct CreateCasePayload {
     pub wound_severity: Option<u8>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum PushPayload {
-    NewCase {
-        case_id: String,
-        lat: f64,
-        lng: f64,
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CaseDraft {
+    pub location: Option<(f64, f64)>,
+    pub description: Option<String>,
+    pub landmark_hint: Option<String>,
+    pub wound_severity: Option<u8>,
+    pub photo_data: Option<Vec<u8>>,
+    pub photo_mime_type: Option<String>,
+    pub updated_at_ms: u64,
+}
+
+/// Fields of a case changed by a push notification; `None` leaves the field as is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CasePatch {
+    pub status: Option<CaseStatus>,
+    pub assigned_rescuer_id: Option<UserId>,
+}
+
+impl CasePatch {
+    #[must_use]
+    pub fn status(status: CaseStatus) -> Self {
+        Self {
+            status: Some(status),
+            assigned_rescuer_id: None,
+        }
+    }
+
+    /// Layers `newer` on top of `self`, keeping fields `newer` leaves unset.
+    #[must_use]
+    pub fn merged_with(self, newer: Self) -> Self {
+        Self {
+            status: newer.status.or(self.status),
+            assigned_rescuer_id: newer.assigned_rescuer_id.or(self.assigned_rescuer_id),
+        }
+    }
+
+    fn apply_to(&self, case: &mut ServerCase) {
+        if let Some(status) = self.status {
+            case.status = status;
+        }
+        if let Some(assignee) = &self.assigned_rescuer_id {
+            case.assigned_rescuer_id = Some(assignee.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PushPayload {
+    NewCase {
+        case_id: String,
+        lat: f64,
+        lng: f64,
         #[serde(default)]
         severity: Option<u8>,
     },
@@ -2097,6 +5474,8 @@ pub enum Event {
         error: String,
     },
     LogoutRequested,
+    LogoutConfirmed,
+    LogoutCancelled,
     LogoutCompleted,
     TokenRefreshRequired,
     TokenRefreshed {
@@ -2126,6 +5505,28 @@ pub enum Event {
     RadiusSelected {
         meters: u32,
     },
+    SetHighlightRadius {
+        meters: u32,
+    },
+    SetUtcOffset {
+        minutes: i32,
+    },
+    SetAutoSelectNearest {
+        enabled: bool,
+    },
+    SetPreference {
+        preference: Preference,
+    },
+    PurgeAllLocalData,
+    PreferencesLoaded {
+        result: Box<Result<Vec<u8>, crate::capabilities::KvError>>,
+    },
+    StoreKeysLoaded {
+        result: Box<Result<Vec<u8>, crate::capabilities::KvError>>,
+    },
+    SetResolutionEvidencePolicy {
+        required: bool,
+    },
     OnboardingComplete,
 
     NetworkStatusChanged {
@@ -2139,6 +5540,17 @@ pub enum Event {
     CapturePhotoRequested,
     CameraResult(Box<Result<crate::capabilities::CameraOutput, crate::capabilities::CameraError>>),
     ClearStagedPhoto,
+    RerunDetection,
+    WarmupVision,
+    DismissFailedOperation {
+        op_id: String,
+    },
+    DiscardOperation {
+        op_id: String,
+    },
+    PromoteOperation {
+        op_id: String,
+    },
     PhotoProcessed {
         staged_photo: StagedPhoto,
     },
@@ -2147,12 +5559,21 @@ pub enum Event {
     },
 
     CreateCaseRequested(CreateCasePayload),
+    UpdateDraft {
+        location: Option<(f64, f64)>,
+        description: Option<String>,
+        landmark_hint: Option<String>,
+        wound_severity: Option<u8>,
+    },
+    DiscardDraft,
+    SubmitDraft,
     CreateCaseResponse {
         op_id: String,
         result: Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>,
     },
     PhotoUploadResponse {
         local_id: String,
+        redirected: bool,
         result: Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>,
     },
 
@@ -2162,7 +5583,7 @@ pub enum Event {
     },
     PersistenceSucceeded,
     PersistenceFailed {
-        error: String,
+        error: AppError,
     },
     RestoreStateRequested,
         RestoreStateResponse {
@@ -2172,10 +5593,13 @@ pub enum Event {
         data: Vec<u8>,
     },
     StateDecryptionFailed {
-        error: String,
+        error: AppError,
     },
 
     OutboxFlushRequested,
+    OutboxCapacityReached {
+        kind: OutboxKind,
+    },
     OutboxEntryCompleted {
         op_id: String,
     },
@@ -2188,10 +5612,20 @@ pub enum Event {
     SwitchToMap,
     SwitchToList,
     ToggleFeedView,
+    SetMapFollowMode {
+        mode: MapFollowMode,
+    },
+    SetStatusFilter {
+        status: Option<String>,
+    },
+    SetListSortMode {
+        mode: ListSortMode,
+    },
     MapMoved {
         center: MapCenter,
         zoom: ZoomLevel,
     },
+    MapSettled,
 
     CaseSelected {
         case_id: String,
@@ -2201,6 +5635,9 @@ pub enum Event {
     ClaimRequested {
         case_id: String,
     },
+    ResumeCaseRequested {
+        case_id: String,
+    },
     ClaimResponse {
         case_id: String,
         mutation_id: String,
@@ -2211,6 +5648,7 @@ pub enum Event {
         case_id: String,
         next_status: String,
         notes: Option<String>,
+        outcome: Option<ResolutionOutcome>,
     },
     TransitionResponse {
         case_id: String,
@@ -2219,9 +5657,15 @@ pub enum Event {
     },
 
     RefreshRequested,
-    RefreshResponse(Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>),
+    RefreshResponse {
+        generation: u64,
+        result: Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>,
+    },
     LoadMoreCases,
-    LoadMoreResponse(Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>),
+    LoadMoreResponse {
+        generation: u64,
+        result: Box<Result<crate::capabilities::HttpOutput, crate::capabilities::HttpError>>,
+    },
 
     PushPermissionRequested,
     PushPermissionResult {
@@ -2247,6 +5691,9 @@ pub enum Event {
 
     TimerTick,
     RetryFailedOperations,
+    ForceRetryPermanentlyFailed {
+        include_client_errors: bool,
+    },
 }
 
 impl Event {
@@ -2261,6 +5708,8 @@ impl Event {
             Self::LoginCompleted { .. } => "login_completed",
             Self::LoginFailed { .. } => "login_failed",
             Self::LogoutRequested => "logout_requested",
+            Self::LogoutConfirmed => "logout_confirmed",
+            Self::LogoutCancelled => "logout_cancelled",
             Self::LogoutCompleted => "logout_completed",
             Self::TokenRefreshRequired => "token_refresh_required",
             Self::TokenRefreshed { .. } => "token_refreshed",
@@ -2271,6 +5720,14 @@ impl Event {
             Self::LocationFailed { .. } => "location_failed",
             Self::LocationPinDropped { .. } => "location_pin_dropped",
             Self::RadiusSelected { .. } => "radius_selected",
+            Self::SetHighlightRadius { .. } => "set_highlight_radius",
+            Self::SetUtcOffset { .. } => "set_utc_offset",
+            Self::SetAutoSelectNearest { .. } => "set_auto_select_nearest",
+            Self::SetPreference { .. } => "set_preference",
+            Self::PurgeAllLocalData => "purge_all_local_data",
+            Self::PreferencesLoaded { .. } => "preferences_loaded",
+            Self::StoreKeysLoaded { .. } => "store_keys_loaded",
+            Self::SetResolutionEvidencePolicy { .. } => "set_resolution_evidence_policy",
             Self::OnboardingComplete => "onboarding_complete",
             Self::NetworkStatusChanged { .. } => "network_status_changed",
             Self::CameraPermissionRequested => "camera_permission_requested",
@@ -2278,9 +5735,17 @@ impl Event {
             Self::CapturePhotoRequested => "capture_photo_requested",
             Self::CameraResult(_) => "camera_result",
             Self::ClearStagedPhoto => "clear_staged_photo",
+            Self::RerunDetection => "rerun_detection",
+            Self::WarmupVision => "warmup_vision",
+            Self::DismissFailedOperation { .. } => "dismiss_failed_operation",
+            Self::DiscardOperation { .. } => "discard_operation",
+            Self::PromoteOperation { .. } => "promote_operation",
             Self::PhotoProcessed { .. } => "photo_processed",
             Self::PhotoProcessingFailed { .. } => "photo_processing_failed",
             Self::CreateCaseRequested(_) => "create_case_requested",
+            Self::UpdateDraft { .. } => "update_draft",
+            Self::DiscardDraft => "discard_draft",
+            Self::SubmitDraft => "submit_draft",
             Self::CreateCaseResponse { .. } => "create_case_response",
             Self::PhotoUploadResponse { .. } => "photo_upload_response",
             Self::WriteEncryptedStore { .. } => "write_encrypted_store",
@@ -2292,21 +5757,27 @@ impl Event {
             Self::StateDecryptionFailed { .. } => "state_decryption_failed",
             Self::OutboxFlushRequested => "outbox_flush_requested",
             Self::OutboxEntryCompleted { .. } => "outbox_entry_completed",
+            Self::OutboxCapacityReached { .. } => "outbox_capacity_reached",
             Self::OutboxEntryFailed { .. } => "outbox_entry_failed",
             Self::SwitchToMap => "switch_to_map",
             Self::SwitchToList => "switch_to_list",
             Self::ToggleFeedView => "toggle_feed_view",
+            Self::SetMapFollowMode { .. } => "set_map_follow_mode",
+            Self::SetStatusFilter { .. } => "set_status_filter",
+            Self::SetListSortMode { .. } => "set_list_sort_mode",
             Self::MapMoved { .. } => "map_moved",
+            Self::MapSettled => "map_settled",
             Self::CaseSelected { .. } => "case_selected",
             Self::CaseDeselected => "case_deselected",
             Self::ClaimRequested { .. } => "claim_requested",
+            Self::ResumeCaseRequested { .. } => "resume_case_requested",
             Self::ClaimResponse { .. } => "claim_response",
             Self::TransitionRequested { .. } => "transition_requested",
             Self::TransitionResponse { .. } => "transition_response",
             Self::RefreshRequested => "refresh_requested",
-            Self::RefreshResponse(_) => "refresh_response",
+            Self::RefreshResponse { .. } => "refresh_response",
             Self::LoadMoreCases => "load_more_cases",
-            Self::LoadMoreResponse(_) => "load_more_response",
+            Self::LoadMoreResponse { .. } => "load_more_response",
             Self::PushPermissionRequested => "push_permission_requested",
             Self::PushPermissionResult { .. } => "push_permission_result",
             Self::PushTokenReceived { .. } => "push_token_received",
@@ -2318,6 +5789,7 @@ impl Event {
             Self::ShowToast { .. } => "show_toast",
             Self::TimerTick => "timer_tick",
             Self::RetryFailedOperations => "retry_failed_operations",
+            Self::ForceRetryPermanentlyFailed { .. } => "force_retry_permanently_failed",
         }
     }
 
@@ -2327,18 +5799,36 @@ impl Event {
             self,
             Self::LoginRequested
                 | Self::LogoutRequested
+                | Self::LogoutConfirmed
+                | Self::LogoutCancelled
                 | Self::LocationPermissionRequested
                 | Self::LocationPinDropped { .. }
                 | Self::RadiusSelected { .. }
+                | Self::SetHighlightRadius { .. }
+                | Self::SetAutoSelectNearest { .. }
+                | Self::SetPreference { .. }
+                | Self::PurgeAllLocalData
                 | Self::CapturePhotoRequested
                 | Self::ClearStagedPhoto
+                | Self::RerunDetection
+                | Self::DismissFailedOperation { .. }
+                | Self::DiscardOperation { .. }
+                | Self::ForceRetryPermanentlyFailed { .. }
+                | Self::PromoteOperation { .. }
                 | Self::CreateCaseRequested(_)
+                | Self::UpdateDraft { .. }
+                | Self::DiscardDraft
+                | Self::SubmitDraft
                 | Self::SwitchToMap
                 | Self::SwitchToList
                 | Self::ToggleFeedView
+                | Self::SetMapFollowMode { .. }
+                | Self::SetStatusFilter { .. }
+                | Self::SetListSortMode { .. }
                 | Self::CaseSelected { .. }
                 | Self::CaseDeselected
                 | Self::ClaimRequested { .. }
+                | Self::ResumeCaseRequested { .. }
                 | Self::TransitionRequested { .. }
                 | Self::RefreshRequested
                 | Self::LoadMoreCases
@@ -2354,12 +5844,67 @@ impl Default for Event {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputImageFormat {
+    #[default]
+    Webp,
+    Jpeg,
+}
+
+impl OutputImageFormat {
+    #[must_use]
+    pub const fn mime_type(self) -> &'static str {
+        match self {
+            Self::Webp => PROCESSED_IMAGE_MIME_TYPE,
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// Encodes `img` in this format. `quality` only applies to JPEG; alpha is
+    /// dropped since JPEG cannot carry it.
+    pub fn encode(
+        self,
+        img: &image::DynamicImage,
+        quality: u8,
+    ) -> Result<Vec<u8>, image::ImageError> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::Webp => img.write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::WebP,
+            )?,
+            Self::Jpeg => {
+                let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+                    &mut bytes,
+                    quality.clamp(1, 100),
+                );
+                image::DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder)?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CaptureConfig {
     pub aspect_ratio: String,
     pub max_dimension: u32,
     pub quality: u8,
     pub format: String,
+    #[serde(default)]
+    pub output_format: OutputImageFormat,
+}
+
+impl CaptureConfig {
+    /// Parses `aspect_ratio` ("4:3") into width / height, `None` if malformed.
+    #[must_use]
+    pub fn aspect_ratio_value(&self) -> Option<f32> {
+        let (w, h) = self.aspect_ratio.split_once(':')?;
+        let w: f32 = w.trim().parse().ok()?;
+        let h: f32 = h.trim().parse().ok()?;
+        (w > 0.0 && h > 0.0 && w.is_finite() && h.is_finite()).then(|| w / h)
+    }
 }
 
 impl Default for CaptureConfig {
@@ -2369,6 +5914,7 @@ impl Default for CaptureConfig {
             max_dimension: MAX_IMAGE_DIMENSION,
             quality: 85,
             format: "jpeg".into(),
+            output_format: OutputImageFormat::default(),
         }
     }
 }
@@ -2382,6 +5928,173 @@ pub struct CasePin {
     pub is_mine: bool,
     pub is_local: bool,
     pub wound_severity: Option<u8>,
+    pub is_new: bool,
+    pub distance_meters: Option<f64>,
+}
+
+pub const PACKED_PIN_FORMAT_VERSION: u8 = 1;
+pub const PACKED_PIN_HEADER_LEN: usize = 8;
+pub const PACKED_PIN_RECORD_LEN: usize = 36;
+pub const PACKED_PIN_STATUSES: [CaseStatus; 7] = [
+    CaseStatus::Pending,
+    CaseStatus::Claimed,
+    CaseStatus::EnRoute,
+    CaseStatus::Arrived,
+    CaseStatus::Resolved,
+    CaseStatus::Cancelled,
+    CaseStatus::Expired,
+];
+pub const PACKED_PIN_FLAG_MINE: u8 = 1;
+pub const PACKED_PIN_FLAG_LOCAL: u8 = 1 << 1;
+pub const PACKED_PIN_FLAG_NEW: u8 = 1 << 2;
+pub const PACKED_PIN_FLAG_HAS_SEVERITY: u8 = 1 << 3;
+pub const PACKED_PIN_FLAG_HAS_DISTANCE: u8 = 1 << 4;
+
+#[must_use]
+pub fn pack_pins(pins: &[CasePin]) -> Vec<u8> {
+    let ids_len: usize = pins.iter().map(|p| p.id.len()).sum();
+    let mut records = Vec::with_capacity(pins.len() * PACKED_PIN_RECORD_LEN);
+    let mut ids = Vec::with_capacity(ids_len);
+
+    for pin in pins {
+        let id_offset = u32::try_from(ids.len()).unwrap_or(u32::MAX);
+        let id_len = u32::try_from(pin.id.len()).unwrap_or(u32::MAX);
+        ids.extend_from_slice(pin.id.as_bytes());
+
+        let status = PACKED_PIN_STATUSES
+            .iter()
+            .position(|s| *s == pin.status)
+            .and_then(|i| u8::try_from(i).ok())
+            .unwrap_or(0);
+
+        let mut flags = 0;
+        if pin.is_mine {
+            flags |= PACKED_PIN_FLAG_MINE;
+        }
+        if pin.is_local {
+            flags |= PACKED_PIN_FLAG_LOCAL;
+        }
+        if pin.is_new {
+            flags |= PACKED_PIN_FLAG_NEW;
+        }
+        if pin.wound_severity.is_some() {
+            flags |= PACKED_PIN_FLAG_HAS_SEVERITY;
+        }
+        if pin.distance_meters.is_some() {
+            flags |= PACKED_PIN_FLAG_HAS_DISTANCE;
+        }
+
+        records.extend_from_slice(&id_offset.to_le_bytes());
+        records.extend_from_slice(&id_len.to_le_bytes());
+        records.extend_from_slice(&pin.lat.to_le_bytes());
+        records.extend_from_slice(&pin.lon.to_le_bytes());
+        records.extend_from_slice(&pin.distance_meters.unwrap_or(0.0).to_le_bytes());
+        records.extend_from_slice(&[status, flags, pin.wound_severity.unwrap_or(0), 0]);
+    }
+
+    let count = u32::try_from(pins.len()).unwrap_or(u32::MAX);
+    let mut packed = Vec::with_capacity(PACKED_PIN_HEADER_LEN + records.len() + ids.len());
+    packed.extend_from_slice(&[PACKED_PIN_FORMAT_VERSION, 0, 0, 0]);
+    packed.extend_from_slice(&count.to_le_bytes());
+    packed.extend_from_slice(&records);
+    packed.extend_from_slice(&ids);
+    packed
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PinCluster {
+    pub lat: f64,
+    pub lon: f64,
+    pub count: usize,
+    pub representative_id: String,
+    pub dominant_status: CaseStatus,
+}
+
+impl PinCluster {
+    #[must_use]
+    pub fn from_pins(pins: &[CasePin]) -> Option<Self> {
+        let representative = cluster_representative(pins)?;
+        let divisor = f64::from(u32::try_from(pins.len()).unwrap_or(u32::MAX));
+
+        Some(Self {
+            lat: pins.iter().map(|p| p.lat).sum::<f64>() / divisor,
+            lon: pins.iter().map(|p| p.lon).sum::<f64>() / divisor,
+            count: pins.len(),
+            representative_id: representative.id.clone(),
+            dominant_status: dominant_status(pins).unwrap_or(representative.status),
+        })
+    }
+}
+
+/// Most urgent live status among `pins`: an unclaimed case outranks one
+/// that already has a rescuer on the way.
+fn dominant_status(pins: &[CasePin]) -> Option<CaseStatus> {
+    const URGENCY: [CaseStatus; 4] = [
+        CaseStatus::Pending,
+        CaseStatus::Claimed,
+        CaseStatus::EnRoute,
+        CaseStatus::Arrived,
+    ];
+    URGENCY
+        .into_iter()
+        .find(|status| pins.iter().any(|p| p.status == *status))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MapMarker {
+    Pin(CasePin),
+    Cluster(PinCluster),
+}
+
+/// Greedily groups pins lying within a zoom-dependent distance of each
+/// group's first pin. Groups of one pass through as plain pins.
+#[must_use]
+pub fn cluster_pins(pins: &[CasePin], zoom: f64) -> Vec<MapMarker> {
+    let radius_m = radius_for_zoom(zoom) * PIN_CLUSTER_RADIUS_FRACTION;
+    let coords: Vec<Option<ValidatedCoordinate>> = pins
+        .iter()
+        .map(|p| ValidatedCoordinate::new(p.lat, p.lon).ok())
+        .collect();
+    let mut assigned = vec![false; pins.len()];
+    let mut markers = Vec::new();
+
+    for (i, pin) in pins.iter().enumerate() {
+        if assigned[i] {
+            continue;
+        }
+        assigned[i] = true;
+        let mut members = vec![pin.clone()];
+
+        if let Some(anchor) = coords[i] {
+            for (j, other) in pins.iter().enumerate().skip(i + 1) {
+                if assigned[j] {
+                    continue;
+                }
+                if coords[j].is_some_and(|c| haversine_distance(anchor, c) <= radius_m) {
+                    assigned[j] = true;
+                    members.push(other.clone());
+                }
+            }
+        }
+
+        match PinCluster::from_pins(&members) {
+            Some(cluster) if members.len() > 1 => markers.push(MapMarker::Cluster(cluster)),
+            _ => markers.extend(members.into_iter().map(MapMarker::Pin)),
+        }
+    }
+    markers
+}
+
+#[must_use]
+pub fn cluster_representative(pins: &[CasePin]) -> Option<&CasePin> {
+    pins.iter().max_by(|a, b| {
+        a.wound_severity
+            .unwrap_or(0)
+            .cmp(&b.wound_severity.unwrap_or(0))
+            .then_with(|| stable_id_hash(&b.id).cmp(&stable_id_hash(&a.id)))
+            .then_with(|| b.id.cmp(&a.id))
+    })
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -2389,15 +6102,21 @@ pub struct CaseListItem {
     pub id: String,
     pub description_preview: String,
     pub status: CaseStatus,
+    pub status_text: String,
     pub distance_meters: f64,
     pub distance_text: String,
     pub time_ago: String,
     pub created_at_ms: u64,
+    pub day_bucket: DayBucket,
     pub wound_severity: Option<u8>,
     pub is_mine: bool,
     pub is_local: bool,
     pub has_photo: bool,
+    /// Server thumbnail URL, or a data URI for cases that have not synced.
+    pub thumbnail_url: Option<String>,
     pub sync_status: Option<String>,
+    pub is_new: bool,
+    pub is_highlighted: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -2409,21 +6128,49 @@ pub enum ClaimState {
     NotClaimable,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClaimBlockedReason {
+    NotAuthenticated,
+    NotClaimable,
+    AlreadyClaimedByOther,
+    AlreadyClaimedByMe,
+    ClaimInFlight,
+}
+
+impl ClaimBlockedReason {
+    #[must_use]
+    pub fn for_claim_state(claim_state: &ClaimState, authenticated: bool) -> Option<Self> {
+        match claim_state {
+            ClaimState::NotClaimable => Some(Self::NotClaimable),
+            ClaimState::Claiming => Some(Self::ClaimInFlight),
+            ClaimState::ClaimedByMe => Some(Self::AlreadyClaimedByMe),
+            ClaimState::ClaimedByOther => Some(Self::AlreadyClaimedByOther),
+            ClaimState::Available if !authenticated => Some(Self::NotAuthenticated),
+            ClaimState::Available => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CaseDetail {
     pub id: String,
     pub description: Option<String>,
     pub landmark_hint: Option<String>,
     pub status: CaseStatus,
+    pub status_text: String,
     pub wound_severity: Option<u8>,
     pub species_guess: Option<String>,
     pub lat: f64,
     pub lon: f64,
+    pub coordinates_text: String,
+    pub share_text: String,
     pub distance_text: String,
     pub time_ago: String,
     pub created_at_ms: u64,
     pub can_claim: bool,
     pub claim_state: ClaimState,
+    pub claim_blocked_reason: Option<ClaimBlockedReason>,
+    pub can_resume: bool,
     pub available_transitions: Vec<CaseStatus>,
     pub photo_url: Option<String>,
     pub thumbnail_url: Option<String>,
@@ -2431,6 +6178,7 @@ pub struct CaseDetail {
     pub reporter_is_me: bool,
     pub is_local: bool,
     pub sync_status: Option<String>,
+    pub resolution_outcome: Option<ResolutionOutcome>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -2451,16 +6199,19 @@ pub enum ViewState {
     Authenticating,
     OnboardingLocation {
         permission_state: PermissionState,
+        onboarding_step: Option<(u8, u8)>,
     },
     PinDrop {
         initial_lat: Option<f64>,
         initial_lon: Option<f64>,
+        onboarding_step: Option<(u8, u8)>,
     },
     OnboardingRadius {
         lat: f64,
         lon: f64,
         radius: u32,
         selected_radius: u32,
+        onboarding_step: Option<(u8, u8)>,
     },
     CameraCapture {
         config: CaptureConfig,
@@ -2468,8 +6219,12 @@ pub enum ViewState {
     Ready {
         feed_view: FeedView,
         pins: Vec<CasePin>,
+        map_markers: Option<Vec<MapMarker>>,
         list_items: Vec<CaseListItem>,
+        list_total_count: usize,
+        list_showing_text: Option<String>,
         selected_detail: Option<CaseDetail>,
+        selected_unavailable: bool,
         map_center_lat: f64,
         map_center_lon: f64,
         map_zoom: f64,
@@ -2477,8 +6232,15 @@ pub enum ViewState {
         online: bool,
         pending_sync_count: usize,
         failed_sync_count: usize,
+        outbox_capacity_reached: Option<OutboxKind>,
         staged_photo: Option<StagedPhotoView>,
         has_more_cases: bool,
+        next_auto_refresh_in_ms: Option<u64>,
+        status_counts: HashMap<CaseStatus, usize>,
+        list_sort: ListSortMode,
+        empty_feed_reason: Option<EmptyFeedReason>,
+        recommended_action: Option<RecommendedAction>,
+        server_reachable: bool,
     },
     Error {
         title: String,
@@ -2496,10 +6258,11 @@ pub struct UserFacingError {
     pub error_code: String,
 }
 
-impl From<&AppError> for UserFacingError {
-    fn from(e: &AppError) -> Self {
+impl UserFacingError {
+    #[must_use]
+    pub fn localized(e: &AppError, catalog: &dyn MessageCatalog) -> Self {
         Self {
-            message: e.user_facing_message(),
+            message: catalog.error_message(e),
             is_transient: e.severity == ErrorSeverity::Transient,
             is_retryable: e.is_retryable(),
             error_code: e.code().to_string(),
@@ -2507,6 +6270,12 @@ impl From<&AppError> for UserFacingError {
     }
 }
 
+impl From<&AppError> for UserFacingError {
+    fn from(e: &AppError) -> Self {
+        Self::localized(e, &EnglishCatalog)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ToastView {
     pub message: String,
@@ -2532,13 +6301,66 @@ pub struct ViewModel {
     pub is_global_loading: bool,
     pub offline_queue_count: usize,
     pub is_authenticated: bool,
+    /// The token is about to expire; the shell should send `TokenRefreshed`.
+    pub token_refresh_needed: bool,
+    pub logout_needs_confirmation: bool,
     pub user_id: Option<String>,
+    pub severity_counts: [usize; 6],
+    pub offline_readiness: OfflineReadiness,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OfflineReadiness {
+    pub ready: bool,
+    pub area_set: bool,
+    pub cases_cached: bool,
+    pub store_restored: bool,
+    pub cache_age_ms: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViewDiff {
+    pub state_changed: bool,
+    pub error_changed: bool,
+    pub toast_changed: bool,
+    pub counts_changed: bool,
+    pub loading_changed: bool,
+    pub session_changed: bool,
+}
+
+impl ViewDiff {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !(self.state_changed
+            || self.error_changed
+            || self.toast_changed
+            || self.counts_changed
+            || self.loading_changed
+            || self.session_changed)
+    }
+}
+
+#[must_use]
+pub fn view_diff(prev: &ViewModel, next: &ViewModel) -> ViewDiff {
+    ViewDiff {
+        state_changed: prev.state != next.state,
+        error_changed: prev.error != next.error,
+        toast_changed: prev.toast != next.toast,
+        counts_changed: prev.offline_queue_count != next.offline_queue_count
+            || prev.severity_counts != next.severity_counts,
+        loading_changed: prev.is_global_loading != next.is_global_loading,
+        session_changed: prev.is_authenticated != next.is_authenticated
+            || prev.token_refresh_needed != next.token_refresh_needed
+            || prev.logout_needs_confirmation != next.logout_needs_confirmation
+            || prev.user_id != next.user_id,
+    }
 }
 
 pub mod app {
     use super::*;
     use crate::capabilities::{
-        CameraError, CameraOutput, Capabilities, CryptoOutput, HttpError, HttpOutput, KvError,
+        CameraError, CameraOutput, Capabilities, CryptoError, CryptoOutput, CryptoResult,
+        HttpError, HttpOutput, KvError,
     };
 
     #[derive(Default)]
@@ -2550,12 +6372,69 @@ pub mod app {
             format!("offline_store_v{}_{}", CURRENT_KEY_VERSION, &hash.to_hex()[..16])
         }
 
-        fn persist_store(model: &Model, caps: &Capabilities) {
+        fn local_data_keys(model: &Model) -> Vec<String> {
+            let mut keys = vec![
+                PREFERENCES_KV_KEY.to_string(),
+                STORE_KEYS_KV_KEY.to_string(),
+            ];
+            keys.extend(model.store_key_ids.iter().cloned());
+            if let Some(user_id) = &model.user_id {
+                let store_key = Self::derive_store_key_id(user_id);
+                if !keys.contains(&store_key) {
+                    keys.push(store_key);
+                }
+            }
+            keys
+        }
+
+        fn complete_logout(model: &mut Model, caps: &Capabilities) {
+            let cancelled = model.cancel_in_flight_requests();
+            if cancelled > 0 {
+                caps.telemetry().event(
+                    "logout_cancelled_in_flight",
+                    &[("count", &cancelled.to_string())],
+                );
+            }
+
+            let rolled_back = model.rollback_all_mutations();
+            if !rolled_back.is_empty() {
+                caps.telemetry().event(
+                    "logout_rolled_back_mutations",
+                    &[("count", &rolled_back.len().to_string())],
+                );
+            }
+
+            model.logout_confirmation_pending = false;
+            model.user_id = None;
+            model.jwt_token = None;
+            model.state = AppState::Unauthenticated;
+            model.cases.clear();
+            model.cases_etag = None;
+            model.offline_store = OfflineStore::new();
+            model.store_restored = false;
+            model.staged_photo = None;
+            model.selected_case_id = None;
+            model.last_gps_fix = None;
+            if let Some(cache) = &mut model.photo_cache {
+                cache.clear();
+            }
+            model.pending_pushes.clear();
+            model.deferred_push_patches.clear();
+            // Invalidate any refresh still in flight.
+            model.refresh_generation += 1;
+            model.is_refreshing = false;
+
+            caps.telemetry().event("logout", &[]);
+            caps.render().render();
+        }
+
+        /// Returns whether an encrypted write was issued.
+        fn persist_store(model: &Model, caps: &Capabilities) -> bool {
             let user_id = match &model.user_id {
                 Some(id) => id.clone(),
                 None => {
                     caps.telemetry().error("persist_no_user", "Cannot persist without user_id");
-                    return;
+                    return false;
                 }
             };
 
@@ -2565,29 +6444,127 @@ pub mod app {
                 Ok(bytes) => bytes,
                 Err(e) => {
                     caps.telemetry().error("persist_serialize_failed", &e.to_string());
-                    return;
+                    return false;
                 }
             };
 
             caps.telemetry().gauge("offline_store_bytes", serialized.len() as f64);
 
             let key_id_for_closure = key_id.clone();
-            caps.crypto().encrypt(
-                key_id,
-                serialized,
-                move |result| match result {
-                    Ok(CryptoOutput::Encrypted(data)) => Event::WriteEncryptedStore {
-                        key_id: key_id_for_closure,
-                        data,
-                    },
-                    Ok(_) => Event::PersistenceFailed {
-                        error: "Unexpected crypto output".into(),
+            caps.crypto().encrypt(key_id, serialized, move |result| {
+                Self::encryption_result_event(key_id_for_closure.clone(), result)
+            });
+            true
+        }
+
+        fn persist_store_keys(model: &Model, caps: &Capabilities) {
+            let bytes = match serde_json::to_vec(&model.store_key_ids) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    caps.telemetry()
+                        .error("store_keys_serialize_failed", &e.to_string());
+                    return;
+                }
+            };
+            caps.kv()
+                .set(STORE_KEYS_KV_KEY, bytes, |result| match result {
+                    Ok(()) => Event::Noop,
+                    Err(e) => Event::PersistenceFailed {
+                        error: AppError::new(ErrorKind::Storage, format!("{e:?}")),
                     },
+                });
+        }
+
+        fn persist_preferences(model: &Model, caps: &Capabilities) {
+            let bytes = match serde_json::to_vec(&model.preferences()) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    caps.telemetry()
+                        .error("preferences_serialize_failed", &e.to_string());
+                    return;
+                }
+            };
+            caps.kv()
+                .set(PREFERENCES_KV_KEY, bytes, |result| match result {
+                    Ok(()) => Event::Noop,
                     Err(e) => Event::PersistenceFailed {
-                        error: format!("{e:?}"),
+                        error: AppError::new(ErrorKind::Storage, format!("{e:?}")),
                     },
+                });
+        }
+
+        pub fn encryption_result_event(key_id: String, result: CryptoResult) -> Event {
+            match result {
+                Ok(CryptoOutput::Encrypted(data)) => Event::WriteEncryptedStore { key_id, data },
+                Ok(other) => Event::PersistenceFailed {
+                    error: Self::unexpected_crypto_output("Encrypted", &other),
                 },
-            );
+                Err(e) => Event::PersistenceFailed {
+                    error: Self::crypto_failure(&e),
+                },
+            }
+        }
+
+        pub fn decryption_result_event(result: CryptoResult) -> Event {
+            match result {
+                Ok(CryptoOutput::Decrypted(data)) => Event::StateDecrypted { data },
+                Ok(other) => Event::StateDecryptionFailed {
+                    error: Self::unexpected_crypto_output("Decrypted", &other),
+                },
+                Err(e) => Event::StateDecryptionFailed {
+                    error: Self::crypto_failure(&e),
+                },
+            }
+        }
+
+        fn unexpected_crypto_output(expected: &str, actual: &CryptoOutput) -> AppError {
+            AppError::new(
+                ErrorKind::Internal,
+                format!(
+                    "Unexpected crypto output: expected {expected}, got {}",
+                    actual.variant_name()
+                ),
+            )
+            .with_context("expected", expected)
+            .with_context("actual", actual.variant_name())
+        }
+
+        fn crypto_failure(error: &CryptoError) -> AppError {
+            AppError::new(ErrorKind::Crypto, error.to_string()).with_internal(format!("{error:?}"))
+        }
+
+        fn crypto_error_telemetry(error: &AppError) -> String {
+            match error.context.get("actual") {
+                Some(actual) => format!("{}: {} (variant {actual})", error.code(), error.message),
+                None => format!("{}: {}", error.code(), error.message),
+            }
+        }
+
+        fn persist_now(model: &mut Model, caps: &Capabilities) {
+            if Self::persist_store(model, caps) {
+                model.mark_store_persisted(get_current_time_ms());
+            }
+        }
+
+        fn persist_if_due(model: &mut Model, caps: &Capabilities) {
+            if model.should_persist(get_current_time_ms()) {
+                Self::persist_now(model, caps);
+            }
+        }
+
+        fn validate_wound_severity(severity: Option<u8>) -> Result<(), AppError> {
+            match severity {
+                Some(level) if !(MIN_WOUND_SEVERITY..=MAX_WOUND_SEVERITY).contains(&level) => {
+                    Err(AppError::new(
+                        ErrorKind::Validation,
+                        format!(
+                            "Wound severity must be between {MIN_WOUND_SEVERITY} and {MAX_WOUND_SEVERITY}"
+                        ),
+                    )
+                    .with_context("wound_severity", level.to_string()))
+                }
+                _ => Ok(()),
+            }
         }
 
         fn validate_coordinates(lat: f64, lng: f64) -> Result<ValidatedCoordinate, AppError> {
@@ -2598,13 +6575,19 @@ pub mod app {
             })
         }
 
-        fn build_case_pins(model: &Model) -> Vec<CasePin> {
+        pub(crate) fn build_case_pins(model: &Model) -> Vec<CasePin> {
             let user_id = model.user_id.as_ref();
             let mut pins = Vec::with_capacity(
                 model.offline_store.pending_local_cases.len() + model.cases.len(),
             );
 
-            for case in &model.offline_store.pending_local_cases {
+            let include_locals = model.passes_status_filter(CaseStatus::Pending);
+            for case in model
+                .offline_store
+                .pending_local_cases
+                .iter()
+                .filter(|_| include_locals)
+            {
                 pins.push(CasePin {
                     id: case.local_id.0.clone(),
                     lat: case.location.lat,
@@ -2613,10 +6596,20 @@ pub mod app {
                     is_mine: true,
                     is_local: true,
                     wound_severity: case.wound_severity,
+                    is_new: false,
+                    distance_meters: model.area_center.and_then(|center| {
+                        ValidatedCoordinate::new(case.location.lat, case.location.lon)
+                            .ok()
+                            .map(|coord| haversine_distance(center, coord))
+                    }),
                 });
             }
 
-            for case in &model.cases {
+            for case in model
+                .cases
+                .iter()
+                .filter(|c| model.passes_status_filter(c.status))
+            {
                 let is_mine = user_id
                     .map(|uid| case.assigned_rescuer_id.as_ref() == Some(uid))
                     .unwrap_or(false);
@@ -2629,6 +6622,8 @@ pub mod app {
                     is_mine,
                     is_local: false,
                     wound_severity: case.wound_severity,
+                    is_new: model.is_case_new(&case.id.0, case.created_at_ms_utc.0),
+                    distance_meters: model.case_distance_m(case),
                 });
             }
 
@@ -2646,7 +6641,13 @@ pub mod app {
                 model.offline_store.pending_local_cases.len() + model.cases.len(),
             );
 
-            for case in &model.offline_store.pending_local_cases {
+            let include_locals = model.passes_status_filter(CaseStatus::Pending);
+            for case in model
+                .offline_store
+                .pending_local_cases
+                .iter()
+                .filter(|_| include_locals)
+            {
                 let distance = if let Ok(case_coord) =
                     ValidatedCoordinate::new(case.location.lat, case.location.lon)
                 {
@@ -2655,41 +6656,41 @@ pub mod app {
                     f64::MAX
                 };
 
-                let sync_status = match case.status {
-                    LocalCaseStatus::PendingUpload => Some("Pending sync".into()),
-                    LocalCaseStatus::Uploading => Some("Syncing...".into()),
-                    LocalCaseStatus::UploadingPhoto => Some("Uploading photo...".into()),
-                    LocalCaseStatus::Failed => Some("Sync failed - tap to retry".into()),
-                    LocalCaseStatus::PermanentlyFailed => Some("Sync failed".into()),
-                    LocalCaseStatus::Synced => None,
-                };
+                let sync_status = sync_status_text(case.status, case.retry_count);
 
                 items.push(CaseListItem {
                     id: case.local_id.0.clone(),
                     description_preview: case.description_preview(DESCRIPTION_PREVIEW_LENGTH),
                     status: CaseStatus::Pending,
+                    status_text: model.catalog().status_name(CaseStatus::Pending),
                     distance_meters: distance,
-                    distance_text: format_distance(distance),
+                    distance_text: format_distance_unit(distance, model.distance_unit),
                     time_ago: format_time_ago(case.created_at_ms_utc.0, now_ms),
                     created_at_ms: case.created_at_ms_utc.0,
+                    day_bucket: day_bucket(
+                        case.created_at_ms_utc.0,
+                        now_ms,
+                        model.utc_offset_minutes,
+                    ),
                     wound_severity: case.wound_severity,
                     is_mine: true,
                     is_local: true,
                     has_photo: case.photo_data.is_some(),
+                    thumbnail_url: case.thumbnail_data.as_deref().map(|data| {
+                        Self::thumbnail_data_uri(data, case.photo_mime_type.as_deref())
+                    }),
                     sync_status,
+                    is_new: false,
+                    is_highlighted: is_within_highlight_radius(distance, model.highlight_radius_m),
                 });
             }
 
-            for case in &model.cases {
-                let distance = case.distance_meters.unwrap_or_else(|| {
-                    if let Ok(case_coord) =
-                        ValidatedCoordinate::new(case.location.lat, case.location.lon)
-                    {
-                        haversine_distance(user_loc, case_coord)
-                    } else {
-                        f64::MAX
-                    }
-                });
+            for case in model
+                .cases
+                .iter()
+                .filter(|c| model.passes_status_filter(c.status))
+            {
+                let distance = case_distance(case, user_loc);
 
                 let is_mine = user_id
                     .map(|uid| case.assigned_rescuer_id.as_ref() == Some(uid))
@@ -2699,30 +6700,92 @@ pub mod app {
                     id: case.id.0.clone(),
                     description_preview: case.description_preview(DESCRIPTION_PREVIEW_LENGTH),
                     status: case.status,
+                    status_text: model.catalog().status_name(case.status),
                     distance_meters: distance,
-                    distance_text: format_distance(distance),
+                    distance_text: format_distance_unit(distance, model.distance_unit),
                     time_ago: format_time_ago(case.created_at_ms_utc.0, now_ms),
                     created_at_ms: case.created_at_ms_utc.0,
+                    day_bucket: day_bucket(
+                        case.created_at_ms_utc.0,
+                        now_ms,
+                        model.utc_offset_minutes,
+                    ),
                     wound_severity: case.wound_severity,
                     is_mine,
                     is_local: false,
-                    has_photo: case.photo_url.is_some(),
+                    has_photo: case.photo_url.is_some() || case.thumbnail_url.is_some(),
+                    thumbnail_url: case.thumbnail_url.clone(),
                     sync_status: None,
+                    is_new: model.is_case_new(&case.id.0, case.created_at_ms_utc.0),
+                    is_highlighted: is_within_highlight_radius(distance, model.highlight_radius_m),
                 });
             }
 
-            items.sort_by(|a, b| {
-                a.distance_meters
-                    .partial_cmp(&b.distance_meters)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            items.sort_by(|a, b| model.list_sort.compare(a, b));
+
+            if let Some(limit) = model.list_limit {
+                items.truncate(limit);
+            }
 
             items
         }
 
-        fn build_case_detail(model: &Model, case_id: &str, now_ms: u64) -> Option<CaseDetail> {
-            let user_loc = model.area_center?;
-            let user_id = model.user_id.as_ref();
+        /// Thumbnails are encoded in the same format as the photo they came from.
+        fn thumbnail_data_uri(data: &[u8], mime_type: Option<&str>) -> String {
+            use base64::Engine as _;
+            format!(
+                "data:{};base64,{}",
+                mime_type.unwrap_or(PROCESSED_IMAGE_MIME_TYPE),
+                base64::engine::general_purpose::STANDARD.encode(data)
+            )
+        }
+
+        fn list_total_count(model: &Model) -> usize {
+            if model.area_center.is_none() {
+                return 0;
+            }
+            let locals = if model.passes_status_filter(CaseStatus::Pending) {
+                model.offline_store.pending_local_cases.len()
+            } else {
+                0
+            };
+            locals
+                + model
+                    .cases
+                    .iter()
+                    .filter(|c| model.passes_status_filter(c.status))
+                    .count()
+        }
+
+        fn coordinates_text(location: &LatLon) -> String {
+            ValidatedCoordinate::new(location.lat, location.lon)
+                .map(|coord| format_coordinate(coord, CoordFormat::DecimalDegrees))
+                .unwrap_or_else(|_| "Unknown".to_string())
+        }
+
+        fn share_text(location: &LatLon, landmark_hint: Option<&str>) -> String {
+            let coord = match ValidatedCoordinate::new(location.lat, location.lon) {
+                Ok(coord) => coord,
+                Err(_) => return "Animal in need of rescue".to_string(),
+            };
+
+            let position = format!(
+                "{} ({})",
+                format_coordinate(coord, CoordFormat::DegreesMinutesSeconds),
+                format_coordinate(coord, CoordFormat::DecimalDegrees)
+            );
+
+            match landmark_hint {
+                Some(hint) if !hint.trim().is_empty() => {
+                    format!("Animal in need of rescue near {}: {position}", hint.trim())
+                }
+                _ => format!("Animal in need of rescue at {position}"),
+            }
+        }
+
+        fn build_case_detail(model: &Model, case_id: &str, now_ms: u64) -> Option<CaseDetail> {
+            let user_loc = model.area_center?;
+            let user_id = model.user_id.as_ref();
 
             if let Some(local_case) = model
                 .offline_store
@@ -2734,52 +6797,62 @@ pub mod app {
                     .map(|coord| haversine_distance(user_loc, coord))
                     .unwrap_or(f64::MAX);
 
-                let sync_status = match local_case.status {
-                    LocalCaseStatus::PendingUpload => Some("Pending sync".into()),
-                    LocalCaseStatus::Uploading => Some("Syncing...".into()),
-                    LocalCaseStatus::UploadingPhoto => Some("Uploading photo...".into()),
-                    LocalCaseStatus::Failed => Some("Sync failed - tap to retry".into()),
-                    LocalCaseStatus::PermanentlyFailed => Some("Sync failed permanently".into()),
-                    LocalCaseStatus::Synced => None,
-                };
+                let sync_status = sync_status_text(local_case.status, local_case.retry_count);
 
                 return Some(CaseDetail {
                     id: local_case.local_id.0.clone(),
                     description: local_case.description.clone(),
                     landmark_hint: local_case.landmark_hint.clone(),
                     status: CaseStatus::Pending,
+                    status_text: model.catalog().status_name(CaseStatus::Pending),
                     wound_severity: local_case.wound_severity,
                     species_guess: None,
                     lat: local_case.location.lat,
                     lon: local_case.location.lon,
-                    distance_text: format_distance(distance),
-                    time_ago: format_time_ago(local_case.created_at_ms_utc.0, now_ms),
+                    coordinates_text: Self::coordinates_text(&local_case.location),
+                    share_text: Self::share_text(
+                        &local_case.location,
+                        local_case.landmark_hint.as_deref(),
+                    ),
+                    distance_text: format_distance_unit(distance, model.distance_unit),
+                    time_ago: format_time_ago_styled(
+                        local_case.created_at_ms_utc.0,
+                        now_ms,
+                        TimeAgoStyle::Verbose,
+                    ),
                     created_at_ms: local_case.created_at_ms_utc.0,
                     can_claim: false,
                     claim_state: ClaimState::ClaimedByMe,
+                    claim_blocked_reason: Some(ClaimBlockedReason::AlreadyClaimedByMe),
+                    can_resume: false,
                     available_transitions: vec![],
                     photo_url: None,
-                    thumbnail_url: None,
+                    thumbnail_url: local_case.thumbnail_data.as_deref().map(|data| {
+                        Self::thumbnail_data_uri(data, local_case.photo_mime_type.as_deref())
+                    }),
                     gemini_diagnosis: None,
                     reporter_is_me: true,
                     is_local: true,
                     sync_status,
+                    resolution_outcome: None,
                 });
             }
 
             let case = model.cases.iter().find(|c| c.id.0 == case_id)?;
 
-            let distance = case.distance_meters.unwrap_or_else(|| {
-                ValidatedCoordinate::new(case.location.lat, case.location.lon)
-                    .map(|coord| haversine_distance(user_loc, coord))
-                    .unwrap_or(f64::MAX)
-            });
+            let distance = case_distance(case, user_loc);
 
             let is_reporter = user_id.map(|uid| &case.reporter_id == uid).unwrap_or(false);
 
-            let claim_state = if !case.status.is_claimable() {
+            let claim_in_flight = model.pending_claims.contains_key(&case.id);
+            let claim_state = if !claim_in_flight
+                && model.is_assigned_to_me(case)
+                && !case.status.is_terminal()
+            {
+                ClaimState::ClaimedByMe
+            } else if !case.status.is_claimable() {
                 ClaimState::NotClaimable
-            } else if model.pending_claims.contains_key(&case.id) {
+            } else if claim_in_flight {
                 ClaimState::Claiming
             } else {
                 match &case.assigned_rescuer_id {
@@ -2790,6 +6863,8 @@ pub mod app {
             };
 
             let can_claim = claim_state == ClaimState::Available && model.is_authenticated();
+            let claim_blocked_reason =
+                ClaimBlockedReason::for_claim_state(&claim_state, model.is_authenticated());
 
             let available_transitions = if user_id
                 .map(|uid| case.assigned_rescuer_id.as_ref() == Some(uid))
@@ -2805,15 +6880,24 @@ pub mod app {
                 description: case.description.clone(),
                 landmark_hint: case.landmark_hint.clone(),
                 status: case.status,
+                status_text: model.catalog().status_name(case.status),
                 wound_severity: case.wound_severity,
                 species_guess: case.species_guess.clone(),
                 lat: case.location.lat,
                 lon: case.location.lon,
-                distance_text: format_distance(distance),
-                time_ago: format_time_ago(case.created_at_ms_utc.0, now_ms),
+                coordinates_text: Self::coordinates_text(&case.location),
+                share_text: Self::share_text(&case.location, case.landmark_hint.as_deref()),
+                distance_text: format_distance_unit(distance, model.distance_unit),
+                time_ago: format_time_ago_styled(
+                    case.created_at_ms_utc.0,
+                    now_ms,
+                    TimeAgoStyle::Verbose,
+                ),
                 created_at_ms: case.created_at_ms_utc.0,
                 can_claim,
+                can_resume: claim_state == ClaimState::ClaimedByMe,
                 claim_state,
+                claim_blocked_reason,
                 available_transitions,
                 photo_url: case.photo_url.clone(),
                 thumbnail_url: case.thumbnail_url.clone(),
@@ -2821,6 +6905,7 @@ pub mod app {
                 reporter_is_me: is_reporter,
                 is_local: false,
                 sync_status: None,
+                resolution_outcome: case.resolution_outcome,
             })
         }
 
@@ -2840,25 +6925,30 @@ pub mod app {
                 ));
             }
 
-            let format = image::guess_format(&data).map_err(|e| {
-                AppError::new(ErrorKind::ImageFormatUnsupported, e.to_string())
-            })?;
-
-            let reader = image::io::Reader::with_format(std::io::Cursor::new(&data), format);
-
-            let limits = image::io::Limits {
-                max_image_width: Some(MAX_IMAGE_DIMENSION),
-                max_image_height: Some(MAX_IMAGE_DIMENSION),
-                max_alloc: Some(MAX_IMAGE_ALLOC),
-            };
-
-            let img = reader
-                .with_limits(limits)
-                .decode()
-                .map_err(|e| AppError::new(ErrorKind::ImageProcessing, e.to_string()))?;
+            let cache_settings = model.photo_processing_fingerprint();
+            if let Some(cached) = model
+                .photo_cache
+                .as_mut()
+                .and_then(|cache| cache.get(&data, &cache_settings))
+            {
+                caps.telemetry().event("image_cache_hit", &[]);
+                return Ok(cached);
+            }
 
+            let (img, format, orientation) = Self::decode_image(&data, model.max_image_alloc)?;
             let (width, height) = (img.width(), img.height());
 
+            caps.telemetry().event(
+                "image_orientation",
+                &[
+                    ("orientation", &format!("{orientation:?}")),
+                    (
+                        "rotated",
+                        &(orientation != image::metadata::Orientation::NoTransforms).to_string(),
+                    ),
+                ],
+            );
+
             caps.telemetry().event(
                 "image_decoded",
                 &[
@@ -2878,51 +6968,21 @@ pub mod app {
                 img.clone()
             };
 
-            let mut processed_data = Vec::new();
-            processed_img
-                .write_to(
-                    &mut std::io::Cursor::new(&mut processed_data),
-                    image::ImageFormat::WebP,
-                )
+            let output_format = model.capture_config.output_format;
+            let processed_data = output_format
+                .encode(&processed_img, model.capture_config.quality)
                 .map_err(|e| AppError::new(ErrorKind::ImageProcessing, e.to_string()))?;
 
-            let (detections, cropped_data) = if let Some(detector) = &mut model.yolo_detector {
-                let raw_pixels: Vec<u8> = img.to_rgb8().into_raw();
-                let dets = detector.detect(&raw_pixels, width, height);
-
-                let cropped = if !dets.is_empty() {
-                    let merged = crate::image_processing::merge_bboxes(&dets);
-                    let padded = crate::image_processing::pad_bbox(merged, 0.15, width, height);
-
-                    let cropped_img = crate::image_processing::crop_image(&img, padded);
-
-                    let mut cropped_bytes = Vec::new();
-                    cropped_img
-                        .write_to(
-                            &mut std::io::Cursor::new(&mut cropped_bytes),
-                            image::ImageFormat::WebP,
-                        )
-                        .ok();
-
-                    if cropped_bytes.is_empty() {
-                        None
-                    } else {
-                        Some(cropped_bytes)
-                    }
-                } else {
-                    None
-                };
+            let thumbnail =
+                processed_img.thumbnail(THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION);
+            let thumbnail_data = output_format
+                .encode(&thumbnail, model.capture_config.quality)
+                .ok();
 
-                (dets, cropped)
-            } else {
-                (vec![], None)
-            };
+            let (detections, cropped_data) = Self::detect_and_crop(&img, model);
 
             let detection_count = detections.len();
-            let top_confidence = detections
-                .iter()
-                .map(|d| d.confidence)
-                .fold(0.0f32, f32::max);
+            let top_confidence = top_detection_confidence(&detections);
 
             caps.telemetry().event(
                 "image_processed",
@@ -2933,17 +6993,113 @@ pub mod app {
                 ],
             );
 
-            Ok(StagedPhoto {
+            let photo = StagedPhoto {
                 original_data: data,
                 processed_data,
                 cropped_data,
+                thumbnail_data,
                 width,
                 height,
-                mime_type: "image/webp".into(),
+                mime_type: output_format.mime_type().into(),
                 detection_count,
                 top_confidence,
                 detections,
-            })
+            };
+            if let Some(cache) = &mut model.photo_cache {
+                cache.insert(&photo, &cache_settings);
+            }
+            Ok(photo)
+        }
+
+        /// Decodes `data` and applies its EXIF orientation so detection and
+        /// crops see the photo upright. Re-encoding never writes EXIF, so the
+        /// processed bytes carry no orientation tag to be applied twice.
+        fn decode_image(
+            data: &[u8],
+            max_alloc: usize,
+        ) -> Result<
+            (
+                image::DynamicImage,
+                image::ImageFormat,
+                image::metadata::Orientation,
+            ),
+            AppError,
+        > {
+            let format = image::guess_format(data)
+                .map_err(|e| AppError::new(ErrorKind::ImageFormatUnsupported, e.to_string()))?;
+
+            let reader = image::io::Reader::with_format(std::io::Cursor::new(data), format);
+
+            let limits = image::io::Limits {
+                max_image_width: Some(MAX_IMAGE_DIMENSION),
+                max_image_height: Some(MAX_IMAGE_DIMENSION),
+                max_alloc: Some(u64::try_from(max_alloc).unwrap_or(u64::MAX)),
+            };
+
+            let to_app_error = |e: image::ImageError| match e {
+                image::ImageError::Limits(limit) => {
+                    AppError::new(ErrorKind::ImageDimensionsTooLarge, limit.to_string())
+                        .with_context("max_alloc_bytes", max_alloc.to_string())
+                }
+                other => AppError::new(ErrorKind::ImageProcessing, other.to_string()),
+            };
+
+            let mut decoder = reader
+                .with_limits(limits)
+                .into_decoder()
+                .map_err(to_app_error)?;
+            let orientation = image::ImageDecoder::orientation(&mut decoder)
+                .unwrap_or(image::metadata::Orientation::NoTransforms);
+            let mut img = image::DynamicImage::from_decoder(decoder).map_err(to_app_error)?;
+            img.apply_orientation(orientation);
+
+            Ok((img, format, orientation))
+        }
+
+        fn detect_and_crop(
+            img: &image::DynamicImage,
+            model: &mut Model,
+        ) -> (Vec<crate::vision::Detection>, Option<Vec<u8>>) {
+            let detector_config = model.detector_config;
+            let min_crop_confidence = model.min_crop_confidence;
+            let merge_strategy = model.crop_merge_strategy;
+            let padding = model.crop_padding_fraction;
+            let aspect = model.capture_config.aspect_ratio_value();
+            let output_format = model.capture_config.output_format;
+            let quality = model.capture_config.quality;
+            let detector = match &mut model.yolo_detector {
+                Some(detector) => detector,
+                None => return (vec![], None),
+            };
+
+            let (width, height) = (img.width(), img.height());
+            let raw_pixels: Vec<u8> = img.to_rgb8().into_raw();
+            detector.set_detector_config(detector_config);
+            let dets = detector.detect(&raw_pixels, width, height);
+
+            let cropped = if should_auto_crop(&dets, min_crop_confidence) {
+                crate::image_processing::primary_bbox(&dets, merge_strategy)
+                    .ok()
+                    .and_then(|merged| {
+                        let padded = match aspect {
+                            Some(aspect) => crate::image_processing::pad_bbox_aspect(
+                                merged, padding, aspect, width, height,
+                            ),
+                            None => crate::image_processing::pad_bbox(merged, padding),
+                        };
+
+                        let cropped_img = crate::image_processing::crop_image(img, padded);
+
+                        output_format
+                            .encode(&cropped_img, quality)
+                            .ok()
+                            .filter(|bytes| !bytes.is_empty())
+                    })
+            } else {
+                None
+            };
+
+            (dets, cropped)
         }
 
         fn send_create_case_request(
@@ -2970,7 +7126,14 @@ pub mod app {
                 landmark_hint: landmark_hint.clone(),
                 wound_severity: *wound_severity,
                 photo_mime_type: if *has_photo {
-                    Some("image/webp".into())
+                    let mime_type = model
+                        .offline_store
+                        .pending_local_cases
+                        .iter()
+                        .find(|c| &c.local_id == local_id)
+                        .and_then(|c| c.photo_mime_type.as_deref())
+                        .and_then(normalize_mime);
+                    Some(mime_type.unwrap_or(PROCESSED_IMAGE_MIME_TYPE).into())
                 } else {
                     None
                 },
@@ -3010,6 +7173,7 @@ pub mod app {
             upload_url: &str,
             upload_headers: &HashMap<String, String>,
             photo_data: &[u8],
+            redirected: bool,
             caps: &Capabilities,
         ) {
             let local_id_str = local_id.0.clone();
@@ -3025,6 +7189,7 @@ pub mod app {
 
             builder.send(move |result| Event::PhotoUploadResponse {
                 local_id: local_id_str,
+                redirected,
                 result: Box::new(result),
             });
         }
@@ -3060,8 +7225,10 @@ pub mod app {
         fn send_transition_request(
             case_id: &CaseId,
             mutation_id: &str,
+            idempotency_key: &IdempotencyKey,
             next_status: CaseStatus,
             notes: Option<String>,
+            outcome: Option<ResolutionOutcome>,
             model: &Model,
             caps: &Capabilities,
         ) {
@@ -3071,6 +7238,7 @@ pub mod app {
             let request = TransitionCaseRequest {
                 next_status: next_status.as_str().to_string(),
                 notes,
+                outcome,
             };
 
             let body = match serde_json::to_vec(&request) {
@@ -3082,12 +7250,11 @@ pub mod app {
             };
 
             let url = format!("/api/v1/cases/{}/transition", case_id.0);
-            let idempotency_key = Uuid::new_v4().to_string();
 
             let mut builder = caps.http().post(&url);
             builder = builder
                 .header("Content-Type", "application/json")
-                .header("Idempotency-Key", &idempotency_key)
+                .header("Idempotency-Key", idempotency_key.as_str())
                 .timeout(TRANSITION_TIMEOUT)
                 .body(body);
 
@@ -3102,12 +7269,25 @@ pub mod app {
             });
         }
 
-        fn send_refresh_request(model: &Model, caps: &Capabilities, cursor: Option<&str>) {
+        /// A first-page refresh starts a new generation so responses to any
+        /// earlier refresh or page load are dropped when they arrive.
+        fn send_refresh_request(model: &mut Model, caps: &Capabilities, cursor: Option<&str>) {
             let center = match model.area_center {
                 Some(c) => c,
                 None => return,
             };
 
+            if model.defer_for_token_refresh(get_current_time_ms()) {
+                model.is_refreshing = false;
+                if cursor.is_none() {
+                    model.refresh_deferred_for_token = true;
+                }
+                caps.telemetry()
+                    .event("refresh_deferred", &[("reason", "token_expiring")]);
+                caps.render().render();
+                return;
+            }
+
             let mut url = format!(
                 "/api/v1/cases?lat={}&lng={}&radius={}",
                 center.lat(),
@@ -3126,10 +7306,61 @@ pub mod app {
                 builder = builder.header("Authorization", &format!("Bearer {token}"));
             }
 
+            if let (None, Some(etag)) = (cursor, &model.cases_etag) {
+                builder = builder.header("If-None-Match", etag);
+            }
+
             if cursor.is_some() {
-                builder.send(|result| Event::LoadMoreResponse(Box::new(result)));
+                let generation = model.refresh_generation;
+                builder.send(move |result| Event::LoadMoreResponse {
+                    generation,
+                    result: Box::new(result),
+                });
             } else {
-                builder.send(|result| Event::RefreshResponse(Box::new(result)));
+                model.refresh_generation += 1;
+                let generation = model.refresh_generation;
+                builder.send(move |result| Event::RefreshResponse {
+                    generation,
+                    result: Box::new(result),
+                });
+            }
+        }
+
+        fn replay_pending_pushes(&self, model: &mut Model, caps: &Capabilities) {
+            let pending = std::mem::take(&mut model.pending_pushes);
+            if pending.is_empty() {
+                return;
+            }
+
+            caps.telemetry().event(
+                "pending_pushes_replayed",
+                &[("count", &pending.len().to_string())],
+            );
+            for payload in pending {
+                self.update(Event::PushReceived(payload), model, caps);
+            }
+        }
+
+        fn request_refresh(model: &mut Model, caps: &Capabilities) {
+            model.is_refreshing = true;
+
+            if model.restore_before_refresh && model.restore_in_flight {
+                model.refresh_after_restore = true;
+                return;
+            }
+
+            Self::send_refresh_request(model, caps, None);
+        }
+
+        fn finish_restore(model: &mut Model, caps: &Capabilities) {
+            model.restore_in_flight = false;
+
+            if std::mem::take(&mut model.refresh_after_restore) {
+                if model.network_online {
+                    Self::send_refresh_request(model, caps, None);
+                } else {
+                    model.is_refreshing = false;
+                }
             }
         }
 
@@ -3169,6 +7400,292 @@ pub mod app {
             }
         }
 
+        fn enqueue_local_case(
+            payload: CreateCasePayload,
+            photo_data: Option<Vec<u8>>,
+            photo_mime_type: Option<String>,
+            from_staged_photo: bool,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) -> Result<(), AppError> {
+            let coord = Self::validate_coordinates(payload.location.0, payload.location.1)?;
+            Self::validate_wound_severity(payload.wound_severity)?;
+
+            let has_photo = photo_data.is_some();
+            let incoming = photo_data.as_ref().map_or(0, Vec::len);
+            let released = if from_staged_photo {
+                model
+                    .staged_photo
+                    .as_ref()
+                    .map_or(0, StagedPhoto::memory_bytes)
+            } else {
+                0
+            };
+            if let Err(e) = model.reserve_image_memory(incoming, released) {
+                caps.telemetry()
+                    .warn("image_memory_cap_reached", &e.message);
+                return Err(e);
+            }
+
+            let mut local_case = LocalCase::new(
+                coord.into(),
+                payload.description.clone(),
+                payload.wound_severity,
+            );
+            local_case.landmark_hint = payload.landmark_hint.clone();
+            local_case.photo_data = photo_data;
+            if has_photo {
+                local_case.photo_mime_type = photo_mime_type
+                    .as_deref()
+                    .and_then(normalize_mime)
+                    .map(str::to_string);
+            }
+            if has_photo && from_staged_photo {
+                local_case.thumbnail_data = model
+                    .staged_photo
+                    .as_ref()
+                    .and_then(|photo| photo.thumbnail_data.clone());
+            }
+
+            let local_id = local_case.local_id.clone();
+
+            model.offline_store.push_local_case(local_case)?;
+
+            let intent = OutboxIntent::CreateCase {
+                local_id: local_id.clone(),
+                location: coord.into(),
+                description: payload.description,
+                landmark_hint: payload.landmark_hint,
+                wound_severity: payload.wound_severity,
+                has_photo,
+                created_at_ms_utc: UnixTimeMs::now(),
+            };
+
+            model.offline_store.push_outbox(OutboxEntry::new(intent))?;
+
+            model.outbox_capacity_reached = None;
+            if from_staged_photo {
+                model.staged_photo = None;
+            }
+            model.map_center = Some(coord);
+
+            model.mark_store_dirty();
+
+            model.show_catalog_toast(ToastKey::CaseCreated, ToastKind::Success);
+            caps.telemetry()
+                .event("case_created_local", &[("local_id", &local_id.0)]);
+
+            Ok(())
+        }
+
+        fn parse_checked_response<T>(
+            body: &[u8],
+            endpoint: &str,
+            caps: &Capabilities,
+        ) -> Result<T, ResponseParseError>
+        where
+            T: serde::de::DeserializeOwned + Serialize,
+        {
+            let result = parse_response(body, cfg!(feature = "strict_responses"));
+            if let Err(ResponseParseError::UnknownField(field)) = &result {
+                caps.telemetry()
+                    .error("response_unknown_fields", &format!("{endpoint}: {field}"));
+            }
+            result
+        }
+
+        fn remove_failed_operation(
+            op_id: &str,
+            reason: RemovalReason,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) {
+            match model
+                .offline_store
+                .remove_failed_entry(&OpId(op_id.to_string()), reason)
+            {
+                Ok(entry) => {
+                    if let Some(case_id) = entry.intent.case_id() {
+                        model.rollback_case_to_server_truth(case_id);
+                    }
+                    Self::persist_now(model, caps);
+                    caps.telemetry().event(
+                        "outbox_entry_removed",
+                        &[
+                            ("op_id", op_id),
+                            ("intent", entry.intent.intent_type()),
+                            ("reason", reason.as_str()),
+                        ],
+                    );
+                }
+                Err(OutboxError::NotFound(_)) => {
+                    // Already synced or removed; the re-render drops it from the list.
+                    caps.telemetry().warn("outbox_remove_not_found", op_id);
+                }
+                Err(e) => {
+                    caps.telemetry()
+                        .warn("outbox_remove_rejected", &e.to_string());
+                    let syncing = model
+                        .offline_store
+                        .outbox
+                        .iter()
+                        .find(|entry| entry.op_id.0 == op_id)
+                        .is_some_and(|entry| model.offline_store.has_in_flight_sibling(entry));
+                    let key = if syncing {
+                        ToastKey::DiscardWhileSyncing
+                    } else {
+                        ToastKey::OnlyFailedDiscardable
+                    };
+                    model.show_catalog_toast(key, ToastKind::Warning);
+                }
+            }
+            caps.render().render();
+        }
+
+        fn apply_deferred_push_patches(model: &mut Model, caps: &Capabilities) {
+            let applied = model.apply_deferred_push_patches();
+            if applied > 0 {
+                caps.telemetry().event(
+                    "deferred_push_patches_applied",
+                    &[("count", &applied.to_string())],
+                );
+            }
+        }
+
+        fn record_request_outcome(
+            endpoint: &str,
+            result: &Result<HttpOutput, HttpError>,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) {
+            match result {
+                Err(HttpError::Timeout) => {
+                    model.timeout_tracker.record_timeout(endpoint);
+                }
+                Ok(_) => model.timeout_tracker.record_response(endpoint),
+                Err(_) => {}
+            }
+
+            let failed = match result {
+                Ok(output) => output.status >= 500 || output.status == 429,
+                Err(_) => true,
+            };
+
+            if failed {
+                if model.circuit_breaker.record_failure(get_current_time_ms()) {
+                    caps.telemetry()
+                        .warn("circuit_opened", "failure rate threshold exceeded");
+                }
+            } else if model.circuit_breaker.record_success() {
+                caps.telemetry().event("circuit_closed", &[]);
+            }
+        }
+
+        fn drop_cancelled_response(
+            request_id: &str,
+            endpoint: &str,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) -> bool {
+            if !model.take_cancelled_request(request_id) {
+                return false;
+            }
+
+            caps.telemetry().event(
+                "cancelled_response_dropped",
+                &[("endpoint", endpoint), ("request_id", request_id)],
+            );
+            true
+        }
+
+        /// Queues a claim or transition made offline. The optimistic change
+        /// stays applied; if the outbox is full it is rolled back instead.
+        fn queue_case_action(
+            &self,
+            intent: OutboxIntent,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) -> bool {
+            let case_id = intent.case_id().cloned();
+            match model.offline_store.push_outbox(OutboxEntry::new(intent)) {
+                Ok(()) => {
+                    model.mark_store_dirty();
+                    model.show_catalog_toast(ToastKey::SavedOffline, ToastKind::Info);
+                    true
+                }
+                Err(e) => {
+                    if let Some(case_id) = &case_id {
+                        model.rollback_case_to_server_truth(case_id);
+                    }
+                    self.enqueue_failed(e.into(), model, caps);
+                    false
+                }
+            }
+        }
+
+        /// Completes or fails the outbox entry behind a claim/transition
+        /// response. Responses to direct (online) requests have no entry.
+        fn settle_queued_case_action(
+            &self,
+            case_id: &str,
+            result: &Result<HttpOutput, HttpError>,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) {
+            let op_id = match model
+                .offline_store
+                .queued_case_action(&CaseId::new(case_id))
+            {
+                Some(entry) if entry.retry_state == RetryState::InFlight => entry.op_id.0.clone(),
+                _ => return,
+            };
+
+            let event = match result {
+                Ok(output) if output.is_success() => Event::OutboxEntryCompleted { op_id },
+                Ok(output) => Event::OutboxEntryFailed {
+                    op_id,
+                    error: format!("HTTP {}", output.status),
+                    is_permanent: OutboxEntryError::server_error(output.status, None).is_permanent,
+                },
+                Err(e) => Event::OutboxEntryFailed {
+                    op_id,
+                    error: format!("{e:?}"),
+                    is_permanent: false,
+                },
+            };
+            self.update(event, model, caps);
+        }
+
+        fn enqueue_failed(&self, error: AppError, model: &mut Model, caps: &Capabilities) {
+            let full = error
+                .context
+                .get("outbox_kind")
+                .and_then(|kind| OutboxKind::from_str(kind));
+            model.set_error(error);
+            match full {
+                Some(kind) => self.update(Event::OutboxCapacityReached { kind }, model, caps),
+                None => caps.render().render(),
+            }
+        }
+
+        fn prune_out_of_radius(model: &mut Model, caps: &Capabilities) {
+            let pruned = model.prune_out_of_radius();
+            if pruned > 0 {
+                caps.telemetry().event(
+                    "cases_pruned_out_of_radius",
+                    &[("count", &pruned.to_string())],
+                );
+            }
+        }
+
+        fn reset_sibling_backoff(model: &mut Model, caps: &Capabilities) {
+            let reset = model.reset_sibling_backoff(get_current_time_ms());
+            if reset > 0 {
+                caps.telemetry()
+                    .event("outbox_backoff_reset", &[("count", &reset.to_string())]);
+            }
+        }
+
         fn handle_create_case_response(
             op_id: &str,
             result: &Result<HttpOutput, HttpError>,
@@ -3177,9 +7694,15 @@ pub mod app {
         ) {
             let op_id_typed = OpId::new(op_id);
 
+            Self::flag_unexpected_redirect("create_case", result, model, caps);
+
             match result {
                 Ok(output) if output.is_success() => {
-                    match serde_json::from_slice::<CreateCaseResponse>(&output.body) {
+                    match Self::parse_checked_response::<CreateCaseResponse>(
+                        &output.body,
+                        "create_case",
+                        caps,
+                    ) {
                         Ok(response) => {
                             if let Some(local_case) = model
                                 .offline_store
@@ -3192,14 +7715,18 @@ pub mod app {
                                 if let Some(upload_url) = &response.photo_upload_url {
                                     if local_case.photo_data.is_some() {
                                         local_case.photo_upload_url = Some(upload_url.clone());
+                                        local_case.photo_upload_headers = response
+                                            .photo_upload_headers
+                                            .clone()
+                                            .unwrap_or_default();
                                         local_case.mark_uploading_photo();
 
-                                        let headers = response.photo_upload_headers.clone().unwrap_or_default();
                                         Self::send_photo_upload(
                                             &local_case.local_id,
                                             upload_url,
-                                            &headers,
+                                            &local_case.photo_upload_headers,
                                             local_case.photo_data.as_ref().unwrap(),
+                                            false,
                                             caps,
                                         );
                                     } else {
@@ -3214,13 +7741,15 @@ pub mod app {
                                 model.offline_store.mark_entry_completed(&op_id_typed);
                             }
 
+                            Self::reset_sibling_backoff(model, caps);
                             caps.telemetry().event("case_created_success", &[("server_id", &response.id)]);
                         }
                         Err(e) => {
                             caps.telemetry().error("case_response_parse_failed", &e.to_string());
-                            model.offline_store.mark_entry_failed(
+                            model.offline_store.mark_entry_failed_with_strategy(
                                 &op_id_typed,
                                 OutboxEntryError::new("PARSE_ERROR").with_message(e.to_string()),
+                                model.outbox_tuning.backoff_strategy,
                             );
                         }
                     }
@@ -3230,18 +7759,21 @@ pub mod app {
                     model.offline_store.mark_entry_completed(&op_id_typed);
                 }
                 Ok(output) if output.status == 429 => {
-                    let retry_after = output
-                        .header("Retry-After")
-                        .and_then(|v| v.parse::<u64>().ok())
-                        .map(|s| s * 1000)
-                        .unwrap_or(60_000);
-
-                    if let Some(entry) = model.offline_store.get_entry_mut(&op_id_typed) {
-                        entry.mark_rate_limited(retry_after);
-                    }
+                    let retry_after = Self::retry_after_ms(output);
+                    model.set_rate_limited("create_case", get_current_time_ms(), retry_after);
+
+                    model
+                        .offline_store
+                        .mark_entry_rate_limited(&op_id_typed, retry_after);
                     caps.telemetry().warn("case_create_rate_limited", op_id);
                 }
-                Ok(output) if output.status >= 400 && output.status < 500 => {
+                Ok(output)
+                    if !classify_retryability(
+                        Some(output.status),
+                        ErrorKind::from_http_status(output.status),
+                    )
+                    .is_retryable() =>
+                {
                     let error = OutboxEntryError::server_error(output.status, None);
                     model.offline_store.mark_entry_permanently_failed(&op_id_typed, error);
 
@@ -3258,24 +7790,49 @@ pub mod app {
                 }
                 Ok(output) => {
                     let error = OutboxEntryError::server_error(output.status, None);
-                    model.offline_store.mark_entry_failed(&op_id_typed, error);
+                    model.offline_store.mark_entry_failed_with_strategy(
+                        &op_id_typed,
+                        error,
+                        model.outbox_tuning.backoff_strategy,
+                    );
                     caps.telemetry().warn("case_create_server_error", &output.status.to_string());
                 }
+                Err(HttpError::Timeout) => {
+                    let endpoint = "create_case";
+                    let threshold = model.slow_link_timeout_threshold;
+                    let streak = model.timeout_tracker.streak(endpoint);
+
+                    model.offline_store.mark_entry_timed_out(
+                        &op_id_typed,
+                        streak,
+                        threshold,
+                        model.outbox_tuning.backoff_strategy,
+                    );
+
+                    if streak == threshold {
+                        model.show_catalog_toast(ToastKey::SlowConnection, ToastKind::Warning);
+                        caps.telemetry().warn("slow_link_escalated", endpoint);
+                    }
+                    caps.telemetry()
+                        .warn("case_create_timeout", &streak.to_string());
+                }
                 Err(e) => {
-                    let error = match e {
-                        HttpError::Timeout => OutboxEntryError::timeout_error(),
-                        _ => OutboxEntryError::network_error(format!("{e:?}")),
-                    };
-                    model.offline_store.mark_entry_failed(&op_id_typed, error);
+                    let error = OutboxEntryError::network_error(format!("{e:?}"));
+                    model.offline_store.mark_entry_failed_with_strategy(
+                        &op_id_typed,
+                        error,
+                        model.outbox_tuning.backoff_strategy,
+                    );
                     caps.telemetry().warn("case_create_network_error", &format!("{e:?}"));
                 }
             }
 
-            Self::persist_store(model, caps);
+            model.mark_store_dirty();
         }
 
         fn handle_photo_upload_response(
             local_id: &str,
+            redirected: bool,
             result: &Result<HttpOutput, HttpError>,
             model: &mut Model,
             caps: &Capabilities,
@@ -3311,6 +7868,41 @@ pub mod app {
 
                     caps.telemetry().event("photo_upload_success", &[("local_id", local_id)]);
                 }
+                Ok(output) if output.is_redirect() => {
+                    let base_url = local_case.photo_upload_url.as_deref();
+                    let location = output
+                        .header("Location")
+                        .filter(|_| !redirected)
+                        .and_then(|location| resolve_redirect_location(base_url, location));
+                    match location {
+                        Some(location) => {
+                            local_case.photo_upload_url = Some(location.clone());
+                            if let Some(photo_data) = &local_case.photo_data {
+                                Self::send_photo_upload(
+                                    &local_case.local_id,
+                                    &location,
+                                    &local_case.photo_upload_headers,
+                                    photo_data,
+                                    true,
+                                    caps,
+                                );
+                            }
+                            caps.telemetry().event(
+                                "photo_upload_redirected",
+                                &[
+                                    ("local_id", local_id),
+                                    ("status", &output.status.to_string()),
+                                ],
+                            );
+                        }
+                        None => {
+                            local_case
+                                .mark_failed(format!("Upload redirect failed: {}", output.status));
+                            caps.telemetry()
+                                .error("photo_upload_redirect_failed", &output.status.to_string());
+                        }
+                    }
+                }
                 Ok(output) => {
                     local_case.mark_failed(format!("Upload failed: {}", output.status));
                     caps.telemetry().error("photo_upload_failed", &output.status.to_string());
@@ -3321,39 +7913,112 @@ pub mod app {
                 }
             }
 
-            Self::persist_store(model, caps);
+            model.mark_store_dirty();
         }
 
-        fn handle_claim_response(
-            case_id: &str,
-            mutation_id: &str,
+        fn flag_unexpected_redirect(
+            endpoint: &str,
             result: &Result<HttpOutput, HttpError>,
             model: &mut Model,
             caps: &Capabilities,
         ) {
-            let case_id_typed = CaseId::new(case_id);
-            model.pending_claims.remove(&case_id_typed);
-
-            match result {
-                Ok(output) if output.is_success() => {
-                    model.commit_mutation(mutation_id);
+            let output = match result {
+                Ok(output) if output.is_redirect() && output.status != 304 => output,
+                _ => return,
+            };
 
-                    if let Ok(response) = serde_json::from_slice::<ClaimCaseResponse>(&output.body) {
+            let location = output.header("Location").unwrap_or("none");
+            model.set_error(
+                AppError::new(
+                    ErrorKind::Configuration,
+                    format!("Unexpected redirect from {endpoint}"),
+                )
+                .with_context("http_status", output.status.to_string())
+                .with_context("location", location),
+            );
+            caps.telemetry().error(
+                "api_unexpected_redirect",
+                &format!("{endpoint}: {} -> {location}", output.status),
+            );
+        }
+
+        fn retry_after_ms(output: &HttpOutput) -> u64 {
+            output
+                .header("Retry-After")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map_or(DEFAULT_RETRY_AFTER_MS, |s| s.saturating_mul(1000))
+        }
+
+        fn blocked_by_rate_limit(endpoint: &str, model: &mut Model, caps: &Capabilities) -> bool {
+            let remaining_ms = match model.rate_limit_remaining_ms(endpoint, get_current_time_ms())
+            {
+                Some(ms) => ms,
+                None => {
+                    model.rate_limited_until_ms.remove(endpoint);
+                    return false;
+                }
+            };
+
+            let seconds = remaining_ms.div_ceil(1000);
+            model.show_rate_limited_toast(seconds);
+            caps.telemetry().event(
+                "rate_limit_blocked",
+                &[
+                    ("endpoint", endpoint),
+                    ("remaining_s", &seconds.to_string()),
+                ],
+            );
+            caps.render().render();
+            true
+        }
+
+        fn handle_claim_response(
+            case_id: &str,
+            mutation_id: &str,
+            result: &Result<HttpOutput, HttpError>,
+            model: &mut Model,
+            caps: &Capabilities,
+        ) {
+            let case_id_typed = CaseId::new(case_id);
+            model.pending_claims.remove(&case_id_typed);
+
+            Self::flag_unexpected_redirect("claim_case", result, model, caps);
+
+            match result {
+                Ok(output) if output.is_success() => {
+                    model.commit_mutation(mutation_id);
+
+                    if let Ok(response) = Self::parse_checked_response::<ClaimCaseResponse>(
+                        &output.body,
+                        "claim_case",
+                        caps,
+                    ) {
                         if let Some(updated_case) = response.case {
-                            if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                *case = updated_case;
+                            let returned_id = updated_case.id.0.clone();
+                            if model.apply_server_case_update(case_id, updated_case) {
+                                caps.telemetry().warn(
+                                    "claim_case_id_mismatch",
+                                    &format!("requested {case_id}, server returned {returned_id}"),
+                                );
                             }
                         }
                     }
 
-                    model.show_toast("Case claimed successfully", ToastKind::Success);
+                    model.show_catalog_toast(ToastKey::CaseClaimed, ToastKind::Success);
                     caps.telemetry().event("claim_success", &[("case_id", case_id)]);
                 }
                 Ok(output) if output.status == 409 => {
                     model.rollback_mutation(mutation_id);
-                    model.show_toast("Case was claimed by another rescuer", ToastKind::Warning);
+                    model.show_catalog_toast(ToastKey::ClaimedByOther, ToastKind::Warning);
                     caps.telemetry().warn("claim_conflict", case_id);
                 }
+                Ok(output) if output.status == 429 => {
+                    model.rollback_mutation(mutation_id);
+                    let retry_after = Self::retry_after_ms(output);
+                    model.set_rate_limited("claim_case", get_current_time_ms(), retry_after);
+                    model.show_rate_limited_toast(retry_after.div_ceil(1000));
+                    caps.telemetry().warn("claim_rate_limited", case_id);
+                }
                 Ok(output) => {
                     model.rollback_mutation(mutation_id);
                     let error = Self::handle_http_error(&HttpError::Status {
@@ -3378,26 +8043,43 @@ pub mod app {
             model: &mut Model,
             caps: &Capabilities,
         ) {
+            Self::flag_unexpected_redirect("transition_case", result, model, caps);
+
             match result {
                 Ok(output) if output.is_success() => {
                     model.commit_mutation(mutation_id);
 
-                    if let Ok(response) = serde_json::from_slice::<TransitionCaseResponse>(&output.body) {
+                    if let Ok(response) = Self::parse_checked_response::<TransitionCaseResponse>(
+                        &output.body,
+                        "transition_case",
+                        caps,
+                    ) {
                         if let Some(updated_case) = response.case {
-                            if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                *case = updated_case;
+                            let returned_id = updated_case.id.0.clone();
+                            if model.apply_server_case_update(case_id, updated_case) {
+                                caps.telemetry().warn(
+                                    "transition_case_id_mismatch",
+                                    &format!("requested {case_id}, server returned {returned_id}"),
+                                );
                             }
                         }
                     }
 
-                    model.show_toast("Status updated", ToastKind::Success);
+                    model.show_catalog_toast(ToastKey::StatusUpdated, ToastKind::Success);
                     caps.telemetry().event("transition_success", &[("case_id", case_id)]);
                 }
                 Ok(output) if output.status == 409 => {
                     model.rollback_mutation(mutation_id);
-                    model.show_toast("Status was changed by someone else", ToastKind::Warning);
+                    model.show_catalog_toast(ToastKey::StatusChangedByOther, ToastKind::Warning);
                     caps.telemetry().warn("transition_conflict", case_id);
                 }
+                Ok(output) if output.status == 429 => {
+                    model.rollback_mutation(mutation_id);
+                    let retry_after = Self::retry_after_ms(output);
+                    model.set_rate_limited("transition_case", get_current_time_ms(), retry_after);
+                    model.show_rate_limited_toast(retry_after.div_ceil(1000));
+                    caps.telemetry().warn("transition_rate_limited", case_id);
+                }
                 Ok(output) => {
                     model.rollback_mutation(mutation_id);
                     let error = Self::handle_http_error(&HttpError::Status {
@@ -3420,21 +8102,72 @@ pub mod app {
             model: &mut Model,
             caps: &Capabilities,
             is_load_more: bool,
+            generation: u64,
         ) {
+            if generation < model.refresh_generation {
+                caps.telemetry().event(
+                    "stale_refresh_dropped",
+                    &[
+                        ("generation", &generation.to_string()),
+                        ("current", &model.refresh_generation.to_string()),
+                    ],
+                );
+                return;
+            }
             model.is_refreshing = false;
 
+            Self::flag_unexpected_redirect("list_cases", result, model, caps);
+
             match result {
                 Ok(output) if output.is_success() => {
-                    match serde_json::from_slice::<ListCasesResponse>(&output.body) {
+                    match Self::parse_checked_response::<ListCasesResponse>(
+                        &output.body,
+                        "list_cases",
+                        caps,
+                    ) {
                         Ok(response) => {
+                            let mut cases = response.cases;
+                            let outside = model.screen_out_of_radius(&mut cases);
+                            if outside > 0 {
+                                caps.telemetry().warn(
+                                    "refresh_cases_outside_radius",
+                                    &format!(
+                                        "{outside} case(s) beyond {}m radius (dropped: {})",
+                                        model.area_radius_m, model.drop_out_of_radius_cases
+                                    ),
+                                );
+                            }
+
                             if is_load_more {
-                                model.cases.extend(response.cases);
+                                let duplicates = model.merge_cases_page(cases);
+                                if duplicates > 0 {
+                                    caps.telemetry().event(
+                                        "load_more_duplicates_merged",
+                                        &[("count", &duplicates.to_string())],
+                                    );
+                                }
                             } else {
-                                model.cases = response.cases;
+                                model.cases = cases;
+                                model.cases_etag = output.header("ETag").map(str::to_string);
                             }
                             model.cases_cursor = response.next_cursor;
                             model.offline_store.update_last_refresh();
                             model.enforce_collection_limits();
+                            Self::prune_out_of_radius(model, caps);
+
+                            if model.reconcile_selection() {
+                                caps.telemetry().event(
+                                    "selected_case_removed",
+                                    &[(
+                                        "behavior",
+                                        &format!("{:?}", model.removed_selection_behavior),
+                                    )],
+                                );
+                            }
+
+                            if model.apply_pending_auto_select() {
+                                caps.telemetry().event("nearest_case_auto_selected", &[]);
+                            }
 
                             caps.telemetry().event(
                                 if is_load_more { "load_more_success" } else { "refresh_success" },
@@ -3446,6 +8179,22 @@ pub mod app {
                         }
                     }
                 }
+                Ok(output) if output.status == 304 && !is_load_more => {
+                    model.offline_store.update_last_refresh();
+                    caps.telemetry().event(
+                        "refresh_not_modified",
+                        &[("count", &model.cases.len().to_string())],
+                    );
+                }
+                Ok(output) if output.status == 429 => {
+                    model.set_rate_limited(
+                        "list_cases",
+                        get_current_time_ms(),
+                        Self::retry_after_ms(output),
+                    );
+                    caps.telemetry()
+                        .warn("refresh_rate_limited", &output.status.to_string());
+                }
                 Ok(output) => {
                     caps.telemetry().warn("refresh_failed", &output.status.to_string());
                 }
@@ -3454,32 +8203,25 @@ pub mod app {
                 }
             }
         }
-    }
-
-    impl crux_core::App for App {
-        type Event = Event;
-        type Model = Model;
-        type ViewModel = ViewModel;
-        type Capabilities = Capabilities;
-
-        fn update(&self, event: Event, model: &mut Model, caps: &Capabilities) {
-            model.update_timestamp();
-
-            let event_name = event.name();
-            caps.telemetry().counter(&format!("event.{event_name}"), 1);
-
-            if event.is_user_initiated() {
-                caps.telemetry().event("user_action", &[("event", event_name)]);
-            }
 
+        fn dispatch(&self, event: Event, model: &mut Model, caps: &Capabilities) {
             match event {
                 Event::Noop => {}
 
                 Event::AppStarted => {
                     model.state = AppState::Loading;
+                    caps.kv()
+                        .get(PREFERENCES_KV_KEY, |result| Event::PreferencesLoaded {
+                            result: Box::new(result),
+                        });
+                    caps.kv()
+                        .get(STORE_KEYS_KV_KEY, |result| Event::StoreKeysLoaded {
+                            result: Box::new(result),
+                        });
 
                     if let Some(model_bytes) = crate::vision::load_bundled_model() {
-                        match crate::vision::YoloDetector::new(&model_bytes) {
+                        match crate::vision::YoloDetector::new(&model_bytes, model.detector_config)
+                        {
                             Ok(detector) => {
                                 model.yolo_detector = Some(detector);
                                 caps.telemetry().event("yolo_initialized", &[]);
@@ -3490,11 +8232,18 @@ pub mod app {
                         }
                     }
 
+                    if model.user_id.is_some() {
+                        self.update(Event::RestoreStateRequested, model, caps);
+                    } else {
+                        caps.telemetry().event("startup_without_user", &[]);
+                    }
+
                     caps.render().render();
                 }
 
                 Event::AppBackgrounded => {
-                    Self::persist_store(model, caps);
+                    model.offline_store.last_session_ms = Some(get_current_time_ms());
+                    Self::persist_now(model, caps);
                     caps.telemetry().event("app_backgrounded", &[]);
                 }
 
@@ -3502,8 +8251,19 @@ pub mod app {
                     model.update_timestamp();
 
                     if model.state == AppState::Ready && model.network_online {
-                        Self::send_refresh_request(model, caps, None);
-                        model.is_refreshing = true;
+                        let now_ms = get_current_time_ms();
+                        // Resuming is not a user action, so wait out a 429 without a toast.
+                        if model
+                            .rate_limit_remaining_ms("list_cases", now_ms)
+                            .is_some()
+                        {
+                            caps.telemetry()
+                                .event("foreground_refresh_skipped", &[("reason", "rate_limited")]);
+                        } else if model.foreground_refresh_due(now_ms) {
+                            Self::request_refresh(model, caps);
+                        } else {
+                            caps.telemetry().event("foreground_refresh_skipped", &[]);
+                        }
                     }
 
                     caps.telemetry().event("app_foregrounded", &[]);
@@ -3516,9 +8276,23 @@ pub mod app {
                 }
 
                 Event::LoginCompleted { jwt, user_id } => {
+                    if let Err(reason) = validate_jwt_structure(&jwt) {
+                        self.update(
+                            Event::LoginFailed {
+                                error: reason.to_string(),
+                            },
+                            model,
+                            caps,
+                        );
+                        return;
+                    }
+
                     model.user_id = Some(UserId::new(&user_id));
                     model.jwt_token = Some(jwt);
                     model.state = AppState::OnboardingLocation;
+                    if model.remember_store_key(Self::derive_store_key_id(&UserId::new(&user_id))) {
+                        Self::persist_store_keys(model, caps);
+                    }
 
                     caps.telemetry().event("login_success", &[]);
                     caps.render().render();
@@ -3533,17 +8307,54 @@ pub mod app {
                 }
 
                 Event::LogoutRequested => {
-                    model.user_id = None;
-                    model.jwt_token = None;
+                    let pending = model.offline_store.pending_sync_count();
+                    if pending > 0 {
+                        model.logout_confirmation_pending = true;
+                        caps.telemetry().event(
+                            "logout_blocked_by_pending_work",
+                            &[("count", &pending.to_string())],
+                        );
+                        caps.render().render();
+                        return;
+                    }
+                    Self::complete_logout(model, caps);
+                }
+
+                Event::LogoutConfirmed => {
+                    Self::complete_logout(model, caps);
+                }
+
+                Event::LogoutCancelled => {
+                    model.logout_confirmation_pending = false;
+                    caps.render().render();
+                }
+
+                Event::PurgeAllLocalData => {
+                    let keys = Self::local_data_keys(model);
+                    Self::complete_logout(model, caps);
+                    let cancelled = std::mem::take(&mut model.cancelled_request_ids);
+                    let detector = model.yolo_detector.take();
+                    let network_online = model.network_online;
+                    let refresh_generation = model.refresh_generation;
+
+                    *model = Model::default();
                     model.state = AppState::Unauthenticated;
-                    model.cases.clear();
-                    model.offline_store = OfflineStore::new();
-                    model.pending_claims.clear();
-                    model.pending_mutations.clear();
-                    model.staged_photo = None;
-                    model.selected_case_id = None;
+                    model.cancelled_request_ids = cancelled;
+                    model.yolo_detector = detector;
+                    model.network_online = network_online;
+                    model.refresh_generation = refresh_generation;
+
+                    for key in &keys {
+                        caps.kv().delete(key, |result| match result {
+                            Ok(_) => Event::Noop,
+                            Err(e) => Event::PersistenceFailed {
+                                error: AppError::new(ErrorKind::Storage, format!("{e:?}")),
+                            },
+                        });
+                    }
 
-                    caps.telemetry().event("logout", &[]);
+                    caps.telemetry()
+                        .event("local_data_purged", &[("keys", &keys.len().to_string())]);
                     caps.render().render();
                 }
 
@@ -3552,16 +8363,29 @@ pub mod app {
                 }
 
                 Event::TokenRefreshRequired => {
+                    model.token_refresh_pending = true;
                     caps.telemetry().event("token_refresh_required", &[]);
+                    caps.render().render();
                 }
 
                 Event::TokenRefreshed { jwt } => {
                     model.jwt_token = Some(jwt);
+                    model.token_refresh_pending = false;
                     caps.telemetry().event("token_refreshed", &[]);
+
+                    if model.network_online {
+                        if std::mem::take(&mut model.refresh_deferred_for_token) {
+                            Self::request_refresh(model, caps);
+                            caps.render().render();
+                        }
+                        self.update(Event::OutboxFlushRequested, model, caps);
+                    }
                 }
 
                 Event::TokenRefreshFailed { error } => {
                     model.jwt_token = None;
+                    model.token_refresh_pending = false;
+                    model.refresh_deferred_for_token = false;
                     model.state = AppState::Unauthenticated;
                     model.set_error(AppError::new(ErrorKind::Authentication, "Session expired"));
 
@@ -3598,22 +8422,63 @@ pub mod app {
                     caps.render().render();
                 }
 
-                Event::LocationReceived { lat, lng, accuracy: _ } => {
+                Event::LocationReceived { lat, lng, accuracy } => {
                     match Self::validate_coordinates(lat, lng) {
                         Ok(coord) => {
-                            model.area_center = Some(coord);
-                            model.map_center = Some(coord);
-                            model.map_zoom = DEFAULT_MAP_ZOOM;
+                            match model.accept_location_fix(coord, get_current_time_ms()) {
+                                Some(center) => {
+                                    model.area_center = Some(center);
+                                    if model.map_follow_mode == MapFollowMode::Follow
+                                        || model.map_center.is_none()
+                                    {
+                                        model.map_center = Some(center);
+                                        model.map_zoom = DEFAULT_MAP_ZOOM;
+                                    }
+                                    model.location_accuracy_m = accuracy;
+
+                                    let accurate = is_location_accurate(
+                                        accuracy,
+                                        model.min_location_accuracy_m,
+                                    );
+
+                                    if model.state == AppState::OnboardingLocation {
+                                        if accurate {
+                                            model.state = AppState::OnboardingRadius;
+                                        } else {
+                                            model.state = AppState::PinDrop;
+                                            model.show_catalog_toast(
+                                                ToastKey::LocationImprecise,
+                                                ToastKind::Warning,
+                                            );
+                                        }
+                                    }
 
-                            if model.state == AppState::OnboardingLocation {
-                                model.state = AppState::OnboardingRadius;
+                                    caps.telemetry().event(
+                                        "location_received",
+                                        &[
+                                            (
+                                                "accuracy_m",
+                                                &accuracy.map_or_else(
+                                                    || "unknown".into(),
+                                                    |a| format!("{a:.0}"),
+                                                ),
+                                            ),
+                                            ("accurate", &accurate.to_string()),
+                                        ],
+                                    );
+                                }
+                                None => {
+                                    caps.telemetry().warn(
+                                        "location_jump_rejected",
+                                        &format!("{:.5}, {:.5}", coord.lat(), coord.lon()),
+                                    );
+                                }
                             }
-
-                            caps.telemetry().event("location_received", &[]);
                         }
                         Err(e) => {
                             model.set_error(e);
-                            caps.telemetry().error("location_invalid", &format!("{lat}, {lng}"));
+                            caps.telemetry()
+                                .error("location_invalid", &format!("{lat}, {lng}"));
                         }
                     }
                     caps.render().render();
@@ -3657,23 +8522,99 @@ pub mod app {
 
                     if model.state == AppState::OnboardingRadius {
                         model.state = AppState::Ready;
-
-                        caps.push().request_permission(|granted| {
-                            Event::PushPermissionResult { granted }
-                        });
+                        model.request_auto_select();
+
+                        let now_ms = get_current_time_ms();
+                        if model.should_prompt_push(now_ms) {
+                            model.record_push_prompt(now_ms);
+                            caps.push()
+                                .request_permission(|granted| Event::PushPermissionResult {
+                                    granted,
+                                });
+                        }
 
                         if model.network_online {
-                            Self::send_refresh_request(model, caps, None);
-                            model.is_refreshing = true;
+                            Self::request_refresh(model, caps);
                         }
+
+                        self.replay_pending_pushes(model, caps);
                     }
 
                     caps.telemetry().event("radius_selected", &[("meters", &radius.to_string())]);
                     caps.render().render();
                 }
 
+                Event::SetHighlightRadius { meters } => {
+                    model.set_preference(Preference::HighlightRadius { meters });
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
+                Event::SetUtcOffset { minutes } => {
+                    model.set_preference(Preference::UtcOffset { minutes });
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
+                Event::SetAutoSelectNearest { enabled } => {
+                    model.set_preference(Preference::AutoSelectNearest { enabled });
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
+                Event::SetPreference { preference } => {
+                    model.set_preference(preference);
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
+                Event::PreferencesLoaded { result } => match *result {
+                    Ok(data) => match serde_json::from_slice::<UserPreferences>(&data) {
+                        Ok(prefs) => {
+                            model.apply_preferences(&prefs);
+                            caps.render().render();
+                        }
+                        Err(e) => {
+                            caps.telemetry()
+                                .warn("preferences_malformed", &e.to_string());
+                        }
+                    },
+                    Err(KvError::NotFound) => {}
+                    Err(e) => {
+                        caps.telemetry()
+                            .warn("preferences_load_failed", &format!("{e:?}"));
+                    }
+                },
+
+                Event::StoreKeysLoaded { result } => match *result {
+                    Ok(data) => match serde_json::from_slice::<Vec<String>>(&data) {
+                        Ok(key_ids) => {
+                            for key_id in key_ids {
+                                model.remember_store_key(key_id);
+                            }
+                        }
+                        Err(e) => {
+                            caps.telemetry()
+                                .warn("store_keys_malformed", &e.to_string());
+                        }
+                    },
+                    Err(KvError::NotFound) => {}
+                    Err(e) => {
+                        caps.telemetry()
+                            .warn("store_keys_load_failed", &format!("{e:?}"));
+                    }
+                },
+
+                Event::SetResolutionEvidencePolicy { required } => {
+                    model.set_preference(Preference::RequireResolutionEvidence { required });
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
                 Event::OnboardingComplete => {
                     model.state = AppState::Ready;
+                    model.request_auto_select();
+                    self.replay_pending_pushes(model, caps);
                     caps.render().render();
                 }
 
@@ -3685,8 +8626,7 @@ pub mod app {
                         self.update(Event::OutboxFlushRequested, model, caps);
 
                         if model.state == AppState::Ready {
-                            Self::send_refresh_request(model, caps, None);
-                            model.is_refreshing = true;
+                            Self::request_refresh(model, caps);
                         }
                     }
 
@@ -3732,13 +8672,40 @@ pub mod app {
                     model.state = AppState::CameraCapture;
                     caps.camera().capture(|result| Event::CameraResult(Box::new(result)));
                     caps.render().render();
+
+                    self.update(Event::WarmupVision, model, caps);
                 }
 
                 Event::CameraResult(result) => {
                     model.state = AppState::Ready;
 
                     match *result {
-                        Ok(CameraOutput::Photo { data, mime_type: _ }) => {
+                        Ok(CameraOutput::Photo { data, mime_type }) => {
+                            if let Err(e) = check_image_mime_type(&mime_type) {
+                                caps.telemetry().warn("camera_mime_unsupported", &mime_type);
+                                model.set_error(e);
+                                caps.render().render();
+                                return;
+                            }
+
+                            if normalize_mime(&mime_type).is_none() && !mime_type.trim().is_empty()
+                            {
+                                caps.telemetry()
+                                    .warn("camera_mime_unrecognized", &mime_type);
+                            }
+
+                            let released = model
+                                .staged_photo
+                                .as_ref()
+                                .map_or(0, StagedPhoto::memory_bytes);
+                            if let Err(e) = model.reserve_image_memory(data.len(), released) {
+                                caps.telemetry()
+                                    .warn("image_memory_cap_reached", &e.message);
+                                model.set_error(e);
+                                caps.render().render();
+                                return;
+                            }
+
                             match Self::process_camera_image(data, model, caps) {
                                 Ok(staged) => {
                                     model.staged_photo = Some(staged);
@@ -3778,6 +8745,58 @@ pub mod app {
                     caps.render().render();
                 }
 
+                Event::WarmupVision => {
+                    let detector = match &mut model.yolo_detector {
+                        Some(detector) if !detector.is_warmed_up() => detector,
+                        _ => return,
+                    };
+
+                    match detector.warmup() {
+                        Ok(()) => caps.telemetry().event("vision_warmed_up", &[]),
+                        Err(e) => caps
+                            .telemetry()
+                            .warn("vision_warmup_failed", &e.to_string()),
+                    }
+                }
+
+                Event::RerunDetection => {
+                    let original_data = match &model.staged_photo {
+                        Some(photo) => photo.original_data.clone(),
+                        None => {
+                            caps.telemetry()
+                                .warn("rerun_detection_no_photo", "No staged photo");
+                            return;
+                        }
+                    };
+
+                    if model.yolo_detector.is_none() {
+                        caps.telemetry()
+                            .warn("rerun_detection_no_detector", "Detector not loaded");
+                        return;
+                    }
+
+                    match Self::decode_image(&original_data, model.max_image_alloc) {
+                        Ok((img, _, _)) => {
+                            let (detections, cropped_data) = Self::detect_and_crop(&img, model);
+                            if let Some(photo) = &mut model.staged_photo {
+                                photo.apply_detections(detections, cropped_data);
+                                caps.telemetry().event(
+                                    "detection_rerun",
+                                    &[
+                                        ("detection_count", &photo.detection_count.to_string()),
+                                        ("top_confidence", &format!("{:.3}", photo.top_confidence)),
+                                    ],
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            model.set_error(e);
+                        }
+                    }
+
+                    caps.render().render();
+                }
+
                 Event::PhotoProcessed { staged_photo } => {
                     model.staged_photo = Some(staged_photo);
                     caps.render().render();
@@ -3789,59 +8808,34 @@ pub mod app {
                 }
 
                 Event::CreateCaseRequested(payload) => {
-                    let coord = match Self::validate_coordinates(payload.location.0, payload.location.1) {
-                        Ok(c) => c,
-                        Err(e) => {
-                            model.set_error(e);
-                            caps.render().render();
-                            return;
-                        }
-                    };
-
-                    let has_photo = model.staged_photo.is_some();
                     let photo_data = model.staged_photo.as_ref().map(|p| p.best_data_for_upload().to_vec());
+                    let photo_mime_type = model.staged_photo.as_ref().map(|p| p.mime_type.clone());
 
-                    let mut local_case = LocalCase::new(
-                        coord.into(),
-                        payload.description.clone(),
-                        payload.wound_severity,
+                    let now_ms = get_current_time_ms();
+                    let fingerprint = submission_fingerprint(
+                        payload.location,
+                        payload.description.as_deref(),
+                        photo_data.as_deref(),
                     );
-                    local_case.landmark_hint = payload.landmark_hint.clone();
-                    local_case.photo_data = photo_data;
-
-                    let local_id = local_case.local_id.clone();
-
-                    if let Err(e) = model.offline_store.push_local_case(local_case) {
-                        model.set_error(e.into());
+                    if model.is_duplicate_submission(fingerprint, now_ms) {
+                        model.show_catalog_toast(ToastKey::AlreadySubmitted, ToastKind::Info);
+                        caps.telemetry().event("duplicate_submission_ignored", &[]);
                         caps.render().render();
                         return;
                     }
 
-                    let intent = OutboxIntent::CreateCase {
-                        local_id: local_id.clone(),
-                        location: coord.into(),
-                        description: payload.description,
-                        landmark_hint: payload.landmark_hint,
-                        wound_severity: payload.wound_severity,
-                        has_photo,
-                        created_at_ms_utc: UnixTimeMs::now(),
-                    };
-
-                    let entry = OutboxEntry::new(intent);
-
-                    if let Err(e) = model.offline_store.push_outbox(entry) {
-                        model.set_error(e.into());
-                        caps.render().render();
+                    if let Err(e) = Self::enqueue_local_case(
+                        payload,
+                        photo_data,
+                        photo_mime_type,
+                        true,
+                        model,
+                        caps,
+                    ) {
+                        self.enqueue_failed(e, model, caps);
                         return;
                     }
-
-                    model.staged_photo = None;
-                    model.map_center = Some(coord);
-
-                    Self::persist_store(model, caps);
-
-                    model.show_toast("Case created", ToastKind::Success);
-                    caps.telemetry().event("case_created_local", &[("local_id", &local_id.0)]);
+                    model.last_submission = Some((fingerprint, now_ms));
 
                     caps.render().render();
 
@@ -3850,84 +8844,257 @@ pub mod app {
                     }
                 }
 
-                Event::CreateCaseResponse { op_id, result } => {
-                    Self::handle_create_case_response(&op_id, &result, model, caps);
-                    caps.render().render();
+                Event::UpdateDraft {
+                    location,
+                    description,
+                    landmark_hint,
+                    wound_severity,
+                } => {
+                    let mut draft = model.draft().cloned().unwrap_or_default();
 
-                    self.update(Event::OutboxFlushRequested, model, caps);
-                }
+                    if let Some((lat, lng)) = location {
+                        match Self::validate_coordinates(lat, lng) {
+                            Ok(_) => draft.location = Some((lat, lng)),
+                            Err(e) => {
+                                model.set_error(e);
+                                caps.render().render();
+                                return;
+                            }
+                        }
+                    }
 
-                Event::PhotoUploadResponse { local_id, result } => {
-                    Self::handle_photo_upload_response(&local_id, &result, model, caps);
-                    caps.render().render();
-                }
+                    if description.is_some() {
+                        draft.description = description;
+                    }
+                    if landmark_hint.is_some() {
+                        draft.landmark_hint = landmark_hint;
+                    }
+                    if wound_severity.is_some() {
+                        draft.wound_severity = wound_severity;
+                    }
+                    if let Some(photo) = &model.staged_photo {
+                        let data = photo.best_data_for_upload().to_vec();
+                        let mime_type = normalize_mime(&photo.mime_type).map(str::to_string);
+                        let released = draft.photo_data.as_ref().map_or(0, Vec::len);
+                        if let Err(e) = model.reserve_image_memory(data.len(), released) {
+                            caps.telemetry()
+                                .warn("image_memory_cap_reached", &e.message);
+                            model.set_error(e);
+                            caps.render().render();
+                            return;
+                        }
+                        draft.photo_data = Some(data);
+                        draft.photo_mime_type = mime_type;
+                    }
+                    draft.updated_at_ms = get_current_time_ms();
 
-                Event::WriteEncryptedStore { key_id, data } => {
-                    caps.kv().set(&key_id, data, |result| match result {
-                        Ok(()) => Event::PersistenceSucceeded,
-                        Err(e) => Event::PersistenceFailed {
-                            error: format!("{e:?}"),
-                        },
-                    });
+                    model.set_draft(Some(draft));
+                    caps.render().render();
                 }
 
-                Event::PersistenceSucceeded => {
-                    model.offline_store.update_last_sync();
-                    caps.telemetry().event("persistence_success", &[]);
+                Event::DiscardDraft => {
+                    if model.draft().is_some() {
+                        model.set_draft(None);
+                        caps.telemetry().event("draft_discarded", &[]);
+                    }
+                    caps.render().render();
                 }
 
-                Event::PersistenceFailed { error } => {
-                    caps.telemetry().error("persistence_failed", &error);
-                }
+                Event::SubmitDraft => {
+                    let draft = match model.draft() {
+                        Some(draft) => draft.clone(),
+                        None => {
+                            caps.telemetry()
+                                .warn("submit_draft_missing", "No draft to submit");
+                            return;
+                        }
+                    };
 
-                Event::RestoreStateRequested => {
+                    let location = match draft.location {
+                        Some(location) => location,
+                        None => {
+                            model.set_error(AppError::new(
+                                ErrorKind::Validation,
+                                "Add a location before submitting the report",
+                            ));
+                            caps.render().render();
+                            return;
+                        }
+                    };
+
+                    let payload = CreateCasePayload {
+                        location,
+                        description: draft.description.clone(),
+                        landmark_hint: draft.landmark_hint.clone(),
+                        wound_severity: draft.wound_severity,
+                    };
+                    // Only fall back to the staged photo (and consume it) when the draft has none.
+                    let from_staged_photo = draft.photo_data.is_none();
+                    let (photo_data, photo_mime_type) = match draft.photo_data.clone() {
+                        Some(data) => (Some(data), draft.photo_mime_type.clone()),
+                        None => match &model.staged_photo {
+                            Some(p) => (
+                                Some(p.best_data_for_upload().to_vec()),
+                                Some(p.mime_type.clone()),
+                            ),
+                            None => (None, None),
+                        },
+                    };
+
+                    // The draft's copy of the photo moves into the local case.
+                    model.set_draft(None);
+                    if let Err(e) = Self::enqueue_local_case(
+                        payload,
+                        photo_data,
+                        photo_mime_type,
+                        from_staged_photo,
+                        model,
+                        caps,
+                    ) {
+                        model.set_draft(Some(draft));
+                        self.enqueue_failed(e, model, caps);
+                        return;
+                    }
+
+                    caps.render().render();
+
+                    if model.network_online {
+                        self.update(Event::OutboxFlushRequested, model, caps);
+                    }
+                }
+
+                Event::CreateCaseResponse { op_id, result } => {
+                    if Self::drop_cancelled_response(&op_id, "create_case", model, caps) {
+                        return;
+                    }
+                    Self::record_request_outcome("create_case", &result, model, caps);
+
+                    Self::handle_create_case_response(&op_id, &result, model, caps);
+                    caps.render().render();
+
+                    self.update(Event::OutboxFlushRequested, model, caps);
+                }
+
+                Event::PhotoUploadResponse {
+                    local_id,
+                    redirected,
+                    result,
+                } => {
+                    if Self::drop_cancelled_response(&local_id, "photo_upload", model, caps) {
+                        return;
+                    }
+                    Self::record_request_outcome("photo_upload", &result, model, caps);
+
+                    Self::handle_photo_upload_response(&local_id, redirected, &result, model, caps);
+                    caps.render().render();
+                }
+
+                Event::WriteEncryptedStore { key_id, data } => {
+                    caps.kv().set(&key_id, data, |result| match result {
+                        Ok(()) => Event::PersistenceSucceeded,
+                        Err(e) => Event::PersistenceFailed {
+                            error: AppError::new(ErrorKind::Storage, format!("{e:?}")),
+                        },
+                    });
+                }
+
+                Event::PersistenceSucceeded => {
+                    model.offline_store.update_last_sync();
+                    caps.telemetry().event("persistence_success", &[]);
+                }
+
+                Event::PersistenceFailed { error } => {
+                    let name = if error.kind == ErrorKind::Internal {
+                        "persistence_internal_error"
+                    } else {
+                        "persistence_failed"
+                    };
+                    caps.telemetry()
+                        .error(name, &Self::crypto_error_telemetry(&error));
+                }
+
+                Event::RestoreStateRequested => {
                     if let Some(user_id) = &model.user_id {
                         let key_id = Self::derive_store_key_id(user_id);
+                        model.restore_in_flight = true;
                         caps.kv().get(&key_id, |result| Event::RestoreStateResponse {
                             result: Box::new(result),
                         });
                     }
                 }
 
-                Event::RestoreStateResponse { result } => {
-                    match *result {
-                        Ok(data) => {
-                            if let Some(user_id) = &model.user_id {
-                                let key_id = Self::derive_store_key_id(user_id);
-                                caps.crypto().decrypt(key_id, data, |result| match result {
-                                    Ok(CryptoOutput::Decrypted(bytes)) => {
-                                        Event::StateDecrypted { data: bytes }
-                                    }
-                                    _ => Event::StateDecryptionFailed {
-                                        error: "Decryption failed".into(),
-                                    },
-                                });
-                            }
-                        }
-                        Err(KvError::NotFound) => {
-                            caps.telemetry().event("no_stored_state", &[]);
-                        }
-                        Err(e) => {
-                            caps.telemetry().error("state_load_failed", &format!("{e:?}"));
+                Event::RestoreStateResponse { result } => match *result {
+                    Ok(data) => {
+                        if let Some(user_id) = &model.user_id {
+                            let key_id = Self::derive_store_key_id(user_id);
+                            caps.crypto()
+                                .decrypt(key_id, data, Self::decryption_result_event);
+                        } else {
+                            Self::finish_restore(model, caps);
                         }
                     }
-                }
+                    Err(KvError::NotFound) => {
+                        model.store_restored = true;
+                        caps.telemetry().event("no_stored_state", &[]);
+                        Self::finish_restore(model, caps);
+                    }
+                    Err(e) => {
+                        caps.telemetry()
+                            .error("state_load_failed", &format!("{e:?}"));
+                        Self::finish_restore(model, caps);
+                    }
+                },
 
                 Event::StateDecrypted { data } => {
                     match serde_cbor::from_slice::<OfflineStore>(&data) {
                         Ok(store) => {
                             model.offline_store = store;
+                            model.store_restored = true;
+                            model.restore_viewed_cases();
+
+                            let duplicate_cases = model.offline_store.dedup_local_cases();
+                            let duplicate_ops = model.offline_store.dedup_outbox();
+                            if duplicate_cases > 0 || duplicate_ops > 0 {
+                                model.mark_store_dirty();
+                                caps.telemetry().warn(
+                                    "restored_duplicate_ids_removed",
+                                    &format!(
+                                        "local_cases={duplicate_cases}, outbox={duplicate_ops}"
+                                    ),
+                                );
+                            }
+
+                            let outbox_len = model.offline_store.outbox.len();
+                            model.offline_store.repair_missing_outbox_entries();
+                            let repaired =
+                                model.offline_store.outbox.len().saturating_sub(outbox_len);
+                            if repaired > 0 {
+                                model.mark_store_dirty();
+                                caps.telemetry()
+                                    .warn("outbox_entries_repaired", &repaired.to_string());
+                            }
+
                             caps.telemetry().event("state_restored", &[]);
                         }
                         Err(e) => {
+                            model.store_restored = false;
                             caps.telemetry().error("state_deserialize_failed", &e.to_string());
                         }
                     }
+                    Self::finish_restore(model, caps);
                     caps.render().render();
                 }
 
                 Event::StateDecryptionFailed { error } => {
-                    caps.telemetry().error("state_decryption_failed", &error);
+                    let name = if error.kind == ErrorKind::Internal {
+                        "state_decryption_internal_error"
+                    } else {
+                        "state_decryption_failed"
+                    };
+                    caps.telemetry()
+                        .error(name, &Self::crypto_error_telemetry(&error));
+                    model.store_restored = false;
+                    Self::finish_restore(model, caps);
                 }
 
                 Event::OutboxFlushRequested => {
@@ -3937,13 +9104,24 @@ pub mod app {
 
                     let now_ms = get_current_time_ms();
 
+                    if model.defer_for_token_refresh(now_ms) {
+                        caps.telemetry()
+                            .event("outbox_flush_paused", &[("reason", "token_expiring")]);
+                        caps.render().render();
+                        return;
+                    }
+
                     if let Some(entry) = model.offline_store.get_next_pending_entry(now_ms) {
                         let entry = entry.clone();
 
-                        if let Some(e) = model.offline_store.get_entry_mut(&entry.op_id) {
-                            e.mark_in_flight();
+                        if !model.circuit_breaker.allow_request(now_ms) {
+                            caps.telemetry()
+                                .event("outbox_flush_paused", &[("reason", "circuit_open")]);
+                            return;
                         }
 
+                        model.offline_store.mark_entry_in_flight(&entry.op_id);
+
                         match &entry.intent {
                             OutboxIntent::CreateCase { .. } => {
                                 Self::send_create_case_request(&entry, model, caps);
@@ -3956,36 +9134,62 @@ pub mod app {
                                 if let Some(local_case) = model
                                     .offline_store
                                     .pending_local_cases
-                                    .iter()
+                                    .iter_mut()
                                     .find(|c| &c.local_id == local_id)
                                 {
+                                    local_case.photo_upload_url = Some(upload_url.clone());
+                                    local_case.photo_upload_headers = upload_headers.clone();
                                     if let Some(photo_data) = &local_case.photo_data {
                                         Self::send_photo_upload(
                                             local_id,
                                             upload_url,
                                             upload_headers,
                                             photo_data,
+                                            false,
                                             caps,
                                         );
                                     }
                                 }
                             }
                             OutboxIntent::ClaimCase { case_id } => {
-                                if let Some(pending) = model.pending_claims.get(case_id) {
-                                    Self::send_claim_request(case_id, pending, model, caps);
-                                }
+                                let pending = match model.pending_claims.get(case_id) {
+                                    Some(pending) => pending.clone(),
+                                    None => {
+                                        let case = model.cases.iter().find(|c| &c.id == case_id);
+                                        PendingClaim::new(
+                                            case_id.clone(),
+                                            case.map_or(CaseStatus::Pending, |c| c.status),
+                                            case.and_then(|c| c.assigned_rescuer_id.clone()),
+                                        )
+                                    }
+                                };
+                                Self::send_claim_request(case_id, &pending, model, caps);
                             }
                             OutboxIntent::TransitionCase {
                                 case_id,
                                 next_status,
                                 notes,
+                                outcome,
+                                idempotency_key,
                             } => {
-                                let mutation_id = Uuid::new_v4().to_string();
+                                let mutation = model.pending_mutations.values().find(|m| {
+                                    &m.case_id == case_id && m.new_status == *next_status
+                                });
+                                let mutation_id = mutation.map_or_else(
+                                    || Uuid::new_v4().to_string(),
+                                    |m| m.mutation_id.clone(),
+                                );
+                                let idempotency_key = idempotency_key
+                                    .clone()
+                                    .or_else(|| mutation.map(|m| m.idempotency_key.clone()))
+                                    .unwrap_or_else(IdempotencyKey::generate);
                                 Self::send_transition_request(
                                     case_id,
                                     &mutation_id,
+                                    &idempotency_key,
                                     *next_status,
                                     notes.clone(),
+                                    *outcome,
                                     model,
                                     caps,
                                 );
@@ -4005,9 +9209,17 @@ pub mod app {
                     }
                 }
 
+                Event::OutboxCapacityReached { kind } => {
+                    model.outbox_capacity_reached = Some(kind);
+                    caps.telemetry()
+                        .warn("outbox_capacity_reached", kind.as_str());
+                    caps.render().render();
+                }
+
                 Event::OutboxEntryCompleted { op_id } => {
                     model.offline_store.mark_entry_completed(&OpId::new(&op_id));
-                    Self::persist_store(model, caps);
+                    model.mark_store_dirty();
+                    Self::reset_sibling_backoff(model, caps);
                     caps.render().render();
 
                     self.update(Event::OutboxFlushRequested, model, caps);
@@ -4024,10 +9236,14 @@ pub mod app {
                     if is_permanent {
                         model.offline_store.mark_entry_permanently_failed(&op_id_typed, err);
                     } else {
-                        model.offline_store.mark_entry_failed(&op_id_typed, err);
+                        model.offline_store.mark_entry_failed_with_strategy(
+                            &op_id_typed,
+                            err,
+                            model.outbox_tuning.backoff_strategy,
+                        );
                     }
 
-                    Self::persist_store(model, caps);
+                    model.mark_store_dirty();
                     caps.render().render();
                 }
 
@@ -4046,25 +9262,111 @@ pub mod app {
                     caps.render().render();
                 }
 
+                Event::SetStatusFilter { status } => {
+                    match status.as_deref().map(CaseStatus::from_str) {
+                        Some(None) => {
+                            model.set_error(AppError::new(
+                                ErrorKind::Validation,
+                                format!("Invalid status filter: {}", status.unwrap_or_default()),
+                            ));
+                        }
+                        Some(Some(filter)) => model.status_filter = Some(filter),
+                        None => model.status_filter = None,
+                    }
+                    caps.render().render();
+                }
+
+                Event::SetListSortMode { mode } => {
+                    model.set_preference(Preference::ListSort { mode });
+                    Self::persist_preferences(model, caps);
+                    caps.render().render();
+                }
+
                 Event::MapMoved { center, zoom } => {
                     if let Ok(coord) = center.to_validated() {
                         model.map_center = Some(coord);
                     }
                     model.map_zoom = zoom.value();
+                    model.map_follow_mode = MapFollowMode::Free;
+                    model.last_map_move_at_ms = Some(get_current_time_ms());
+                }
+
+                Event::MapSettled => {
+                    model.last_map_move_at_ms = None;
+
+                    // The feed area only follows the map when its cases are refetched,
+                    // otherwise distances and the radius filter would disagree with them.
+                    if let Some(center) = model.drifted_map_center() {
+                        if model.state == AppState::Ready
+                            && model.network_online
+                            && !model.is_refreshing
+                        {
+                            model.area_center = Some(center);
+                            caps.telemetry().event("map_drift_refresh", &[]);
+                            Self::request_refresh(model, caps);
+                        }
+                    }
+
+                    caps.render().render();
+                }
+
+                Event::SetMapFollowMode { mode } => {
+                    model.map_follow_mode = mode;
+                    if mode == MapFollowMode::Follow {
+                        if let Some(center) = model.area_center {
+                            model.map_center = Some(center);
+                        }
+                    }
+                    caps.telemetry()
+                        .event("map_follow_mode_set", &[("mode", &format!("{mode:?}"))]);
+                    caps.render().render();
                 }
 
                 Event::CaseSelected { case_id } => {
                     model.selected_case_id = Some(CaseId::new(&case_id));
+                    model.selected_case_unavailable = false;
+                    if model.mark_case_viewed(&case_id) {
+                        model.mark_store_dirty();
+                    }
+
+                    if model.recenter_on_select || model.feed_view == FeedView::Map {
+                        model.frame_case(&CaseId::new(&case_id));
+                    }
+
                     caps.telemetry().event("case_selected", &[("case_id", &case_id)]);
                     caps.render().render();
                 }
 
                 Event::CaseDeselected => {
                     model.selected_case_id = None;
+                    model.selected_case_unavailable = false;
+                    caps.render().render();
+                }
+
+                Event::ResumeCaseRequested { case_id } => {
+                    let case_id_typed = CaseId::new(&case_id);
+                    if !model.reconcile_own_claim(&case_id_typed) {
+                        caps.telemetry().warn("resume_case_not_owned", &case_id);
+                        return;
+                    }
+
+                    model.selected_case_id = Some(case_id_typed);
+                    model.selected_case_unavailable = false;
+
+                    if model.network_online && !model.is_refreshing {
+                        Self::request_refresh(model, caps);
+                    }
+
+                    caps.telemetry()
+                        .event("case_resumed", &[("case_id", &case_id)]);
                     caps.render().render();
                 }
 
                 Event::ClaimRequested { case_id } => {
+                    if Self::blocked_by_rate_limit("claim_case", model, caps) {
+                        return;
+                    }
+
                     let case_id_typed = CaseId::new(&case_id);
 
                     let case = match model.cases.iter().find(|c| c.id.0 == case_id) {
@@ -4076,7 +9378,7 @@ pub mod app {
                     };
 
                     if !case.status.is_claimable() {
-                        model.show_toast("Case cannot be claimed", ToastKind::Warning);
+                        model.show_catalog_toast(ToastKey::NotClaimable, ToastKind::Warning);
                         return;
                     }
 
@@ -4109,6 +9411,17 @@ pub mod app {
 
                     caps.render().render();
 
+                    if !model.network_online {
+                        let intent = OutboxIntent::ClaimCase {
+                            case_id: case_id_typed,
+                        };
+                        if self.queue_case_action(intent, model, caps) {
+                            caps.telemetry()
+                                .event("claim_queued_offline", &[("case_id", &case_id)]);
+                        }
+                        return;
+                    }
+
                     Self::send_claim_request(&case_id_typed, &pending, model, caps);
                     caps.telemetry().event("claim_requested", &[("case_id", &case_id)]);
                 }
@@ -4118,7 +9431,14 @@ pub mod app {
                     mutation_id,
                     result,
                 } => {
+                    if Self::drop_cancelled_response(&mutation_id, "claim_case", model, caps) {
+                        return;
+                    }
+                    Self::record_request_outcome("claim_case", &result, model, caps);
+
                     Self::handle_claim_response(&case_id, &mutation_id, &result, model, caps);
+                    Self::apply_deferred_push_patches(model, caps);
+                    self.settle_queued_case_action(&case_id, &result, model, caps);
                     caps.render().render();
                 }
 
@@ -4126,7 +9446,12 @@ pub mod app {
                     case_id,
                     next_status,
                     notes,
+                    outcome,
                 } => {
+                    if Self::blocked_by_rate_limit("transition_case", model, caps) {
+                        return;
+                    }
+
                     let next = match CaseStatus::from_str(&next_status) {
                         Some(s) => s,
                         None => {
@@ -4147,12 +9472,28 @@ pub mod app {
                         }
                     };
 
-                    if let Err(e) = case.status.validate_transition(next) {
+                    if let Err(e) = case.status.validate_transition(next, outcome) {
                         model.set_error(e.into());
                         caps.render().render();
                         return;
                     }
 
+                    if next == CaseStatus::Resolved
+                        && !model.has_resolution_evidence(notes.as_deref())
+                    {
+                        model.set_error(
+                            AppError::new(
+                                ErrorKind::Validation,
+                                "Add notes or a photo before marking this case resolved",
+                            )
+                            .with_context("case_id", &case_id),
+                        );
+                        caps.telemetry()
+                            .warn("transition_missing_evidence", &case_id);
+                        caps.render().render();
+                        return;
+                    }
+
                     let mutation_id = model.store_optimistic_mutation(
                         CaseId::new(&case_id),
                         case.status,
@@ -4162,15 +9503,42 @@ pub mod app {
 
                     if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
                         case.status = next;
+                        if next == CaseStatus::Resolved {
+                            case.resolution_outcome = outcome;
+                        }
                     }
 
                     caps.render().render();
 
+                    let idempotency_key = model
+                        .pending_mutations
+                        .get(&mutation_id)
+                        .map_or_else(IdempotencyKey::generate, |m| m.idempotency_key.clone());
+
+                    if !model.network_online {
+                        let intent = OutboxIntent::TransitionCase {
+                            case_id: CaseId::new(&case_id),
+                            next_status: next,
+                            notes,
+                            outcome,
+                            idempotency_key: Some(idempotency_key),
+                        };
+                        if self.queue_case_action(intent, model, caps) {
+                            caps.telemetry().event(
+                                "transition_queued_offline",
+                                &[("case_id", &case_id), ("next", next.as_str())],
+                            );
+                        }
+                        return;
+                    }
+
                     Self::send_transition_request(
                         &CaseId::new(&case_id),
                         &mutation_id,
+                        &idempotency_key,
                         next,
                         notes,
+                        outcome,
                         model,
                         caps,
                     );
@@ -4186,14 +9554,21 @@ pub mod app {
                     mutation_id,
                     result,
                 } => {
+                    if Self::drop_cancelled_response(&mutation_id, "transition_case", model, caps) {
+                        return;
+                    }
+                    Self::record_request_outcome("transition_case", &result, model, caps);
+
                     Self::handle_transition_response(&case_id, &mutation_id, &result, model, caps);
+                    Self::apply_deferred_push_patches(model, caps);
+                    self.settle_queued_case_action(&case_id, &result, model, caps);
                     caps.render().render();
                 }
 
                 Event::RefreshRequested => {
                     if !model.network_online {
-                        model.show_toast("No internet connection", ToastKind::Warning);
-                                                caps.render().render();
+                        model.show_catalog_toast(ToastKey::NoConnection, ToastKind::Warning);
+                        caps.render().render();
                         return;
                     }
 
@@ -4201,15 +9576,18 @@ pub mod app {
                         return;
                     }
 
-                    model.is_refreshing = true;
+                    if Self::blocked_by_rate_limit("list_cases", model, caps) {
+                        return;
+                    }
+
+                    Self::request_refresh(model, caps);
                     caps.render().render();
 
-                    Self::send_refresh_request(model, caps, None);
                     caps.telemetry().event("refresh_requested", &[]);
                 }
 
-                Event::RefreshResponse(result) => {
-                    Self::handle_refresh_response(&result, model, caps, false);
+                Event::RefreshResponse { generation, result } => {
+                    Self::handle_refresh_response(&result, model, caps, false, generation);
                     caps.render().render();
                 }
 
@@ -4227,12 +9605,13 @@ pub mod app {
                     }
                 }
 
-                Event::LoadMoreResponse(result) => {
-                    Self::handle_refresh_response(&result, model, caps, true);
+                Event::LoadMoreResponse { generation, result } => {
+                    Self::handle_refresh_response(&result, model, caps, true, generation);
                     caps.render().render();
                 }
 
                 Event::PushPermissionRequested => {
+                    model.record_push_prompt(get_current_time_ms());
                     caps.push().request_permission(|granted| {
                         Event::PushPermissionResult { granted }
                     });
@@ -4240,6 +9619,12 @@ pub mod app {
 
                 Event::PushPermissionResult { granted } => {
                     model.push_permission_granted = granted;
+                    model.offline_store.push_denied_at_ms = if granted {
+                        None
+                    } else {
+                        Some(get_current_time_ms())
+                    };
+                    model.mark_store_dirty();
 
                     if granted {
                         caps.push().get_token(|result| match result {
@@ -4263,7 +9648,11 @@ pub mod app {
                     } else {
                         let intent = OutboxIntent::SyncFcmToken { token };
                         let entry = OutboxEntry::new(intent);
-                        let _ = model.offline_store.push_outbox(entry);
+                        if let Err(OutboxError::Full { kind, .. }) =
+                            model.offline_store.push_outbox(entry)
+                        {
+                            self.update(Event::OutboxCapacityReached { kind }, model, caps);
+                        }
                     }
 
                     caps.telemetry().event("push_token_received", &[]);
@@ -4274,6 +9663,19 @@ pub mod app {
                 }
 
                 Event::PushReceived(payload) => {
+                    if model.should_defer_push() {
+                        let dropped = model.defer_push(payload);
+                        caps.telemetry().event(
+                            "push_deferred",
+                            &[("queued", &model.pending_pushes.len().to_string())],
+                        );
+                        if dropped > 0 {
+                            caps.telemetry()
+                                .warn("pending_pushes_dropped", &dropped.to_string());
+                        }
+                        return;
+                    }
+
                     match payload {
                         PushPayload::NewCase { case_id, lat, lng, severity } => {
                             caps.telemetry().event(
@@ -4285,17 +9687,20 @@ pub mod app {
                                 if let Some(center) = model.area_center {
                                     let distance = haversine_distance(center, coord);
                                     if distance <= f64::from(model.area_radius_m) {
-                                        Self::send_refresh_request(model, caps, None);
-                                        model.is_refreshing = true;
+                                        Self::request_refresh(model, caps);
                                     }
                                 }
                             }
+                            Self::prune_out_of_radius(model, caps);
                         }
                         PushPayload::CaseClaimed { case_id, claimed_by } => {
-                            if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                case.status = CaseStatus::Claimed;
-                                case.assigned_rescuer_id = Some(UserId::new(&claimed_by));
-                            }
+                            model.apply_push_update(
+                                &case_id,
+                                CasePatch {
+                                    status: Some(CaseStatus::Claimed),
+                                    assigned_rescuer_id: Some(UserId::new(&claimed_by)),
+                                },
+                            );
 
                             let dominated_by_other = model.user_id.as_ref()
                                 .map(|uid| uid.0 != claimed_by)
@@ -4303,7 +9708,10 @@ pub mod app {
 
                             if dominated_by_other {
                                 if model.selected_case_id.as_ref().map(|id| id.0 == case_id).unwrap_or(false) {
-                                    model.show_toast("Case claimed by another rescuer", ToastKind::Info);
+                                    model.show_catalog_toast(
+                                        ToastKey::ClaimedByOther,
+                                        ToastKind::Info,
+                                    );
                                 }
                             }
 
@@ -4314,9 +9722,7 @@ pub mod app {
                         }
                         PushPayload::CaseUpdated { case_id, new_status, updated_by: _ } => {
                             if let Some(status) = CaseStatus::from_str(&new_status) {
-                                if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                    case.status = status;
-                                }
+                                model.apply_push_update(&case_id, CasePatch::status(status));
                             }
 
                             caps.telemetry().event(
@@ -4325,18 +9731,22 @@ pub mod app {
                             );
                         }
                         PushPayload::CaseResolved { case_id } => {
-                            if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                case.status = CaseStatus::Resolved;
-                            }
+                            model.apply_push_update(
+                                &case_id,
+                                CasePatch::status(CaseStatus::Resolved),
+                            );
 
-                            caps.telemetry().event("push_case_resolved", &[("case_id", &case_id)]);
+                            caps.telemetry()
+                                .event("push_case_resolved", &[("case_id", &case_id)]);
                         }
                         PushPayload::CaseCancelled { case_id, reason: _ } => {
-                            if let Some(case) = model.cases.iter_mut().find(|c| c.id.0 == case_id) {
-                                case.status = CaseStatus::Cancelled;
-                            }
+                            model.apply_push_update(
+                                &case_id,
+                                CasePatch::status(CaseStatus::Cancelled),
+                            );
 
-                            caps.telemetry().event("push_case_cancelled", &[("case_id", &case_id)]);
+                            caps.telemetry()
+                                .event("push_case_cancelled", &[("case_id", &case_id)]);
                         }
                     }
 
@@ -4344,6 +9754,7 @@ pub mod app {
                 }
 
                 Event::FcmSyncResponse { result } => {
+                    Self::record_request_outcome("sync_fcm_token", &result, model, caps);
                     match &*result {
                         Ok(output) if output.is_success() => {
                             caps.telemetry().event("fcm_sync_success", &[]);
@@ -4375,6 +9786,10 @@ pub mod app {
                 Event::TimerTick => {
                     model.update_timestamp();
 
+                    if model.map_settle_due(model.view_timestamp_ms) {
+                        self.update(Event::MapSettled, model, caps);
+                    }
+
                     if let Some(toast) = &model.active_toast {
                         if toast.is_expired(model.view_timestamp_ms) {
                             model.clear_toast();
@@ -4382,33 +9797,43 @@ pub mod app {
                         }
                     }
 
-                    for mutation_id in model
-                        .pending_mutations
-                        .iter()
-                        .filter(|(_, m)| {
-                            model.view_timestamp_ms.saturating_sub(m.created_at_ms) > 30_000
-                        })
-                        .map(|(id, _)| id.clone())
-                        .collect::<Vec<_>>()
+                    if model.network_online
+                        && model.circuit_breaker.probe_due(model.view_timestamp_ms)
                     {
-                        model.rollback_mutation(&mutation_id);
-                        caps.telemetry().warn("mutation_timeout", &mutation_id);
+                        self.update(Event::OutboxFlushRequested, model, caps);
                     }
 
-                    for case_id in model
-                        .pending_claims
-                        .iter()
-                        .filter(|(_, c)| {
-                            model.view_timestamp_ms.saturating_sub(c.created_at_ms) > 30_000
-                        })
-                        .map(|(id, _)| id.clone())
-                        .collect::<Vec<_>>()
-                    {
-                        if let Some(pending) = model.pending_claims.remove(&case_id) {
-                            model.rollback_mutation(&pending.mutation_id);
+                    let expired = model.expire_stale_mutations(model.view_timestamp_ms);
+                    if !expired.is_empty() {
+                        for case_id in &expired {
+                            caps.telemetry().warn("mutation_timeout", &case_id.0);
+                        }
+                        Self::apply_deferred_push_patches(model, caps);
+                        caps.render().render();
+                    }
+                }
+
+                Event::DismissFailedOperation { op_id } => {
+                    Self::remove_failed_operation(&op_id, RemovalReason::Dismissed, model, caps);
+                }
+
+                Event::DiscardOperation { op_id } => {
+                    Self::remove_failed_operation(&op_id, RemovalReason::Discarded, model, caps);
+                }
+
+                Event::PromoteOperation { op_id } => {
+                    match model.offline_store.promote_entry(&OpId(op_id.clone())) {
+                        Ok(()) => {
+                            model.mark_store_dirty();
+                            caps.telemetry()
+                                .event("outbox_entry_promoted", &[("op_id", &op_id)]);
+                        }
+                        Err(e) => {
+                            caps.telemetry()
+                                .warn("outbox_promote_rejected", &e.to_string());
                         }
-                        caps.telemetry().warn("claim_timeout", &case_id.0);
                     }
+                    caps.render().render();
                 }
 
                 Event::RetryFailedOperations => {
@@ -4418,14 +9843,9 @@ pub mod app {
                         }
                     }
 
-                    for entry in &mut model.offline_store.outbox {
-                        if entry.retry_state == RetryState::Failed {
-                            entry.retry_state = RetryState::Pending;
-                            entry.next_retry_at = None;
-                        }
-                    }
+                    model.offline_store.retry_failed_entries();
 
-                    Self::persist_store(model, caps);
+                    model.mark_store_dirty();
 
                     if model.network_online {
                         self.update(Event::OutboxFlushRequested, model, caps);
@@ -4434,7 +9854,54 @@ pub mod app {
                     caps.telemetry().event("retry_failed_requested", &[]);
                     caps.render().render();
                 }
+
+                Event::ForceRetryPermanentlyFailed {
+                    include_client_errors,
+                } => {
+                    let (entries, cases) = model
+                        .offline_store
+                        .revive_permanently_failed(include_client_errors);
+
+                    if entries + cases > 0 {
+                        Self::persist_now(model, caps);
+                        if model.network_online {
+                            self.update(Event::OutboxFlushRequested, model, caps);
+                        }
+                    }
+
+                    caps.telemetry().event(
+                        "force_retry_permanently_failed",
+                        &[
+                            ("entries", &entries.to_string()),
+                            ("local_cases", &cases.to_string()),
+                            ("include_client_errors", &include_client_errors.to_string()),
+                        ],
+                    );
+                    caps.render().render();
+                }
+            }
+        }
+    }
+
+    impl crux_core::App for App {
+        type Event = Event;
+        type Model = Model;
+        type ViewModel = ViewModel;
+        type Capabilities = Capabilities;
+
+        fn update(&self, event: Event, model: &mut Model, caps: &Capabilities) {
+            model.update_timestamp();
+
+            let event_name = event.name();
+            caps.telemetry().counter(&format!("event.{event_name}"), 1);
+
+            if event.is_user_initiated() {
+                caps.telemetry()
+                    .event("user_action", &[("event", event_name)]);
             }
+
+            self.dispatch(event, model, caps);
+            Self::persist_if_due(model, caps);
         }
 
         fn view(&self, model: &Model) -> ViewModel {
@@ -4449,11 +9916,13 @@ pub mod app {
 
                 AppState::OnboardingLocation => ViewState::OnboardingLocation {
                     permission_state: model.location_permission_state,
+                    onboarding_step: model.state.onboarding_step(),
                 },
 
                 AppState::PinDrop => ViewState::PinDrop {
                     initial_lat: model.area_center.map(|c| c.lat()),
                     initial_lon: model.area_center.map(|c| c.lon()),
+                    onboarding_step: model.state.onboarding_step(),
                 },
 
                 AppState::OnboardingRadius => {
@@ -4463,6 +9932,7 @@ pub mod app {
                             lon: center.lon(),
                             radius: model.area_radius_m,
                             selected_radius: model.area_radius_m,
+                            onboarding_step: model.state.onboarding_step(),
                         },
                         None => ViewState::Error {
                             title: "Location Required".into(),
@@ -4474,7 +9944,7 @@ pub mod app {
                 }
 
                 AppState::CameraCapture => ViewState::CameraCapture {
-                    config: CaptureConfig::default(),
+                    config: model.capture_config.clone(),
                 },
 
                 AppState::Ready => {
@@ -4482,6 +9952,10 @@ pub mod app {
                         Some(area_center) => {
                             let pins = Self::build_case_pins(model);
                             let list_items = Self::build_list_items(model, now_ms);
+                            let list_total_count = Self::list_total_count(model);
+                            let list_showing_text = (list_items.len() < list_total_count).then(|| {
+                                model.list_showing_text(list_items.len(), list_total_count)
+                            });
 
                             let selected_detail = model
                                 .selected_case_id
@@ -4497,10 +9971,19 @@ pub mod app {
                                 has_detections: p.has_detections(),
                             });
 
+                            let map_markers = model
+                                .clustering_enabled
+                                .then(|| cluster_pins(&pins, model.map_zoom));
+
                             ViewState::Ready {
                                 feed_view: model.feed_view,
                                 pins,
+                                map_markers,
                                 list_items,
+                                list_total_count,
+                                list_showing_text,
+                                selected_unavailable: model.selected_case_unavailable
+                                    && selected_detail.is_none(),
                                 selected_detail,
                                 map_center_lat: map_center.lat(),
                                 map_center_lon: map_center.lon(),
@@ -4509,8 +9992,15 @@ pub mod app {
                                 online: model.network_online,
                                 pending_sync_count: model.offline_store.pending_sync_count(),
                                 failed_sync_count: model.offline_store.failed_count(),
+                                outbox_capacity_reached: model.outbox_capacity_reached,
                                 staged_photo,
                                 has_more_cases: model.cases_cursor.is_some(),
+                                next_auto_refresh_in_ms: model.next_auto_refresh_in_ms(now_ms),
+                                status_counts: model.status_counts(false),
+                                list_sort: model.list_sort,
+                                empty_feed_reason: model.empty_feed_reason(),
+                                recommended_action: model.recommended_action(),
+                                server_reachable: model.server_reachable(now_ms),
                             }
                         }
                         None => ViewState::Error {
@@ -4527,7 +10017,7 @@ pub mod app {
                     message: model
                         .active_error
                         .as_ref()
-                        .map(|e| e.user_facing_message())
+                        .map(|e| model.catalog().error_message(e))
                         .unwrap_or_else(|| "An unknown error occurred".into()),
                     is_retryable: model
                         .active_error
@@ -4540,14 +10030,52 @@ pub mod app {
 
             ViewModel {
                 state,
-                error: model.active_error.as_ref().map(UserFacingError::from),
+                error: model
+                    .active_error
+                    .as_ref()
+                    .map(|e| UserFacingError::localized(e, model.catalog())),
                 toast: model.active_toast.as_ref().map(ToastView::from),
                 is_global_loading: model.is_loading,
                 offline_queue_count: model.offline_store.pending_sync_count(),
                 is_authenticated: model.is_authenticated(),
+                token_refresh_needed: model.token_refresh_pending,
+                logout_needs_confirmation: model.logout_confirmation_pending,
                 user_id: model.user_id.as_ref().map(|u| u.0.clone()),
+                severity_counts: model.severity_counts(),
+                offline_readiness: model.offline_readiness(now_ms),
+            }
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing {
+    use crate::capabilities::testing::FakeServer;
+    use crate::capabilities::Effect;
+    use crate::{App, Event, Model};
+    use crux_core::testing::{AppTester, Update};
+
+    pub fn respond_from(
+        app: &AppTester<App, Effect>,
+        server: &FakeServer,
+        update: Update<Effect, Event>,
+        model: &mut Model,
+    ) -> Vec<Update<Effect, Event>> {
+        let mut updates = Vec::new();
+        for effect in update.effects {
+            let mut request = match effect {
+                Effect::Http(request) => request,
+                _ => continue,
+            };
+            let response = server.respond(&request.operation.url);
+            let resolved = app
+                .resolve(&mut request, response)
+                .expect("HTTP request should resolve");
+            for event in resolved.events {
+                updates.push(app.update(event, model));
             }
         }
+        updates
     }
 }
 
@@ -4555,6 +10083,27 @@ pub mod app {
 mod tests {
     use super::*;
 
+    fn server_case(id: &str, status: CaseStatus) -> ServerCase {
+        ServerCase {
+            id: CaseId::new(id),
+            location: LatLon::new(0.0, 0.0),
+            description: None,
+            landmark_hint: None,
+            wound_severity: None,
+            status,
+            created_at_ms_utc: UnixTimeMs::now(),
+            updated_at_ms_utc: UnixTimeMs::now(),
+            reporter_id: UserId::new("reporter"),
+            assigned_rescuer_id: None,
+            photo_url: None,
+            thumbnail_url: None,
+            gemini_diagnosis: None,
+            species_guess: None,
+            distance_meters: None,
+            resolution_outcome: None,
+        }
+    }
+
     mod coordinate_tests {
         use super::*;
 
@@ -4605,13 +10154,129 @@ mod tests {
                 Err(CoordinateError::NonFinite)
             ));
         }
-    }
-
-    mod distance_tests {
-        use super::*;
 
         #[test]
-        fn test_same_point_distance() {
+        fn test_case_distance_prefers_server_value() {
+            let from = ValidatedCoordinate::new(0.0, 0.0).unwrap();
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.location = LatLon::new(0.0, 1.0);
+            case.distance_meters = Some(42.0);
+
+            assert!((case_distance(&case, from) - 42.0).abs() < f64::EPSILON);
+        }
+
+        #[test]
+        fn test_case_distance_computed_when_server_omits_it() {
+            let from = ValidatedCoordinate::new(0.0, 0.0).unwrap();
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.location = LatLon::new(0.0, 1.0);
+
+            let expected = haversine_distance(from, ValidatedCoordinate::new(0.0, 1.0).unwrap());
+            assert!((case_distance(&case, from) - expected).abs() < f64::EPSILON);
+
+            case.distance_meters = Some(f64::NAN);
+            assert!((case_distance(&case, from) - expected).abs() < f64::EPSILON);
+        }
+
+        #[test]
+        fn smooth_location_without_previous_returns_new_fix() {
+            let fix = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+
+            assert_eq!(smooth_location(None, fix, 0.3), fix);
+        }
+
+        #[test]
+        fn smooth_location_dampens_small_jitter() {
+            let prev = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let jittered = ValidatedCoordinate::new(51.5002, -0.1198).unwrap();
+
+            let smoothed = smooth_location(Some(prev), jittered, 0.3);
+
+            assert!(prev.distance_to(smoothed) < prev.distance_to(jittered) * 0.5);
+            assert!(smoothed.distance_to(jittered) > 0.0);
+        }
+
+        #[test]
+        fn smooth_location_wraps_across_antimeridian() {
+            let prev = ValidatedCoordinate::new(0.0, 179.9).unwrap();
+            let new = ValidatedCoordinate::new(0.0, -179.9).unwrap();
+
+            let smoothed = smooth_location(Some(prev), new, 0.5);
+
+            assert!((smoothed.lon().abs() - 180.0).abs() < 0.01);
+        }
+
+        #[test]
+        fn test_bearing_to_cardinal_directions() {
+            let origin = ValidatedCoordinate::new(0.0, 0.0).unwrap();
+            let cases = [
+                ((1.0, 0.0), 0.0),
+                ((0.0, 1.0), 90.0),
+                ((-1.0, 0.0), 180.0),
+                ((0.0, -1.0), 270.0),
+            ];
+
+            for ((lat, lon), expected) in cases {
+                let bearing = origin.bearing_to(ValidatedCoordinate::new(lat, lon).unwrap());
+                assert!(
+                    (bearing - expected).abs() < 1e-9,
+                    "{lat},{lon} -> {bearing}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_bearing_london_to_paris() {
+            let london = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let paris = ValidatedCoordinate::new(48.85, 2.35).unwrap();
+
+            let bearing = london.bearing_to(paris);
+            assert!((bearing - 148.2).abs() < 0.1, "{bearing}");
+            assert_eq!(format_bearing(bearing), "SSE");
+        }
+
+        #[test]
+        fn test_bearing_identical_points_is_zero() {
+            let point = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            assert!(point.bearing_to(point).abs() < f64::EPSILON);
+        }
+
+        #[test]
+        fn test_bearing_stays_in_range_and_finite() {
+            let poles = [
+                ValidatedCoordinate::new(90.0, 0.0).unwrap(),
+                ValidatedCoordinate::new(-90.0, 180.0).unwrap(),
+                ValidatedCoordinate::new(0.0, -180.0).unwrap(),
+                ValidatedCoordinate::new(0.0, 180.0).unwrap(),
+            ];
+
+            for from in poles {
+                for to in poles {
+                    let bearing = from.bearing_to(to);
+                    assert!(
+                        bearing.is_finite() && (0.0..360.0).contains(&bearing),
+                        "{bearing}"
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_format_bearing_compass_points() {
+            assert_eq!(format_bearing(0.0), "N");
+            assert_eq!(format_bearing(45.0), "NE");
+            assert_eq!(format_bearing(202.5), "SSW");
+            assert_eq!(format_bearing(359.0), "N");
+            assert_eq!(format_bearing(-90.0), "W");
+            assert_eq!(format_bearing(f64::NAN), "Unknown");
+        }
+    }
+
+    mod distance_tests {
+        use super::*;
+
+        #[test]
+        fn test_same_point_distance() {
             let p = ValidatedCoordinate::new(51.5074, -0.1278).unwrap();
             assert_eq!(haversine_distance(p, p), 0.0);
         }
@@ -4713,6 +10378,280 @@ mod tests {
             assert_eq!(format_time_ago(2000, 1000), "Just now");
             assert_eq!(format_time_ago(120_000, 1000), "Upcoming");
         }
+
+        #[test]
+        fn test_format_coordinate_decimal_degrees() {
+            let coord = ValidatedCoordinate::new(37.774_929, -122.419_416).unwrap();
+            assert_eq!(
+                format_coordinate(coord, CoordFormat::DecimalDegrees),
+                "37.77493, -122.41942"
+            );
+
+            let south_west = ValidatedCoordinate::new(-33.8688, -151.2093).unwrap();
+            assert_eq!(
+                format_coordinate(south_west, CoordFormat::DecimalDegrees),
+                "-33.86880, -151.20930"
+            );
+        }
+
+        #[test]
+        fn test_format_coordinate_dms_hemispheres() {
+            let north_west = ValidatedCoordinate::new(37.774_929, -122.419_416).unwrap();
+            assert_eq!(
+                format_coordinate(north_west, CoordFormat::DegreesMinutesSeconds),
+                "37\u{b0}46'29.7\"N, 122\u{b0}25'9.9\"W"
+            );
+
+            let south_east = ValidatedCoordinate::new(-33.8688, 151.2093).unwrap();
+            assert_eq!(
+                format_coordinate(south_east, CoordFormat::DegreesMinutesSeconds),
+                "33\u{b0}52'7.7\"S, 151\u{b0}12'33.5\"E"
+            );
+        }
+
+        #[test]
+        fn test_format_dms_carries_rounded_seconds() {
+            assert_eq!(
+                format_latitude(10.999_999, CoordFormat::DegreesMinutesSeconds),
+                "11\u{b0}0'0.0\"N"
+            );
+            assert_eq!(
+                format_longitude(0.0, CoordFormat::DegreesMinutesSeconds),
+                "0\u{b0}0'0.0\"E"
+            );
+        }
+
+        #[test]
+        fn test_highlight_radius_inside_and_outside() {
+            assert!(is_within_highlight_radius(0.0, 500));
+            assert!(is_within_highlight_radius(499.9, 500));
+            assert!(is_within_highlight_radius(500.0, 500));
+            assert!(!is_within_highlight_radius(500.1, 500));
+            assert!(!is_within_highlight_radius(120.0, 0));
+        }
+
+        #[test]
+        fn test_highlight_radius_ignores_unknown_distance() {
+            assert!(!is_within_highlight_radius(f64::MAX, MAX_RADIUS_M));
+            assert!(!is_within_highlight_radius(f64::NAN, MAX_RADIUS_M));
+            assert!(!is_within_highlight_radius(f64::INFINITY, MAX_RADIUS_M));
+            assert!(!is_within_highlight_radius(-1.0, MAX_RADIUS_M));
+        }
+
+        #[test]
+        fn test_location_accuracy_gate() {
+            assert!(is_location_accurate(Some(15.0), MIN_LOCATION_ACCURACY_M));
+            assert!(is_location_accurate(
+                Some(MIN_LOCATION_ACCURACY_M),
+                MIN_LOCATION_ACCURACY_M
+            ));
+            assert!(is_location_accurate(None, MIN_LOCATION_ACCURACY_M));
+            assert!(!is_location_accurate(
+                Some(5_000.0),
+                MIN_LOCATION_ACCURACY_M
+            ));
+            assert!(!is_location_accurate(
+                Some(f64::NAN),
+                MIN_LOCATION_ACCURACY_M
+            ));
+            assert!(!is_location_accurate(Some(-1.0), MIN_LOCATION_ACCURACY_M));
+        }
+
+        #[test]
+        fn test_sync_status_text_for_each_status() {
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::PendingUpload, 0).as_deref(),
+                Some("Pending sync")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::Uploading, 0).as_deref(),
+                Some("Syncing...")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::UploadingPhoto, 0).as_deref(),
+                Some("Uploading photo...")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::Failed, 0).as_deref(),
+                Some("Sync failed \u{2014} tap to retry")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::Failed, 1).as_deref(),
+                Some("Sync failed (1 attempt) \u{2014} tap to retry")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::Failed, 2).as_deref(),
+                Some("Sync failed (2 attempts) \u{2014} tap to retry")
+            );
+            assert_eq!(
+                sync_status_text(LocalCaseStatus::PermanentlyFailed, 5).as_deref(),
+                Some("Sync failed permanently (5 attempts)")
+            );
+        }
+
+        #[test]
+        fn test_sync_status_text_synced_is_none() {
+            assert_eq!(sync_status_text(LocalCaseStatus::Synced, 0), None);
+            assert_eq!(sync_status_text(LocalCaseStatus::Synced, 3), None);
+        }
+
+        #[test]
+        fn day_bucket_splits_cases_around_local_midnight() {
+            let local_midnight_utc_ms = 1_704_150_000_000;
+            let now_ms = local_midnight_utc_ms + 3 * 60 * 60 * 1000;
+            let offset_minutes = 60;
+
+            assert_eq!(
+                day_bucket(local_midnight_utc_ms - 60_000, now_ms, offset_minutes),
+                DayBucket::Yesterday
+            );
+            assert_eq!(
+                day_bucket(local_midnight_utc_ms + 60_000, now_ms, offset_minutes),
+                DayBucket::Today
+            );
+        }
+
+        #[test]
+        fn day_bucket_depends_on_utc_offset() {
+            let created_ms = 1_704_150_000_000 + 60_000;
+            let now_ms = 1_704_150_000_000 + 3 * 60 * 60 * 1000;
+
+            assert_eq!(day_bucket(created_ms, now_ms, 60), DayBucket::Today);
+            assert_eq!(day_bucket(created_ms, now_ms, 0), DayBucket::Yesterday);
+        }
+
+        #[test]
+        fn day_bucket_handles_negative_offsets() {
+            let local_midnight_utc_ms = 1_704_171_600_000;
+            let now_ms = local_midnight_utc_ms + 60 * 60 * 1000;
+
+            assert_eq!(
+                day_bucket(local_midnight_utc_ms - 1, now_ms, -300),
+                DayBucket::Yesterday
+            );
+            assert_eq!(
+                day_bucket(local_midnight_utc_ms, now_ms, -300),
+                DayBucket::Today
+            );
+        }
+
+        #[test]
+        fn day_bucket_groups_older_and_future_cases() {
+            let now_ms = 1_704_153_600_000;
+            let day_ms = 24 * 60 * 60 * 1000;
+
+            assert_eq!(
+                day_bucket(now_ms - 3 * day_ms, now_ms, 0),
+                DayBucket::Earlier
+            );
+            assert_eq!(day_bucket(now_ms + day_ms, now_ms, 0), DayBucket::Today);
+            assert_eq!(
+                day_bucket(0, now_ms, MIN_UTC_OFFSET_MINUTES),
+                DayBucket::Earlier
+            );
+        }
+
+        #[test]
+        fn test_format_distance_unit_feet() {
+            assert_eq!(format_distance_unit(0.0, DistanceUnit::Imperial), "0 ft");
+            assert_eq!(
+                format_distance_unit(91.44, DistanceUnit::Imperial),
+                "300 ft"
+            );
+            assert_eq!(
+                format_distance_unit(304.0, DistanceUnit::Imperial),
+                "997 ft"
+            );
+        }
+
+        #[test]
+        fn test_format_distance_unit_miles() {
+            assert_eq!(
+                format_distance_unit(305.0, DistanceUnit::Imperial),
+                "0.2 mi"
+            );
+            assert_eq!(
+                format_distance_unit(1931.2, DistanceUnit::Imperial),
+                "1.2 mi"
+            );
+            assert_eq!(
+                format_distance_unit(16_093.44, DistanceUnit::Imperial),
+                "10 mi"
+            );
+            assert_eq!(
+                format_distance_unit(160_934.4, DistanceUnit::Imperial),
+                "100 mi"
+            );
+        }
+
+        #[test]
+        fn test_format_distance_unit_metric_matches_default() {
+            for meters in [0.0, 999.0, 1500.0, 150_000.0, -1.0] {
+                assert_eq!(
+                    format_distance_unit(meters, DistanceUnit::Metric),
+                    format_distance(meters)
+                );
+            }
+        }
+
+        #[test]
+        fn test_format_distance_unit_invalid() {
+            assert_eq!(
+                format_distance_unit(f64::NAN, DistanceUnit::Imperial),
+                "Unknown"
+            );
+            assert_eq!(
+                format_distance_unit(f64::INFINITY, DistanceUnit::Imperial),
+                "Unknown"
+            );
+            assert_eq!(
+                format_distance_unit(-100.0, DistanceUnit::Imperial),
+                "Unknown"
+            );
+        }
+
+        #[test]
+        fn test_format_time_ago_styles_compared() {
+            let cases = [
+                (10_000, "10s ago", "10 seconds ago"),
+                (60_000, "1m ago", "1 minute ago"),
+                (330_000, "5m ago", "5 minutes 30 seconds ago"),
+                (3_660_000, "1h ago", "1 hour 1 minute ago"),
+                (3_605_000, "1h ago", "1 hour ago"),
+                (90_000_000, "1d ago", "1 day 1 hour ago"),
+                (1_296_000_000, "2w ago", "2 weeks 1 day ago"),
+                (34_560_000_000, "1y ago", "1 year 1 month ago"),
+            ];
+
+            for (elapsed_ms, compact, verbose) in cases {
+                assert_eq!(
+                    format_time_ago_styled(0, elapsed_ms, TimeAgoStyle::Compact),
+                    compact
+                );
+                assert_eq!(
+                    format_time_ago_styled(0, elapsed_ms, TimeAgoStyle::Verbose),
+                    verbose
+                );
+                assert_eq!(format_time_ago(0, elapsed_ms), compact);
+            }
+        }
+
+        #[test]
+        fn test_format_time_ago_edge_cases_match_across_styles() {
+            for (timestamp_ms, now_ms, expected) in [
+                (1000, 1000, "Just now"),
+                (0, 4_999, "Just now"),
+                (2000, 1000, "Just now"),
+                (120_000, 1000, "Upcoming"),
+            ] {
+                for style in [TimeAgoStyle::Compact, TimeAgoStyle::Verbose] {
+                    assert_eq!(
+                        format_time_ago_styled(timestamp_ms, now_ms, style),
+                        expected
+                    );
+                }
+            }
+        }
     }
 
     mod case_status_tests {
@@ -4823,16 +10762,24 @@ mod tests {
 
         #[test]
         fn test_validate_transition_success() {
-            assert!(CaseStatus::Pending.validate_transition(CaseStatus::Claimed).is_ok());
-            assert!(CaseStatus::Claimed.validate_transition(CaseStatus::EnRoute).is_ok());
-            assert!(CaseStatus::EnRoute.validate_transition(CaseStatus::Arrived).is_ok());
-            assert!(CaseStatus::Arrived.validate_transition(CaseStatus::Resolved).is_ok());
+            assert!(CaseStatus::Pending
+                .validate_transition(CaseStatus::Claimed, None)
+                .is_ok());
+            assert!(CaseStatus::Claimed
+                .validate_transition(CaseStatus::EnRoute, None)
+                .is_ok());
+            assert!(CaseStatus::EnRoute
+                .validate_transition(CaseStatus::Arrived, None)
+                .is_ok());
+            assert!(CaseStatus::Arrived
+                .validate_transition(CaseStatus::Resolved, Some(ResolutionOutcome::Rescued))
+                .is_ok());
         }
 
         #[test]
         fn test_validate_transition_same_status() {
             assert!(matches!(
-                CaseStatus::Pending.validate_transition(CaseStatus::Pending),
+                CaseStatus::Pending.validate_transition(CaseStatus::Pending, None),
                 Err(TransitionError::SameStatus)
             ));
         }
@@ -4840,7 +10787,7 @@ mod tests {
         #[test]
         fn test_validate_transition_from_terminal() {
             assert!(matches!(
-                CaseStatus::Resolved.validate_transition(CaseStatus::Pending),
+                CaseStatus::Resolved.validate_transition(CaseStatus::Pending, None),
                 Err(TransitionError::FromTerminalStatus { .. })
             ));
         }
@@ -4848,10 +10795,155 @@ mod tests {
         #[test]
         fn test_validate_transition_invalid() {
             assert!(matches!(
-                CaseStatus::Pending.validate_transition(CaseStatus::Resolved),
+                CaseStatus::Pending.validate_transition(CaseStatus::Resolved, None),
                 Err(TransitionError::InvalidTransition { .. })
             ));
         }
+
+        #[test]
+        fn test_every_case_status_maps_to_server_status() {
+            use crate::capabilities::ServerCaseStatus;
+
+            let expected = [
+                (CaseStatus::Pending, ServerCaseStatus::Open),
+                (CaseStatus::Claimed, ServerCaseStatus::InProgress),
+                (CaseStatus::EnRoute, ServerCaseStatus::InProgress),
+                (CaseStatus::Arrived, ServerCaseStatus::InProgress),
+                (CaseStatus::Resolved, ServerCaseStatus::Resolved),
+                (CaseStatus::Cancelled, ServerCaseStatus::Closed),
+                (CaseStatus::Expired, ServerCaseStatus::Closed),
+            ];
+            for (status, server) in expected {
+                assert_eq!(ServerCaseStatus::from(status), server, "{status:?}");
+            }
+        }
+
+        #[test]
+        fn test_server_status_maps_to_representative_case_status() {
+            use crate::capabilities::ServerCaseStatus;
+
+            assert_eq!(
+                CaseStatus::try_from(ServerCaseStatus::Open),
+                Ok(CaseStatus::Pending)
+            );
+            assert_eq!(
+                CaseStatus::try_from(ServerCaseStatus::InProgress),
+                Ok(CaseStatus::Claimed)
+            );
+            assert_eq!(
+                CaseStatus::try_from(ServerCaseStatus::Resolved),
+                Ok(CaseStatus::Resolved)
+            );
+            assert_eq!(
+                CaseStatus::try_from(ServerCaseStatus::Closed),
+                Err(StatusMappingError::Ambiguous(ServerCaseStatus::Closed))
+            );
+        }
+
+        #[test]
+        fn test_server_status_round_trip_is_stable() {
+            use crate::capabilities::ServerCaseStatus;
+
+            for server in [
+                ServerCaseStatus::Open,
+                ServerCaseStatus::InProgress,
+                ServerCaseStatus::Resolved,
+            ] {
+                let status = CaseStatus::try_from(server.clone()).unwrap();
+                assert_eq!(ServerCaseStatus::from(status), server);
+            }
+        }
+
+        #[test]
+        fn test_resolving_requires_outcome() {
+            assert_eq!(
+                CaseStatus::Arrived.validate_transition(CaseStatus::Resolved, None),
+                Err(TransitionError::MissingOutcome)
+            );
+            assert!(CaseStatus::Arrived
+                .validate_transition(CaseStatus::Resolved, Some(ResolutionOutcome::FalseAlarm))
+                .is_ok());
+        }
+
+        #[test]
+        fn test_cancelling_does_not_require_outcome() {
+            assert!(CaseStatus::Arrived
+                .validate_transition(CaseStatus::Cancelled, None)
+                .is_ok());
+        }
+
+        #[test]
+        fn test_transition_request_serializes_outcome() {
+            let request = TransitionCaseRequest {
+                next_status: "resolved".into(),
+                notes: None,
+                outcome: Some(ResolutionOutcome::ReferredToVet),
+            };
+
+            let json = serde_json::to_value(&request).unwrap();
+            assert_eq!(json["outcome"], "referred_to_vet");
+
+            let without = TransitionCaseRequest {
+                outcome: None,
+                ..request
+            };
+            assert!(serde_json::to_value(&without)
+                .unwrap()
+                .get("outcome")
+                .is_none());
+        }
+
+        #[test]
+        fn test_transition_path_pending_to_arrived() {
+            assert_eq!(
+                CaseStatus::Pending.transition_path(CaseStatus::Arrived),
+                Some(vec![
+                    CaseStatus::Claimed,
+                    CaseStatus::EnRoute,
+                    CaseStatus::Arrived
+                ])
+            );
+        }
+
+        #[test]
+        fn test_transition_path_claimed_to_resolved() {
+            assert_eq!(
+                CaseStatus::Claimed.transition_path(CaseStatus::Resolved),
+                Some(vec![
+                    CaseStatus::EnRoute,
+                    CaseStatus::Arrived,
+                    CaseStatus::Resolved
+                ])
+            );
+        }
+
+        #[test]
+        fn test_transition_path_single_step_matches_valid_transition() {
+            assert_eq!(
+                CaseStatus::Pending.transition_path(CaseStatus::Cancelled),
+                Some(vec![CaseStatus::Cancelled])
+            );
+        }
+
+        #[test]
+        fn test_transition_path_unreachable() {
+            assert_eq!(
+                CaseStatus::Resolved.transition_path(CaseStatus::Pending),
+                None
+            );
+            assert_eq!(
+                CaseStatus::Arrived.transition_path(CaseStatus::Claimed),
+                None
+            );
+            assert_eq!(
+                CaseStatus::Claimed.transition_path(CaseStatus::Expired),
+                None
+            );
+            assert_eq!(
+                CaseStatus::EnRoute.transition_path(CaseStatus::EnRoute),
+                None
+            );
+        }
     }
 
     mod retry_tests {
@@ -4876,31 +10968,181 @@ mod tests {
             let delay = calculate_retry_delay(0, 500);
             assert_eq!(delay, BASE_RETRY_DELAY_MS + 500);
         }
-    }
 
-    mod outbox_tests {
-        use super::*;
+        #[test]
+        fn test_generate_jitter_from_is_deterministic() {
+            assert_eq!(generate_jitter_from(42), generate_jitter_from(42));
+            assert_ne!(generate_jitter_from(42), generate_jitter_from(43));
+        }
 
         #[test]
-        fn test_outbox_entry_new() {
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test_token".into(),
-            };
-            let entry = OutboxEntry::new(intent);
+        fn test_generate_jitter_from_is_roughly_uniform() {
+            const BUCKETS: usize = 10;
+            const SAMPLES: u64 = 20_000;
+            let mut counts = [0u64; BUCKETS];
+
+            for seed in 0..SAMPLES {
+                let jitter = generate_jitter_from(seed);
+                assert!(jitter < JITTER_MAX_MS);
+                let bucket = usize::try_from(jitter * BUCKETS as u64 / JITTER_MAX_MS).unwrap();
+                counts[bucket] += 1;
+            }
 
-            assert!(!entry.op_id.0.is_empty());
-            assert!(!entry.idempotency_key.0.is_empty());
-            assert_eq!(entry.retry_state, RetryState::Pending);
-            assert_eq!(entry.attempt_count, 0);
-            assert!(entry.last_attempt_at.is_none());
-            assert!(entry.next_retry_at.is_none());
-            assert!(entry.last_error.is_none());
+            let expected = SAMPLES / BUCKETS as u64;
+            for count in counts {
+                assert!(
+                    count.abs_diff(expected) < expected / 10,
+                    "bucket counts {counts:?}"
+                );
+            }
         }
 
         #[test]
-        fn test_outbox_entry_mark_in_flight() {
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test".into(),
+        fn test_mark_failed_with_jitter_is_deterministic() {
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            entry.attempt_count = 1;
+
+            entry.mark_failed_with_jitter(OutboxEntryError::network_error("offline"), 250);
+
+            let delay = entry.next_retry_at.unwrap().elapsed_since(entry.updated_at);
+            assert_eq!(delay, calculate_retry_delay(1, 250));
+        }
+
+        #[test]
+        fn test_exponential_additive_strategy_matches_legacy_delay() {
+            for seed in 0..100 {
+                let delay = calculate_retry_delay_strategy(
+                    2,
+                    0,
+                    seed,
+                    BackoffStrategy::ExponentialAdditive,
+                );
+                assert!(delay >= calculate_retry_delay(2, 0));
+                assert!(delay < calculate_retry_delay(2, JITTER_MAX_MS));
+            }
+        }
+
+        #[test]
+        fn test_full_jitter_stays_within_capped_exponential() {
+            for attempt in 0..20 {
+                let ceiling = calculate_retry_delay(attempt, 0);
+                let delays: Vec<u64> = (0..200)
+                    .map(|seed| {
+                        calculate_retry_delay_strategy(
+                            attempt,
+                            0,
+                            seed,
+                            BackoffStrategy::FullJitter,
+                        )
+                    })
+                    .collect();
+
+                assert!(delays
+                    .iter()
+                    .all(|&d| d <= ceiling && d <= MAX_RETRY_DELAY_MS));
+                assert!(delays.iter().any(|&d| d < ceiling / 2));
+            }
+        }
+
+        #[test]
+        fn test_decorrelated_stays_between_base_and_cap() {
+            let mut prev = 0;
+            for seed in 0..200 {
+                let delay =
+                    calculate_retry_delay_strategy(0, prev, seed, BackoffStrategy::Decorrelated);
+                assert!(delay >= BASE_RETRY_DELAY_MS);
+                assert!(delay <= MAX_RETRY_DELAY_MS);
+                assert!(delay <= prev.saturating_mul(3).max(BASE_RETRY_DELAY_MS));
+                prev = delay;
+            }
+        }
+
+        #[test]
+        fn test_strategy_delays_respect_their_ceilings() {
+            for attempt in 0..30 {
+                let capped = calculate_retry_delay(attempt, 0);
+                for seed in 0..50 {
+                    let delay = |strategy| {
+                        calculate_retry_delay_strategy(attempt, MAX_RETRY_DELAY_MS, seed, strategy)
+                    };
+
+                    let additive = delay(BackoffStrategy::ExponentialAdditive);
+                    assert!(
+                        (capped..capped + JITTER_MAX_MS).contains(&additive),
+                        "attempt {attempt}"
+                    );
+                    assert!(
+                        delay(BackoffStrategy::FullJitter) <= capped,
+                        "attempt {attempt}"
+                    );
+                    let decorrelated = delay(BackoffStrategy::Decorrelated);
+                    assert!(
+                        (BASE_RETRY_DELAY_MS..=MAX_RETRY_DELAY_MS).contains(&decorrelated),
+                        "attempt {attempt}"
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_decorrelated_entry_remembers_previous_delay() {
+            let mut entry = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: LocalOpId::generate(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+
+            entry.mark_failed_with_strategy(
+                OutboxEntryError::network_error("offline"),
+                BackoffStrategy::Decorrelated,
+                7,
+            );
+            let first = entry.last_retry_delay_ms;
+            assert_eq!(
+                first,
+                entry.next_retry_at.unwrap().elapsed_since(entry.updated_at)
+            );
+            assert_eq!(first, BASE_RETRY_DELAY_MS);
+
+            entry.mark_failed_with_strategy(
+                OutboxEntryError::network_error("offline"),
+                BackoffStrategy::Decorrelated,
+                8,
+            );
+            assert!((BASE_RETRY_DELAY_MS..=first * 3).contains(&entry.last_retry_delay_ms));
+
+            entry.reset_backoff();
+            assert_eq!(entry.last_retry_delay_ms, 0);
+        }
+    }
+
+    mod outbox_tests {
+        use super::*;
+
+        #[test]
+        fn test_outbox_entry_new() {
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test_token".into(),
+            };
+            let entry = OutboxEntry::new(intent);
+
+            assert!(!entry.op_id.0.is_empty());
+            assert!(!entry.idempotency_key.0.is_empty());
+            assert_eq!(entry.retry_state, RetryState::Pending);
+            assert_eq!(entry.attempt_count, 0);
+            assert!(entry.last_attempt_at.is_none());
+            assert!(entry.next_retry_at.is_none());
+            assert!(entry.last_error.is_none());
+        }
+
+        #[test]
+        fn test_outbox_entry_mark_in_flight() {
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test".into(),
             };
             let mut entry = OutboxEntry::new(intent);
 
@@ -4955,563 +11197,7513 @@ mod tests {
                 entry.mark_failed(OutboxEntryError::network_error("test error"));
             }
 
-            assert_eq!(entry.retry_state, RetryState::PermanentlyFailed);
-            assert!(entry.is_permanently_failed());
+            assert_eq!(entry.retry_state, RetryState::PermanentlyFailed);
+            assert!(entry.is_permanently_failed());
+        }
+
+        #[test]
+        fn test_outbox_entry_rate_limited() {
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test".into(),
+            };
+            let mut entry = OutboxEntry::new(intent);
+
+            entry.mark_rate_limited(60_000);
+
+            assert_eq!(entry.retry_state, RetryState::RateLimited);
+            assert!(entry.next_retry_at.is_some());
+        }
+
+        #[test]
+        fn test_outbox_entry_is_ready_for_retry() {
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test".into(),
+            };
+            let mut entry = OutboxEntry::new(intent);
+
+            assert!(entry.is_ready_for_retry(0));
+
+            entry.mark_in_flight();
+            assert!(!entry.is_ready_for_retry(0));
+
+            entry.mark_completed();
+            assert!(!entry.is_ready_for_retry(0));
+        }
+
+        #[test]
+        fn test_offline_store_push_outbox() {
+            let mut store = OfflineStore::new();
+
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test".into(),
+            };
+            let entry = OutboxEntry::new(intent);
+            let op_id = entry.op_id.clone();
+
+            assert!(store.push_outbox(entry).is_ok());
+            assert_eq!(store.outbox.len(), 1);
+
+            let duplicate_entry = OutboxEntry {
+                op_id: op_id.clone(),
+                idempotency_key: IdempotencyKey::generate(),
+                intent: OutboxIntent::SyncFcmToken {
+                    token: "test2".into(),
+                },
+                created_at: UnixTimeMs::now(),
+                updated_at: UnixTimeMs::now(),
+                retry_state: RetryState::Pending,
+                attempt_count: 0,
+                last_attempt_at: None,
+                next_retry_at: None,
+                last_retry_delay_ms: 0,
+                last_error: None,
+            };
+            assert!(matches!(
+                store.push_outbox(duplicate_entry),
+                Err(OutboxError::DuplicateOpId(_))
+            ));
+        }
+
+        #[test]
+        fn test_offline_store_pending_count() {
+            let mut store = OfflineStore::new();
+
+            assert_eq!(store.pending_sync_count(), 0);
+
+            let intent = OutboxIntent::SyncFcmToken {
+                token: "test".into(),
+            };
+            store.push_outbox(OutboxEntry::new(intent)).unwrap();
+
+            assert_eq!(store.pending_sync_count(), 1);
+
+            store.outbox[0].mark_completed();
+            assert_eq!(store.pending_sync_count(), 0);
+        }
+
+        fn retry_delay_ms(entry: &OutboxEntry) -> u64 {
+            entry.next_retry_at.unwrap().elapsed_since(entry.updated_at)
+        }
+
+        #[test]
+        fn test_repeated_timeouts_escalate_backoff() {
+            let mut tracker = TimeoutTracker::default();
+            let mut plain = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "a".into() });
+            let mut slow = plain.clone();
+
+            let mut streak = 0;
+            for _ in 0..SLOW_LINK_TIMEOUT_THRESHOLD {
+                streak = tracker.record_timeout("create_case");
+            }
+            assert!(tracker.is_escalated("create_case", SLOW_LINK_TIMEOUT_THRESHOLD));
+
+            plain.mark_in_flight();
+            plain.mark_timed_out(1, SLOW_LINK_TIMEOUT_THRESHOLD, BackoffStrategy::default());
+            slow.mark_in_flight();
+            slow.mark_timed_out(
+                streak,
+                SLOW_LINK_TIMEOUT_THRESHOLD,
+                BackoffStrategy::default(),
+            );
+
+            assert_eq!(slow.retry_state, RetryState::Failed);
+            assert!(retry_delay_ms(&slow) > retry_delay_ms(&plain));
+            assert!(
+                retry_delay_ms(&slow) >= calculate_retry_delay(1, 0) * SLOW_LINK_BACKOFF_MULTIPLIER
+            );
+            assert_eq!(slow.last_retry_delay_ms, retry_delay_ms(&slow));
+        }
+
+        #[test]
+        fn test_intermittent_timeouts_do_not_escalate() {
+            let mut tracker = TimeoutTracker::default();
+
+            for _ in 0..SLOW_LINK_TIMEOUT_THRESHOLD * 2 {
+                assert_eq!(tracker.record_timeout("create_case"), 1);
+                tracker.record_response("create_case");
+            }
+            assert!(!tracker.is_escalated("create_case", SLOW_LINK_TIMEOUT_THRESHOLD));
+
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "a".into() });
+            entry.mark_in_flight();
+            entry.mark_timed_out(
+                tracker.record_timeout("create_case"),
+                SLOW_LINK_TIMEOUT_THRESHOLD,
+                BackoffStrategy::default(),
+            );
+            assert!(retry_delay_ms(&entry) <= calculate_retry_delay(1, JITTER_MAX_MS));
+        }
+
+        #[test]
+        fn test_timeout_streaks_are_tracked_per_endpoint() {
+            let mut tracker = TimeoutTracker::default();
+            tracker.record_timeout("create_case");
+            tracker.record_timeout("create_case");
+            tracker.record_timeout("sync_fcm_token");
+
+            assert_eq!(tracker.streak("create_case"), 2);
+            assert_eq!(tracker.streak("sync_fcm_token"), 1);
+            assert_eq!(tracker.streak("claim_case"), 0);
+        }
+
+        #[test]
+        fn test_slow_link_backoff_stays_within_max_delay() {
+            let mut entry = OutboxEntry::new(OutboxIntent::UploadPhoto {
+                local_id: LocalOpId::new("local-1"),
+                upload_url: "https://upload.example/photo".into(),
+                upload_headers: HashMap::new(),
+            });
+            // The fourth attempt backs off 16s, which the slow-link multiplier would push past the cap.
+            entry.attempt_count = 3;
+            entry.mark_in_flight();
+            entry.mark_timed_out(
+                SLOW_LINK_TIMEOUT_THRESHOLD,
+                SLOW_LINK_TIMEOUT_THRESHOLD,
+                BackoffStrategy::default(),
+            );
+
+            assert_eq!(entry.retry_state, RetryState::Failed);
+            assert!(retry_delay_ms(&entry) <= MAX_RETRY_DELAY_MS);
+            assert!(entry.last_retry_delay_ms <= MAX_RETRY_DELAY_MS);
+        }
+
+        fn attempts_until_dead_letter(intent: OutboxIntent) -> u32 {
+            let mut entry = OutboxEntry::new(intent);
+            while !entry.is_permanently_failed() {
+                entry.mark_in_flight();
+                entry.mark_failed(OutboxEntryError::network_error("offline"));
+            }
+            entry.attempt_count
+        }
+
+        #[test]
+        fn test_max_attempts_for_intent() {
+            let fcm = OutboxIntent::SyncFcmToken { token: "t".into() };
+            let claim = OutboxIntent::ClaimCase {
+                case_id: CaseId::new("c"),
+            };
+            let create = OutboxIntent::CreateCase {
+                local_id: LocalOpId::generate(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: UnixTimeMs::now(),
+            };
+
+            assert_eq!(max_attempts_for(&create), MAX_RETRY_ATTEMPTS);
+            assert!(max_attempts_for(&claim) < MAX_RETRY_ATTEMPTS);
+            assert!(max_attempts_for(&fcm) < max_attempts_for(&claim));
+        }
+
+        #[test]
+        fn test_fcm_sync_dead_letters_before_create_case() {
+            let fcm_attempts =
+                attempts_until_dead_letter(OutboxIntent::SyncFcmToken { token: "t".into() });
+            let create_attempts = attempts_until_dead_letter(OutboxIntent::CreateCase {
+                local_id: LocalOpId::generate(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+
+            assert_eq!(fcm_attempts, 2);
+            assert_eq!(create_attempts, MAX_RETRY_ATTEMPTS);
+            assert!(fcm_attempts < create_attempts);
+        }
+
+        #[test]
+        fn test_projected_retry_schedule_respects_max_attempts() {
+            assert_eq!(projected_retry_schedule(0, 5, 0).len(), 4);
+            assert_eq!(projected_retry_schedule(3, 5, 0).len(), 1);
+            assert!(projected_retry_schedule(4, 5, 0).is_empty());
+            assert!(projected_retry_schedule(9, 5, 0).is_empty());
+        }
+
+        #[test]
+        fn test_projected_retry_schedule_grows_then_caps() {
+            let now_ms = 1_000_000;
+            let schedule = projected_retry_schedule(0, 9, now_ms);
+            let delays: Vec<u64> = std::iter::once(now_ms)
+                .chain(schedule.iter().copied())
+                .collect::<Vec<_>>()
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .collect();
+
+            assert_eq!(
+                delays,
+                vec![
+                    2_000,
+                    4_000,
+                    8_000,
+                    16_000,
+                    32_000,
+                    MAX_RETRY_DELAY_MS,
+                    MAX_RETRY_DELAY_MS,
+                    MAX_RETRY_DELAY_MS
+                ]
+            );
+        }
+
+        #[test]
+        fn test_debug_snapshot_includes_projected_retries() {
+            let mut store = OfflineStore::new();
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            entry.mark_in_flight();
+            entry.mark_failed(OutboxEntryError::network_error("offline"));
+            let next_retry_ms = entry.next_retry_at.unwrap().0;
+            store.push_outbox(entry).unwrap();
+
+            let snapshot = store.debug_snapshot(0);
+
+            assert_eq!(snapshot.len(), 1);
+            assert_eq!(snapshot[0].max_attempts, 2);
+            assert_eq!(snapshot[0].projected_retries_ms, vec![next_retry_ms]);
+        }
+
+        fn dead_lettered_create(store: &mut OfflineStore) -> OpId {
+            let mut local_case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            local_case.status = LocalCaseStatus::PermanentlyFailed;
+            let local_id = local_case.local_id.clone();
+            store.push_local_case(local_case).unwrap();
+
+            let mut entry = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id,
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+            entry.mark_permanently_failed(OutboxEntryError::server_error(422, None));
+            let op_id = entry.op_id.clone();
+            store.push_outbox(entry).unwrap();
+            op_id
+        }
+
+        #[test]
+        fn test_dismiss_dead_lettered_create_removes_entry_and_case() {
+            let mut store = OfflineStore::new();
+            let op_id = dead_lettered_create(&mut store);
+            store
+                .push_outbox(OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: "t".into(),
+                }))
+                .unwrap();
+
+            assert_eq!(store.failed_count(), 1);
+            assert_eq!(store.pending_sync_count(), 1);
+
+            let dismissed = store.dismiss_failed_entry(&op_id).unwrap();
+
+            assert_eq!(dismissed.op_id, op_id);
+            assert_eq!(store.failed_count(), 0);
+            assert_eq!(store.pending_sync_count(), 1);
+            assert!(store.pending_local_cases.is_empty());
+            assert_eq!(store.outbox.len(), 1);
+        }
+
+        #[test]
+        fn test_dismiss_rejects_live_or_unknown_entries() {
+            let mut store = OfflineStore::new();
+            let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            let op_id = entry.op_id.clone();
+            store.push_outbox(entry).unwrap();
+
+            assert!(matches!(
+                store.dismiss_failed_entry(&op_id),
+                Err(OutboxError::InvalidState)
+            ));
+            assert!(matches!(
+                store.dismiss_failed_entry(&OpId("missing".into())),
+                Err(OutboxError::NotFound(_))
+            ));
+            assert_eq!(store.outbox.len(), 1);
+        }
+
+        #[test]
+        fn test_repair_creates_missing_outbox_entry() {
+            let mut store = OfflineStore::new();
+            let local_case = LocalCase::new(LatLon::new(1.0, 2.0), Some("dog".into()), Some(3));
+            let local_id = local_case.local_id.clone();
+            store.push_local_case(local_case).unwrap();
+
+            store.repair_missing_outbox_entries();
+
+            assert_eq!(store.outbox.len(), 1);
+            let entry = &store.outbox[0];
+            assert_eq!(entry.idempotency_key.as_str(), local_id.as_str());
+            match &entry.intent {
+                OutboxIntent::CreateCase {
+                    local_id: id,
+                    wound_severity,
+                    ..
+                } => {
+                    assert_eq!(id, &local_id);
+                    assert_eq!(*wound_severity, Some(3));
+                }
+                other => panic!("unexpected intent {other:?}"),
+            }
+
+            store.repair_missing_outbox_entries();
+            assert_eq!(store.outbox.len(), 1);
+        }
+
+        #[test]
+        fn test_repair_leaves_covered_cases_untouched() {
+            let mut store = OfflineStore::new();
+            let local_case = LocalCase::new(LatLon::new(1.0, 2.0), None, None);
+            let entry = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: local_case.local_id.clone(),
+                location: local_case.location,
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: local_case.created_at_ms_utc,
+            });
+            let op_id = entry.op_id.clone();
+            store.push_local_case(local_case).unwrap();
+            store.push_outbox(entry).unwrap();
+
+            let mut synced = LocalCase::new(LatLon::new(3.0, 4.0), None, None);
+            synced.mark_synced(CaseId::new("server1"));
+            store.push_local_case(synced).unwrap();
+
+            store.repair_missing_outbox_entries();
+
+            assert_eq!(store.outbox.len(), 1);
+            assert_eq!(store.outbox[0].op_id, op_id);
+        }
+
+        fn store_with_fcm_entries(count: usize) -> (OfflineStore, Vec<OpId>) {
+            let mut store = OfflineStore::new();
+            let mut ids = Vec::new();
+            for i in 0..count {
+                let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: format!("t{i}"),
+                });
+                ids.push(entry.op_id.clone());
+                store.push_outbox(entry).unwrap();
+            }
+            (store, ids)
+        }
+
+        #[test]
+        fn test_promoted_entry_is_next_pending_despite_being_newest() {
+            let (mut store, ids) = store_with_fcm_entries(3);
+            let newest = ids[2].clone();
+
+            store.promote_entry(&newest).unwrap();
+
+            let next = store.get_next_pending_entry(get_current_time_ms()).unwrap();
+            assert_eq!(next.op_id, newest);
+            assert_eq!(store.outbox.len(), 3);
+        }
+
+        #[test]
+        fn test_promote_clears_backoff_and_rejects_finished_entries() {
+            let (mut store, ids) = store_with_fcm_entries(2);
+            store.mark_entry_failed(&ids[1], OutboxEntryError::new("NETWORK"));
+            assert!(store.outbox[1].next_retry_at.is_some());
+
+            store.promote_entry(&ids[1]).unwrap();
+            assert!(store.outbox[0].next_retry_at.is_none());
+            assert!(store.outbox[0].is_ready_for_retry(0));
+
+            store.mark_entry_completed(&ids[0]);
+            assert!(matches!(
+                store.promote_entry(&ids[0]),
+                Err(OutboxError::InvalidState)
+            ));
+            assert!(matches!(
+                store.promote_entry(&OpId("missing".into())),
+                Err(OutboxError::NotFound(_))
+            ));
+        }
+
+        #[test]
+        fn audit_log_records_ordered_state_transitions() {
+            let mut store = OfflineStore::new();
+            let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                token: "token".into(),
+            });
+            let op_id = entry.op_id.clone();
+            store.push_outbox(entry).unwrap();
+
+            store.mark_entry_in_flight(&op_id);
+            store.mark_entry_failed(&op_id, OutboxEntryError::network_error("offline"));
+            store.mark_entry_in_flight(&op_id);
+            store.mark_entry_completed(&op_id);
+
+            let transitions: Vec<(RetryState, RetryState, Option<&str>)> = store
+                .audit_log()
+                .iter()
+                .map(|a| (a.from_state, a.to_state, a.error_code.as_deref()))
+                .collect();
+
+            assert_eq!(
+                transitions,
+                vec![
+                    (RetryState::Pending, RetryState::InFlight, None),
+                    (
+                        RetryState::InFlight,
+                        RetryState::Failed,
+                        Some("NETWORK_ERROR")
+                    ),
+                    (RetryState::Failed, RetryState::InFlight, None),
+                    (RetryState::InFlight, RetryState::Completed, None),
+                ]
+            );
+            assert!(store.audit_log().iter().all(|a| a.op_id == op_id));
+        }
+
+        #[test]
+        fn audit_log_ignores_unknown_entries_and_is_capped() {
+            let (mut store, ids) = store_with_fcm_entries(1);
+
+            store.mark_entry_completed(&OpId::new("missing"));
+            assert!(store.audit_log().is_empty());
+
+            for _ in 0..MAX_AUDIT_LOG_ENTRIES {
+                store.mark_entry_in_flight(&ids[0]);
+                store.mark_entry_failed(&ids[0], OutboxEntryError::new("NETWORK"));
+                store.outbox[0].attempt_count = 0;
+            }
+
+            assert_eq!(store.audit_log().len(), MAX_AUDIT_LOG_ENTRIES);
+            assert_eq!(store.audit_log()[0].from_state, RetryState::Failed);
+        }
+
+        #[test]
+        fn audit_log_records_manual_retry_of_failed_entries() {
+            let (mut store, ids) = store_with_fcm_entries(2);
+            store.mark_entry_failed(&ids[0], OutboxEntryError::new("NETWORK"));
+
+            assert_eq!(store.retry_failed_entries(), 1);
+
+            assert_eq!(store.outbox[0].retry_state, RetryState::Pending);
+            assert!(store.outbox[0].next_retry_at.is_none());
+            let last = store.audit_log().last().unwrap();
+            assert_eq!(
+                (last.from_state, last.to_state),
+                (RetryState::Failed, RetryState::Pending)
+            );
+            assert_eq!(last.op_id, ids[0]);
+        }
+
+        #[test]
+        fn audit_log_survives_serialization() {
+            let (mut store, ids) = store_with_fcm_entries(1);
+            store.mark_entry_in_flight(&ids[0]);
+
+            let restored: OfflineStore =
+                serde_json::from_str(&serde_json::to_string(&store).unwrap()).unwrap();
+
+            assert_eq!(restored.audit_log(), store.audit_log());
+        }
+
+        #[test]
+        fn dedup_local_cases_keeps_newest_duplicate() {
+            let mut store = OfflineStore::new();
+            let mut older = LocalCase::new(LatLon::new(1.0, 1.0), Some("older".into()), None);
+            older.updated_at_ms_utc = UnixTimeMs(1_000);
+            let mut newer = older.clone();
+            newer.description = Some("newer".into());
+            newer.updated_at_ms_utc = UnixTimeMs(2_000);
+            let unique = LocalCase::new(LatLon::new(2.0, 2.0), Some("unique".into()), None);
+            store.pending_local_cases = vec![newer, unique.clone(), older];
+
+            let removed = store.dedup_local_cases();
+
+            assert_eq!(removed, 1);
+            assert_eq!(store.pending_local_cases.len(), 2);
+            assert_eq!(
+                store.pending_local_cases[0].description.as_deref(),
+                Some("newer")
+            );
+            assert_eq!(store.pending_local_cases[1].local_id, unique.local_id);
+            assert!(store.get_local_case_mut(&unique.local_id).is_some());
+        }
+
+        #[test]
+        fn dedup_outbox_collapses_duplicate_op_ids() {
+            let (mut store, ids) = store_with_fcm_entries(2);
+            let mut stale = store.outbox[0].clone();
+            stale.updated_at = UnixTimeMs(0);
+            stale.attempt_count = 99;
+            store.outbox.push(stale);
+
+            let removed = store.dedup_outbox();
+
+            assert_eq!(removed, 1);
+            let op_ids: Vec<&OpId> = store.outbox.iter().map(|e| &e.op_id).collect();
+            assert_eq!(op_ids, vec![&ids[0], &ids[1]]);
+            assert_eq!(store.outbox[0].attempt_count, 0);
+        }
+
+        #[test]
+        fn dedup_preserves_unique_ids() {
+            let (mut store, ids) = store_with_fcm_entries(3);
+            store.pending_local_cases = vec![
+                LocalCase::new(LatLon::new(1.0, 1.0), None, None),
+                LocalCase::new(LatLon::new(2.0, 2.0), None, None),
+            ];
+
+            assert_eq!(store.dedup_local_cases(), 0);
+            assert_eq!(store.dedup_outbox(), 0);
+            assert_eq!(store.pending_local_cases.len(), 2);
+            assert_eq!(store.outbox.len(), ids.len());
+        }
+
+        #[test]
+        fn test_discard_failed_create_drops_local_case_and_photo() {
+            let mut store = OfflineStore::new();
+            let local_case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            let local_id = local_case.local_id.clone();
+            store.push_local_case(local_case).unwrap();
+
+            let mut create = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: local_id.clone(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: true,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+            create.mark_failed(OutboxEntryError::server_error(503, None));
+            assert_eq!(create.retry_state, RetryState::Failed);
+            let op_id = create.op_id.clone();
+            store.push_outbox(create).unwrap();
+            store
+                .push_outbox(OutboxEntry::new(OutboxIntent::UploadPhoto {
+                    local_id,
+                    upload_url: "https://example.com/upload".into(),
+                    upload_headers: HashMap::new(),
+                }))
+                .unwrap();
+
+            store.discard_entry(&op_id).unwrap();
+
+            assert!(store.outbox.is_empty());
+            assert!(store.pending_local_cases.is_empty());
+        }
+
+        #[test]
+        fn test_discard_rejects_in_flight_entry() {
+            let mut store = OfflineStore::new();
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            entry.mark_in_flight();
+            let op_id = entry.op_id.clone();
+            store.push_outbox(entry).unwrap();
+
+            assert!(matches!(
+                store.discard_entry(&op_id),
+                Err(OutboxError::InvalidState)
+            ));
+            assert_eq!(store.outbox.len(), 1);
+            assert!(matches!(
+                store.discard_entry(&OpId("missing".into())),
+                Err(OutboxError::NotFound(_))
+            ));
+        }
+
+        #[test]
+        fn test_discard_rejects_failed_entry_with_sibling_in_flight() {
+            let mut store = OfflineStore::new();
+            let local_id = LocalOpId::new("local-1");
+            let mut create = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: local_id.clone(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: true,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+            create.mark_failed(OutboxEntryError::server_error(503, None));
+            let op_id = create.op_id.clone();
+            store.push_outbox(create).unwrap();
+            let mut upload = OutboxEntry::new(OutboxIntent::UploadPhoto {
+                local_id,
+                upload_url: "https://example.com/upload".into(),
+                upload_headers: HashMap::new(),
+            });
+            upload.mark_in_flight();
+            store.push_outbox(upload).unwrap();
+
+            assert!(store.has_in_flight_sibling(&store.outbox[0]));
+            assert!(matches!(
+                store.discard_entry(&op_id),
+                Err(OutboxError::InvalidState)
+            ));
+            assert_eq!(store.outbox.len(), 2);
+        }
+
+        #[test]
+        fn test_discarding_failed_upload_keeps_local_case_and_create() {
+            let mut store = OfflineStore::new();
+            let case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            let local_id = case.local_id.clone();
+            store.pending_local_cases.push(case);
+            let create = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: local_id.clone(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: true,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+            let create_id = create.op_id.clone();
+            store.push_outbox(create).unwrap();
+            let mut upload = OutboxEntry::new(OutboxIntent::UploadPhoto {
+                local_id,
+                upload_url: "https://example.com/upload".into(),
+                upload_headers: HashMap::new(),
+            });
+            upload.mark_failed(OutboxEntryError::server_error(503, None));
+            let upload_id = upload.op_id.clone();
+            store.push_outbox(upload).unwrap();
+
+            store.discard_entry(&upload_id).unwrap();
+
+            assert_eq!(store.pending_local_cases.len(), 1);
+            assert_eq!(store.outbox.len(), 1);
+            assert_eq!(store.outbox[0].op_id, create_id);
+        }
+
+        #[test]
+        fn test_removing_failed_entry_is_audited() {
+            let (mut store, ids) = store_with_fcm_entries(1);
+            store.mark_entry_failed(&ids[0], OutboxEntryError::new("NETWORK"));
+
+            assert!(matches!(
+                store.dismiss_failed_entry(&ids[0]),
+                Err(OutboxError::InvalidState)
+            ));
+            store.discard_entry(&ids[0]).unwrap();
+
+            let last = store.audit_log().last().unwrap();
+            assert_eq!(last.op_id, ids[0]);
+            assert_eq!(
+                (last.from_state, last.to_state),
+                (RetryState::Failed, RetryState::Discarded)
+            );
+        }
+    }
+
+    mod error_tests {
+        use super::*;
+
+        #[test]
+        fn test_app_error_new() {
+            let error = AppError::new(ErrorKind::Network, "Test error");
+
+            assert_eq!(error.kind, ErrorKind::Network);
+            assert_eq!(error.severity, ErrorSeverity::Transient);
+            assert_eq!(error.message, "Test error");
+            assert!(error.internal_message.is_none());
+            assert!(error.is_retryable());
+        }
+
+        #[test]
+        fn test_app_error_with_internal() {
+            let error = AppError::new(ErrorKind::Network, "User message")
+                .with_internal("Internal details");
+
+            assert_eq!(error.internal_message, Some("Internal details".into()));
+        }
+
+        #[test]
+        fn test_app_error_with_context() {
+            let error = AppError::new(ErrorKind::Validation, "Invalid input")
+                .with_context("field", "email")
+                .with_context("value", "invalid@");
+
+            assert_eq!(error.context.get("field"), Some(&"email".to_string()));
+            assert_eq!(error.context.get("value"), Some(&"invalid@".to_string()));
+        }
+
+        #[test]
+        fn test_app_error_from_http_status() {
+            assert_eq!(
+                AppError::from_http_status(400, None).kind,
+                ErrorKind::Validation
+            );
+            assert_eq!(
+                AppError::from_http_status(401, None).kind,
+                ErrorKind::Authentication
+            );
+            assert_eq!(
+                AppError::from_http_status(403, None).kind,
+                ErrorKind::Authorization
+            );
+            assert_eq!(
+                AppError::from_http_status(404, None).kind,
+                ErrorKind::NotFound
+            );
+            assert_eq!(
+                AppError::from_http_status(409, None).kind,
+                ErrorKind::Conflict
+            );
+            assert_eq!(
+                AppError::from_http_status(429, None).kind,
+                ErrorKind::RateLimited
+            );
+            assert_eq!(
+                AppError::from_http_status(500, None).kind,
+                ErrorKind::Internal
+            );
+        }
+
+        #[test]
+        fn test_error_kind_retryable() {
+            assert!(ErrorKind::Network.is_retryable());
+            assert!(ErrorKind::Timeout.is_retryable());
+            assert!(ErrorKind::RateLimited.is_retryable());
+            assert!(!ErrorKind::Conflict.is_retryable());
+            assert!(!ErrorKind::Authentication.is_retryable());
+            assert!(!ErrorKind::Validation.is_retryable());
+            assert!(!ErrorKind::Internal.is_retryable());
+        }
+
+        #[test]
+        fn test_user_facing_message() {
+            let network_error = AppError::new(ErrorKind::Network, "Connection failed");
+            assert!(network_error.user_facing_message().contains("internet"));
+
+            let validation_error = AppError::new(ErrorKind::Validation, "Email is invalid");
+            assert_eq!(validation_error.user_facing_message(), "Email is invalid");
+
+            let internal_error = AppError::new(ErrorKind::Internal, "Database error");
+            assert!(internal_error.user_facing_message().contains("unexpected"));
+        }
+
+        #[test]
+        fn test_classify_retryability_pins_status_codes() {
+            let cases = [
+                (Some(409), ErrorKind::Conflict, Retryability::Permanent),
+                (Some(429), ErrorKind::RateLimited, Retryability::Retryable),
+                (Some(400), ErrorKind::Validation, Retryability::Permanent),
+                (Some(500), ErrorKind::Internal, Retryability::Retryable),
+                (None, ErrorKind::Network, Retryability::Retryable),
+                (None, ErrorKind::Conflict, Retryability::Permanent),
+            ];
+            for (status, kind, expected) in cases {
+                assert_eq!(
+                    classify_retryability(status, kind),
+                    expected,
+                    "{status:?} {kind:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_app_error_and_outbox_error_agree_on_retryability() {
+            for status in [409, 429, 400, 500] {
+                let app_error = AppError::from_http_status(status, None);
+                let outbox_error = OutboxEntryError::server_error(status, None);
+                assert_eq!(app_error.http_status(), Some(status));
+                assert_eq!(
+                    app_error.is_retryable(),
+                    !outbox_error.is_permanent,
+                    "status {status}"
+                );
+            }
+
+            assert!(AppError::new(ErrorKind::Network, "offline").is_retryable());
+            assert!(!OutboxEntryError::network_error("offline").is_permanent);
+        }
+
+        #[test]
+        fn strict_response_parsing_rejects_unknown_fields() {
+            let body = br#"{"cases":[],"next_cursor":null,"total_count":0,"extra":1}"#;
+
+            let result = parse_response::<ListCasesResponse>(body, true);
+
+            match result {
+                Err(ResponseParseError::UnknownField(field)) => assert_eq!(field, "extra"),
+                other => panic!("expected unknown field error, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn strict_response_parsing_rejects_unknown_nested_case_fields() {
+            let mut case =
+                serde_json::to_value(server_case("case-1", CaseStatus::Pending)).unwrap();
+            case["priority"] = serde_json::json!(1);
+            let body = serde_json::to_vec(&serde_json::json!({ "cases": [case] })).unwrap();
+
+            assert!(parse_response::<ListCasesResponse>(&body, false).is_ok());
+            match parse_response::<ListCasesResponse>(&body, true) {
+                Err(ResponseParseError::UnknownField(field)) => assert_eq!(field, "priority"),
+                other => panic!("expected unknown field error, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn strict_response_parsing_ignores_omitted_optional_fields() {
+            let body = br#"{"id":"case-1","created_at":"2024-01-01T00:00:00Z"}"#;
+
+            let response = parse_response::<CreateCaseResponse>(body, true).unwrap();
+
+            assert!(response.photo_upload_url.is_none());
+        }
+
+        #[test]
+        fn lenient_response_parsing_ignores_unknown_fields() {
+            let body = br#"{"cases":[],"next_cursor":"abc","extra":1}"#;
+
+            let response = parse_response::<ListCasesResponse>(body, false).unwrap();
+
+            assert!(response.cases.is_empty());
+            assert_eq!(response.next_cursor.as_deref(), Some("abc"));
+        }
+
+        #[test]
+        fn strict_response_parsing_accepts_exact_fields() {
+            let body = br#"{"cases":[],"next_cursor":null,"total_count":3}"#;
+
+            let response = parse_response::<ListCasesResponse>(body, true).unwrap();
+
+            assert_eq!(response.total_count, Some(3));
+        }
+
+        #[test]
+        fn response_parsing_reports_malformed_body() {
+            let result = parse_response::<ListCasesResponse>(b"not json", true);
+
+            assert!(matches!(result, Err(ResponseParseError::Malformed(_))));
+        }
+
+        struct FrenchCatalog;
+
+        impl MessageCatalog for FrenchCatalog {
+            fn locale(&self) -> Locale {
+                Locale::Fr
+            }
+
+            fn error_message(&self, error: &AppError) -> String {
+                match error.kind {
+                    ErrorKind::Network => {
+                        "Connexion impossible. Vérifiez votre connexion Internet.".into()
+                    }
+                    _ => EnglishCatalog.error_message(error),
+                }
+            }
+
+            fn status_name(&self, status: CaseStatus) -> String {
+                match status {
+                    CaseStatus::Pending => "En attente".into(),
+                    other => other.display_name().into(),
+                }
+            }
+
+            fn toast(&self, key: ToastKey) -> String {
+                match key {
+                    ToastKey::StatusUpdated => "Statut mis à jour".into(),
+                    other => other.english().into(),
+                }
+            }
+        }
+
+        #[test]
+        fn test_catalog_localizes_network_error() {
+            let error = AppError::new(ErrorKind::Network, "connection reset");
+
+            let localized = UserFacingError::localized(&error, &FrenchCatalog);
+
+            assert_eq!(
+                localized.message,
+                "Connexion impossible. Vérifiez votre connexion Internet."
+            );
+            assert_eq!(localized.error_code, error.code());
+            assert_eq!(
+                UserFacingError::from(&error).message,
+                error.user_facing_message()
+            );
+        }
+
+        #[test]
+        fn test_catalog_falls_back_to_english() {
+            let error = AppError::new(ErrorKind::Timeout, "slow");
+
+            assert_eq!(
+                FrenchCatalog.error_message(&error),
+                error.user_facing_message()
+            );
+            assert_eq!(FrenchCatalog.status_name(CaseStatus::EnRoute), "En Route");
+            assert_eq!(FrenchCatalog.locale().tag(), "fr");
+            assert_eq!(EnglishCatalog.toast(ToastKey::CaseCreated), "Case created");
+        }
+
+        #[test]
+        fn test_model_uses_configured_catalog() {
+            let mut model = Model::default();
+            assert_eq!(model.catalog().locale(), Locale::En);
+
+            model.set_preference(Preference::Locale { locale: Locale::Fr });
+            model.show_catalog_toast(ToastKey::StatusUpdated, ToastKind::Success);
+
+            assert_eq!(model.catalog().locale(), Locale::Fr);
+            assert_eq!(
+                model.active_toast.as_ref().unwrap().message,
+                "Statut mis à jour"
+            );
+        }
+
+        #[test]
+        fn test_every_locale_has_a_built_in_catalog() {
+            for locale in [Locale::Fr, Locale::Es, Locale::De] {
+                assert_ne!(
+                    locale.toast(ToastKey::CaseCreated),
+                    ToastKey::CaseCreated.english()
+                );
+                assert_ne!(
+                    locale.status_name(CaseStatus::Pending),
+                    CaseStatus::Pending.display_name()
+                );
+                assert!(locale.toast(ToastKey::RateLimited).contains("{seconds}"));
+                let showing = locale.toast(ToastKey::ListShowing);
+                assert!(showing.contains("{shown}") && showing.contains("{total}"));
+            }
+            assert_eq!(
+                Locale::En.toast(ToastKey::CaseRemoved),
+                "This case was removed"
+            );
+        }
+
+        #[test]
+        fn test_rate_limited_toast_is_localized() {
+            let mut model = Model::default();
+            model.set_preference(Preference::Locale { locale: Locale::De });
+
+            model.show_rate_limited_toast(30);
+
+            assert_eq!(
+                model.active_toast.as_ref().unwrap().message,
+                "Bitte 30 s warten"
+            );
+        }
+
+        #[test]
+        fn test_list_showing_text_is_localized() {
+            let mut model = Model::default();
+            assert_eq!(model.list_showing_text(2, 5), "Showing 2 of 5");
+
+            model.set_preference(Preference::Locale { locale: Locale::Fr });
+
+            assert_eq!(model.list_showing_text(2, 5), "2 sur 5 affichés");
+        }
+    }
+
+    mod local_case_tests {
+        use super::*;
+
+        #[test]
+        fn test_local_case_new() {
+            let location = LatLon::new(51.5074, -0.1278);
+            let case = LocalCase::new(location, Some("Test description".into()), Some(3));
+
+            assert!(!case.local_id.0.is_empty());
+            assert_eq!(case.location, location);
+            assert_eq!(case.description, Some("Test description".into()));
+            assert_eq!(case.wound_severity, Some(3));
+            assert_eq!(case.status, LocalCaseStatus::PendingUpload);
+            assert!(case.server_id.is_none());
+            assert!(case.photo_data.is_none());
+        }
+
+        #[test]
+        fn test_local_case_mark_synced() {
+            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            case.photo_data = Some(vec![1, 2, 3]);
+
+            case.mark_synced(CaseId::new("server123"));
+
+            assert_eq!(case.status, LocalCaseStatus::Synced);
+            assert_eq!(case.server_id, Some(CaseId::new("server123")));
+            assert!(case.sync_error.is_none());
+            assert!(case.photo_data.is_none());
+        }
+
+        #[test]
+        fn test_local_case_mark_failed() {
+            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+
+            case.mark_failed("Connection timeout");
+
+            assert_eq!(case.status, LocalCaseStatus::Failed);
+            assert_eq!(case.sync_error, Some("Connection timeout".into()));
+            assert_eq!(case.retry_count, 1);
+        }
+
+        #[test]
+        fn test_local_case_permanent_failure() {
+            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+
+            for _ in 0..=MAX_RETRY_ATTEMPTS {
+                case.mark_failed("Error");
+            }
+
+            assert_eq!(case.status, LocalCaseStatus::PermanentlyFailed);
+        }
+
+        #[test]
+        fn test_local_case_description_preview() {
+            let case = LocalCase::new(
+                LatLon::new(0.0, 0.0),
+                Some("This is a very long description that should be truncated".into()),
+                None,
+            );
+
+            let preview = case.description_preview(20);
+            assert_eq!(preview.len(), 20);
+            assert!(preview.ends_with("..."));
+        }
+
+        #[test]
+        fn test_local_case_description_preview_short() {
+            let case = LocalCase::new(LatLon::new(0.0, 0.0), Some("Short".into()), None);
+
+            let preview = case.description_preview(20);
+            assert_eq!(preview, "Short");
+        }
+    }
+
+    mod model_tests {
+        use super::*;
+
+        #[test]
+        fn test_model_default() {
+            let model = Model::default();
+
+            assert_eq!(model.state, AppState::Loading);
+            assert!(model.user_id.is_none());
+            assert!(model.area_center.is_none());
+            assert_eq!(model.area_radius_m, DEFAULT_RADIUS_M);
+            assert_eq!(model.map_zoom, DEFAULT_MAP_ZOOM);
+            assert!(model.cases.is_empty());
+            assert!(model.network_online);
+            assert!(!model.is_refreshing);
+        }
+
+        #[test]
+        fn test_model_is_authenticated() {
+            let mut model = Model::default();
+
+            assert!(!model.is_authenticated());
+
+            model.user_id = Some(UserId::new("user123"));
+            assert!(model.is_authenticated());
+        }
+
+        #[test]
+        fn test_model_show_toast() {
+            let mut model = Model::default();
+
+            model.show_toast("Test message", ToastKind::Success);
+
+            assert!(model.active_toast.is_some());
+            let toast = model.active_toast.as_ref().unwrap();
+            assert_eq!(toast.message, "Test message");
+            assert_eq!(toast.kind, ToastKind::Success);
+        }
+
+        #[test]
+        fn test_model_set_clear_error() {
+            let mut model = Model::default();
+
+            model.set_error(AppError::new(ErrorKind::Network, "Test error"));
+            assert!(model.active_error.is_some());
+
+            model.clear_error();
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_model_optimistic_mutation() {
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user123"));
+
+            let case_id = CaseId::new("case123");
+
+            model.cases.push(ServerCase {
+                id: case_id.clone(),
+                location: LatLon::new(0.0, 0.0),
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                status: CaseStatus::Pending,
+                created_at_ms_utc: UnixTimeMs::now(),
+                updated_at_ms_utc: UnixTimeMs::now(),
+                reporter_id: UserId::new("other"),
+                assigned_rescuer_id: None,
+                photo_url: None,
+                thumbnail_url: None,
+                gemini_diagnosis: None,
+                species_guess: None,
+                distance_meters: None,
+                resolution_outcome: None,
+            });
+
+            let mutation_id = model.store_optimistic_mutation(
+                case_id.clone(),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+
+            assert!(model.pending_mutations.contains_key(&mutation_id));
+
+            if let Some(case) = model.cases.iter_mut().find(|c| c.id == case_id) {
+                case.status = CaseStatus::Claimed;
+                case.assigned_rescuer_id = model.user_id.clone();
+            }
+
+            let rolled_back = model.rollback_mutation(&mutation_id);
+            assert!(rolled_back);
+
+            let case = model.cases.iter().find(|c| c.id == case_id).unwrap();
+            assert_eq!(case.status, CaseStatus::Pending);
+            assert!(case.assigned_rescuer_id.is_none());
+        }
+
+        #[test]
+        fn test_rollback_of_failed_resolve_clears_outcome() {
+            let mut model = Model::default();
+            model.cases.push(server_case("case1", CaseStatus::Arrived));
+
+            let mutation_id = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Arrived,
+                None,
+                CaseStatus::Resolved,
+            );
+            model.cases[0].status = CaseStatus::Resolved;
+            model.cases[0].resolution_outcome = Some(ResolutionOutcome::Rescued);
+
+            assert!(model.rollback_mutation(&mutation_id));
+
+            assert_eq!(model.cases[0].status, CaseStatus::Arrived);
+            assert!(model.cases[0].resolution_outcome.is_none());
+        }
+
+        #[test]
+        fn test_expire_stale_transition_restores_server_status() {
+            let mut model = Model::default();
+            let me = UserId::new("me");
+            model.user_id = Some(me.clone());
+
+            let mut case = server_case("case1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(me.clone());
+            model.cases.push(case);
+
+            let mutation_id = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Claimed,
+                Some(me.clone()),
+                CaseStatus::EnRoute,
+            );
+            model
+                .pending_mutations
+                .get_mut(&mutation_id)
+                .unwrap()
+                .created_at_ms = 1_000;
+            model.cases[0].status = CaseStatus::EnRoute;
+
+            let expired = model.expire_stale_mutations(1_000 + OPTIMISTIC_MUTATION_TIMEOUT_MS + 1);
+
+            assert_eq!(expired, vec![CaseId::new("case1")]);
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert_eq!(model.cases[0].assigned_rescuer_id, Some(me));
+            assert!(model.pending_mutations.is_empty());
+        }
+
+        #[test]
+        fn test_expire_stale_stacked_mutations_restores_oldest_original() {
+            let mut model = Model::default();
+            let me = UserId::new("me");
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let mut claim = PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None);
+            claim.created_at_ms = 1_000;
+            let claim_mutation = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            model
+                .pending_mutations
+                .get_mut(&claim_mutation)
+                .unwrap()
+                .created_at_ms = 1_000;
+            claim.mutation_id = claim_mutation;
+            model.pending_claims.insert(CaseId::new("case1"), claim);
+
+            let transition_mutation = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Claimed,
+                Some(me.clone()),
+                CaseStatus::EnRoute,
+            );
+            model
+                .pending_mutations
+                .get_mut(&transition_mutation)
+                .unwrap()
+                .created_at_ms = 20_000;
+            model.cases[0].status = CaseStatus::EnRoute;
+            model.cases[0].assigned_rescuer_id = Some(me);
+
+            let expired = model.expire_stale_mutations(20_000 + OPTIMISTIC_MUTATION_TIMEOUT_MS + 1);
+
+            assert_eq!(expired.len(), 1);
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+            assert!(model.cases[0].assigned_rescuer_id.is_none());
+            assert!(model.pending_mutations.is_empty());
+            assert!(model.pending_claims.is_empty());
+
+            assert!(model.expire_stale_mutations(u64::MAX).is_empty());
+        }
+
+        #[test]
+        fn test_expire_stale_mutations_respects_configured_timeout() {
+            let mut model = Model::default();
+            model.optimistic_mutation_timeout_ms = 5_000;
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let mutation_id = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            model
+                .pending_mutations
+                .get_mut(&mutation_id)
+                .unwrap()
+                .created_at_ms = 1_000;
+            model.cases[0].status = CaseStatus::Claimed;
+
+            assert!(model.expire_stale_mutations(5_000).is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+
+            assert_eq!(model.expire_stale_mutations(6_001).len(), 1);
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+        }
+
+        #[test]
+        fn test_dirty_store_coalesces_within_debounce_window() {
+            let mut model = Model::default();
+            assert!(!model.should_persist(0));
+
+            model.mark_store_dirty();
+            assert!(model.should_persist(0));
+            model.mark_store_persisted(10_000);
+
+            for _ in 0..5 {
+                model.mark_store_dirty();
+            }
+            assert!(!model.should_persist(10_000 + PERSIST_DEBOUNCE_MS - 1));
+            assert!(model.should_persist(10_000 + PERSIST_DEBOUNCE_MS));
+
+            model.mark_store_persisted(10_000 + PERSIST_DEBOUNCE_MS);
+            assert!(!model.should_persist(u64::MAX));
+        }
+
+        #[test]
+        fn test_check_invariants_default_model() {
+            assert!(Model::default().check_invariants().is_ok());
+        }
+
+        #[test]
+        fn test_check_invariants_radius_out_of_bounds() {
+            let mut model = Model::default();
+            model.area_radius_m = MAX_RADIUS_M + 1;
+
+            let err = model.check_invariants().unwrap_err();
+            assert!(err.contains("area_radius_m"), "{err}");
+        }
+
+        #[test]
+        fn test_check_invariants_synced_local_case_duplicated_in_cases() {
+            let mut model = Model::default();
+            let mut local = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            local.mark_synced(CaseId::new("case1"));
+            model.offline_store.pending_local_cases.push(local);
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let err = model.check_invariants().unwrap_err();
+            assert!(err.contains("case1"), "{err}");
+        }
+
+        #[test]
+        fn test_check_invariants_claim_without_mutation() {
+            let mut model = Model::default();
+            model.pending_claims.insert(
+                CaseId::new("case1"),
+                PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None),
+            );
+
+            let err = model.check_invariants().unwrap_err();
+            assert!(err.contains("missing mutation"), "{err}");
+        }
+
+        #[test]
+        fn test_check_invariants_duplicate_outbox_op_id() {
+            let mut model = Model::default();
+            let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                token: "token".into(),
+            });
+            model.offline_store.outbox.push(entry.clone());
+            model.offline_store.outbox.push(entry);
+
+            let err = model.check_invariants().unwrap_err();
+            assert!(err.contains("duplicate outbox op_id"), "{err}");
+        }
+
+        #[test]
+        fn test_never_seen_recent_case_is_new() {
+            let mut model = Model::default();
+            let case = server_case("case1", CaseStatus::Pending);
+            model.previous_session_ms = Some(case.created_at_ms_utc.0 - 1_000);
+
+            assert!(model.is_case_new("case1", case.created_at_ms_utc.0));
+            assert!(!model.is_case_new("case1", case.created_at_ms_utc.0 - 2_000));
+
+            model.mark_case_viewed("case1");
+            assert!(!model.is_case_new("case1", case.created_at_ms_utc.0));
+        }
+
+        #[test]
+        fn test_nothing_is_new_without_previous_session() {
+            let model = Model::default();
+            let case = server_case("case1", CaseStatus::Pending);
+
+            assert!(model.previous_session_ms.is_none());
+            assert!(!model.is_case_new("case1", case.created_at_ms_utc.0));
+        }
+
+        #[test]
+        fn test_viewed_cases_are_bounded_and_restored() {
+            let mut model = Model::default();
+            for i in 0..=MAX_VIEWED_CASE_IDS {
+                model.mark_case_viewed(&format!("case{i}"));
+            }
+
+            assert_eq!(model.viewed_case_ids.len(), MAX_VIEWED_CASE_IDS);
+            assert!(!model.viewed_case_ids.contains("case0"));
+            assert!(model
+                .viewed_case_ids
+                .contains(&format!("case{MAX_VIEWED_CASE_IDS}")));
+
+            let mut restored = Model::default();
+            restored.offline_store = model.offline_store.clone();
+            restored.offline_store.last_session_ms = Some(42);
+            restored.restore_viewed_cases();
+
+            assert_eq!(restored.viewed_case_ids, model.viewed_case_ids);
+            assert_eq!(restored.previous_session_ms, Some(42));
+        }
+
+        fn local_case_with_photo(bytes: usize) -> LocalCase {
+            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            case.photo_data = Some(vec![0; bytes]);
+            case
+        }
+
+        #[test]
+        fn test_image_memory_guard_rejects_past_cap() {
+            let mut model = Model::default();
+            model.image_memory_cap_bytes = 1_000;
+
+            for _ in 0..3 {
+                assert!(model.reserve_image_memory(300, 0).is_ok());
+                model
+                    .offline_store
+                    .push_local_case(local_case_with_photo(300))
+                    .unwrap();
+            }
+            assert_eq!(model.total_image_memory_bytes(), 900);
+
+            let err = model.reserve_image_memory(300, 0).unwrap_err();
+            assert_eq!(err.kind, ErrorKind::QuotaExceeded);
+            assert!(err.context.contains_key("suggestion"));
+            assert_eq!(model.offline_store.pending_local_cases.len(), 3);
+        }
+
+        #[test]
+        fn test_image_memory_guard_evicts_synced_cases() {
+            let mut model = Model::default();
+            model.image_memory_cap_bytes = 1_000;
+
+            let mut synced = local_case_with_photo(600);
+            synced.status = LocalCaseStatus::Synced;
+            model.offline_store.push_local_case(synced).unwrap();
+            model
+                .offline_store
+                .push_local_case(local_case_with_photo(300))
+                .unwrap();
+
+            assert!(model.reserve_image_memory(300, 0).is_ok());
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+            assert_eq!(model.total_image_memory_bytes(), 300);
+        }
+
+        #[test]
+        fn test_server_case_update_with_same_id_replaces_in_place() {
+            let mut model = Model::default();
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let merged =
+                model.apply_server_case_update("case1", server_case("case1", CaseStatus::Claimed));
+
+            assert!(!merged);
+            assert_eq!(model.cases.len(), 1);
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+        }
+
+        #[test]
+        fn test_server_case_update_with_different_id_upserts_and_removes_stale() {
+            let mut model = Model::default();
+            model.cases.push(server_case("other", CaseStatus::Pending));
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+            model.selected_case_id = Some(CaseId::new("case1"));
+
+            let merged =
+                model.apply_server_case_update("case1", server_case("case2", CaseStatus::Claimed));
+
+            assert!(merged);
+            assert_eq!(model.cases.len(), 2);
+            assert!(model.cases.iter().all(|c| c.id.0 != "case1"));
+            assert_eq!(model.cases[1].id.0, "case2");
+            assert_eq!(model.cases[1].status, CaseStatus::Claimed);
+            assert_eq!(model.selected_case_id, Some(CaseId::new("case2")));
+        }
+
+        #[test]
+        fn test_server_case_update_merges_into_existing_case() {
+            let mut model = Model::default();
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+            model.cases.push(server_case("case2", CaseStatus::Pending));
+
+            assert!(
+                model.apply_server_case_update("case1", server_case("case2", CaseStatus::Claimed))
+            );
+
+            assert_eq!(model.cases.len(), 1);
+            assert_eq!(model.cases[0].id.0, "case2");
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+        }
+
+        #[test]
+        fn test_server_case_update_with_different_id_rekeys_pending_state() {
+            let mut model = Model::default();
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+            let mutation_id = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            let mut claim = PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None);
+            claim.mutation_id = mutation_id.clone();
+            model.pending_claims.insert(CaseId::new("case1"), claim);
+            assert!(!model.apply_push_update("case1", CasePatch::status(CaseStatus::EnRoute)));
+
+            assert!(
+                model.apply_server_case_update("case1", server_case("case2", CaseStatus::Claimed))
+            );
+
+            assert!(!model.has_pending_mutation("case1"));
+            assert!(model.has_pending_mutation("case2"));
+            assert_eq!(
+                model.pending_claims[&CaseId::new("case2")].case_id.0,
+                "case2"
+            );
+            assert_eq!(model.pending_mutations[&mutation_id].case_id.0, "case2");
+            assert!(!model.deferred_push_patches.contains_key("case1"));
+            assert!(model.deferred_push_patches.contains_key("case2"));
+        }
+
+        #[test]
+        fn test_rate_limit_cooldown_elapses() {
+            let mut model = Model::default();
+            model.set_rate_limited("list_cases", 1_000, 5_000);
+
+            assert_eq!(
+                model.rate_limit_remaining_ms("list_cases", 1_000),
+                Some(5_000)
+            );
+            assert_eq!(model.rate_limit_remaining_ms("list_cases", 5_999), Some(1));
+            assert_eq!(model.rate_limit_remaining_ms("list_cases", 6_000), None);
+
+            model.set_rate_limited("list_cases", 2_000, 1_000);
+            assert_eq!(model.rate_limited_until_ms.get("list_cases"), Some(&6_000));
+            assert_eq!(model.rate_limit_remaining_ms("create_case", 1_000), None);
+        }
+
+        fn case_with_severity(id: &str, severity: Option<u8>, distance_m: f64) -> ServerCase {
+            let mut case = server_case(id, CaseStatus::Pending);
+            case.wound_severity = severity;
+            case.distance_meters = Some(distance_m);
+            case
+        }
+
+        #[test]
+        fn test_severity_counts_histogram() {
+            let mut model = Model::default();
+            model.area_radius_m = 5_000;
+            model.cases = vec![
+                case_with_severity("a", Some(5), 100.0),
+                case_with_severity("b", Some(5), 200.0),
+                case_with_severity("c", Some(3), 300.0),
+                case_with_severity("d", Some(1), 400.0),
+                case_with_severity("e", None, 500.0),
+                case_with_severity("f", Some(9), 600.0),
+            ];
+
+            assert_eq!(model.severity_counts(), [2, 1, 0, 1, 0, 2]);
+        }
+
+        #[test]
+        fn test_severity_counts_excludes_out_of_radius() {
+            let mut model = Model::default();
+            model.area_radius_m = 1_000;
+            model.cases = vec![
+                case_with_severity("near", Some(4), 999.0),
+                case_with_severity("far", Some(4), 1_001.0),
+                case_with_severity("unknown", Some(4), f64::NAN),
+            ];
+
+            assert_eq!(model.severity_counts(), [0, 0, 0, 0, 1, 0]);
+
+            model.area_center = Some(ValidatedCoordinate::new(0.0, 0.0).unwrap());
+            assert_eq!(model.severity_counts(), [0, 0, 0, 0, 2, 0]);
+        }
+
+        #[test]
+        fn test_next_auto_refresh_mid_interval() {
+            let mut model = Model::default();
+            model.auto_refresh_interval_ms = Some(60_000);
+            model.offline_store.last_cases_refresh_ms = Some(100_000);
+
+            assert_eq!(model.next_auto_refresh_in_ms(148_000), Some(12_000));
+        }
+
+        #[test]
+        fn test_next_auto_refresh_elapsed_is_zero() {
+            let mut model = Model::default();
+            model.auto_refresh_interval_ms = Some(60_000);
+            model.offline_store.last_cases_refresh_ms = Some(100_000);
+
+            assert_eq!(model.next_auto_refresh_in_ms(160_000), Some(0));
+            assert_eq!(model.next_auto_refresh_in_ms(500_000), Some(0));
+
+            model.offline_store.last_cases_refresh_ms = None;
+            assert_eq!(model.next_auto_refresh_in_ms(100_000), Some(0));
+        }
+
+        #[test]
+        fn test_next_auto_refresh_disabled() {
+            let mut model = Model::default();
+            model.offline_store.last_cases_refresh_ms = Some(100_000);
+
+            assert_eq!(model.next_auto_refresh_in_ms(148_000), None);
+
+            model.auto_refresh_interval_ms = Some(0);
+            assert_eq!(model.next_auto_refresh_in_ms(148_000), None);
+        }
+
+        #[test]
+        fn test_should_prompt_push_for_never_asked_user() {
+            let model = Model::default();
+            assert!(model.should_prompt_push(1_000));
+        }
+
+        #[test]
+        fn test_should_not_prompt_push_when_granted() {
+            let mut model = Model::default();
+            model.push_permission_granted = true;
+            assert!(!model.should_prompt_push(1_000));
+        }
+
+        #[test]
+        fn test_should_not_reprompt_recently_denied_user() {
+            let mut model = Model::default();
+            model.offline_store.push_denied_at_ms = Some(1_000);
+            assert!(!model.should_prompt_push(1_000));
+            assert!(!model.should_prompt_push(1_000 + PUSH_PROMPT_COOLDOWN_MS - 1));
+            assert!(model.should_prompt_push(1_000 + PUSH_PROMPT_COOLDOWN_MS));
+        }
+
+        #[test]
+        fn test_rollback_all_mutations_restores_server_state() {
+            let mut model = Model::default();
+            let me = UserId::new("me");
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+            model.cases.push(server_case("case2", CaseStatus::Claimed));
+
+            let claim_mutation = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            let mut claim = PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None);
+            claim.mutation_id = claim_mutation;
+            model.pending_claims.insert(CaseId::new("case1"), claim);
+            model.cases[0].status = CaseStatus::Claimed;
+            model.cases[0].assigned_rescuer_id = Some(me.clone());
+
+            model.store_optimistic_mutation(
+                CaseId::new("case2"),
+                CaseStatus::Claimed,
+                Some(me.clone()),
+                CaseStatus::EnRoute,
+            );
+            model.cases[1].status = CaseStatus::EnRoute;
+            model.cases[1].assigned_rescuer_id = Some(me.clone());
+
+            let rolled_back = model.rollback_all_mutations();
+
+            assert_eq!(
+                rolled_back,
+                vec![CaseId::new("case1"), CaseId::new("case2")]
+            );
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+            assert!(model.cases[0].assigned_rescuer_id.is_none());
+            assert_eq!(model.cases[1].status, CaseStatus::Claimed);
+            assert_eq!(model.cases[1].assigned_rescuer_id, Some(me));
+            assert!(model.pending_mutations.is_empty());
+            assert!(model.pending_claims.is_empty());
+        }
+
+        #[test]
+        fn sustained_move_converges_through_smoothing() {
+            let mut model = Model::default();
+            let start = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let target = ValidatedCoordinate::new(51.502, -0.12).unwrap();
+            model.area_center = model.accept_location_fix(start, 1_000);
+
+            for i in 0..30 {
+                let center = model.accept_location_fix(target, 2_000 + i * 1_000);
+                model.area_center = center;
+                assert!(center.is_some());
+            }
+
+            assert!(model.area_center.unwrap().distance_to(target) < 1.0);
+        }
+
+        #[test]
+        fn implausible_jump_is_rejected_until_it_persists() {
+            let mut model = Model::default();
+            let home = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let far = ValidatedCoordinate::new(52.5, -0.12).unwrap();
+            model.area_center = model.accept_location_fix(home, 1_000);
+
+            for i in 1..LOCATION_JUMP_CONFIRMATIONS {
+                assert_eq!(
+                    model.accept_location_fix(far, 1_000 + u64::from(i) * 1_000),
+                    None
+                );
+            }
+
+            let accepted = model.accept_location_fix(far, 10_000);
+
+            assert_eq!(accepted, Some(far));
+            assert!(model.location_jump_candidate.is_none());
+        }
+
+        #[test]
+        fn single_outlier_does_not_count_towards_later_jump() {
+            let mut model = Model::default();
+            let home = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let far = ValidatedCoordinate::new(52.5, -0.12).unwrap();
+            model.area_center = model.accept_location_fix(home, 1_000);
+
+            assert_eq!(model.accept_location_fix(far, 2_000), None);
+            assert!(model.accept_location_fix(home, 3_000).is_some());
+
+            assert_eq!(model.location_jump_count, 0);
+            assert_eq!(model.accept_location_fix(far, 4_000), None);
+        }
+
+        #[test]
+        fn status_counts_tallies_each_status() {
+            let mut model = Model::default();
+            model.cases = vec![
+                server_case("a", CaseStatus::Pending),
+                server_case("b", CaseStatus::Pending),
+                server_case("c", CaseStatus::Claimed),
+                server_case("d", CaseStatus::Arrived),
+                server_case("e", CaseStatus::Resolved),
+                server_case("f", CaseStatus::Cancelled),
+            ];
+
+            let counts = model.status_counts(true);
+
+            assert_eq!(counts.get(&CaseStatus::Pending), Some(&2));
+            assert_eq!(counts.get(&CaseStatus::Claimed), Some(&1));
+            assert_eq!(counts.get(&CaseStatus::Arrived), Some(&1));
+            assert_eq!(counts.get(&CaseStatus::Resolved), Some(&1));
+            assert_eq!(counts.get(&CaseStatus::Cancelled), Some(&1));
+            assert_eq!(counts.get(&CaseStatus::EnRoute), None);
+        }
+
+        #[test]
+        fn status_counts_can_exclude_terminal_cases() {
+            let mut model = Model::default();
+            model.cases = vec![
+                server_case("a", CaseStatus::Claimed),
+                server_case("b", CaseStatus::Resolved),
+                server_case("c", CaseStatus::Expired),
+            ];
+
+            let counts = model.status_counts(false);
+
+            assert_eq!(counts.len(), 1);
+            assert_eq!(counts.get(&CaseStatus::Claimed), Some(&1));
+        }
+
+        #[test]
+        fn status_counts_include_pending_local_cases() {
+            let mut model = Model::default();
+            model.cases = vec![server_case("a", CaseStatus::Pending)];
+            let mut synced = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            synced.status = LocalCaseStatus::Synced;
+            model.offline_store.pending_local_cases = vec![
+                LocalCase::new(LatLon::new(0.0, 0.0), None, None),
+                LocalCase::new(LatLon::new(0.0, 0.0), None, None),
+                synced,
+            ];
+
+            let counts = model.status_counts(false);
+
+            assert_eq!(counts.get(&CaseStatus::Pending), Some(&3));
+        }
+
+        fn feed_model() -> Model {
+            let mut model = Model::default();
+            model.state = AppState::Ready;
+            model.area_center = Some(ValidatedCoordinate::new(51.5, -0.12).unwrap());
+            model
+        }
+
+        #[test]
+        fn empty_feed_reason_reports_filter_hiding_all_cases() {
+            let mut model = feed_model();
+            model.cases = vec![server_case("a", CaseStatus::Pending)];
+            model.status_filter = Some(CaseStatus::Claimed);
+
+            assert_eq!(
+                model.empty_feed_reason(),
+                Some(EmptyFeedReason::FilteredOut)
+            );
+
+            model.status_filter = None;
+            assert_eq!(model.empty_feed_reason(), None);
+        }
+
+        #[test]
+        fn empty_feed_reason_reports_offline_without_cache() {
+            let mut model = feed_model();
+            model.network_online = false;
+
+            assert_eq!(
+                model.empty_feed_reason(),
+                Some(EmptyFeedReason::OfflineNoCache)
+            );
+        }
+
+        #[test]
+        fn empty_feed_reason_reports_genuinely_empty_area() {
+            let mut model = feed_model();
+            model.offline_store.update_last_refresh();
+
+            assert_eq!(
+                model.empty_feed_reason(),
+                Some(EmptyFeedReason::NoCasesNearby)
+            );
+
+            model.network_online = false;
+            assert_eq!(
+                model.empty_feed_reason(),
+                Some(EmptyFeedReason::NoCasesNearby)
+            );
+        }
+
+        #[test]
+        fn empty_feed_reason_reports_missing_location() {
+            let mut model = feed_model();
+            model.area_center = None;
+
+            assert_eq!(
+                model.empty_feed_reason(),
+                Some(EmptyFeedReason::LocationMissing)
+            );
+        }
+
+        #[test]
+        fn pending_local_cases_count_as_visible_pending() {
+            let mut model = feed_model();
+            model.offline_store.pending_local_cases =
+                vec![LocalCase::new(LatLon::new(51.5, -0.12), None, None)];
+            model.status_filter = Some(CaseStatus::Pending);
+
+            assert_eq!(model.empty_feed_reason(), None);
+        }
+
+        fn recommendation_model() -> Model {
+            let mut model = Model::default();
+            model.state = AppState::Ready;
+            model.user_id = Some(UserId::new("rescuer"));
+            model.area_center = Some(ValidatedCoordinate::new(0.0, 0.0).unwrap());
+            model.cases = vec![server_case("nearby-open", CaseStatus::Pending)];
+            model
+        }
+
+        #[test]
+        fn test_arrived_owned_case_outranks_nearby_claimable() {
+            let mut model = recommendation_model();
+            assert_eq!(
+                model.recommended_action(),
+                Some(RecommendedAction::ClaimNearest {
+                    case_id: "nearby-open".into()
+                })
+            );
+
+            let mut en_route = server_case("owned-en-route", CaseStatus::EnRoute);
+            en_route.assigned_rescuer_id = Some(UserId::new("rescuer"));
+            let mut arrived = server_case("owned-arrived", CaseStatus::Arrived);
+            arrived.assigned_rescuer_id = Some(UserId::new("rescuer"));
+            model.cases.extend([en_route, arrived]);
+
+            assert_eq!(
+                model.recommended_action(),
+                Some(RecommendedAction::ResumeCase {
+                    case_id: "owned-arrived".into(),
+                    status: CaseStatus::Arrived,
+                })
+            );
+        }
+
+        #[test]
+        fn test_failed_syncs_surface_before_claiming() {
+            let mut model = recommendation_model();
+            let mut failed = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+            failed.mark_failed("network");
+            model.offline_store.pending_local_cases.push(failed);
+
+            assert_eq!(
+                model.recommended_action(),
+                Some(RecommendedAction::RetryFailedSyncs { count: 1 })
+            );
+        }
+
+        #[test]
+        fn test_onboarding_recommends_completing_onboarding() {
+            let mut model = recommendation_model();
+            model.state = AppState::PinDrop;
+
+            assert_eq!(
+                model.recommended_action(),
+                Some(RecommendedAction::CompleteOnboarding)
+            );
+        }
+
+        #[test]
+        fn test_circuit_opens_after_failure_burst() {
+            let mut breaker = CircuitBreaker::default();
+
+            for _ in 0..CIRCUIT_BREAKER_MIN_SAMPLES - 1 {
+                assert!(!breaker.record_failure(1_000));
+            }
+            assert_eq!(breaker.state(1_000), CircuitState::Closed);
+
+            assert!(breaker.record_failure(1_000));
+            assert_eq!(breaker.state(1_000), CircuitState::Open);
+            assert!(!breaker.allow_request(1_000));
+        }
+
+        #[test]
+        fn test_circuit_stays_closed_when_failures_are_rare() {
+            let mut breaker = CircuitBreaker::default();
+
+            for i in 0..20 {
+                if i % 4 == 0 {
+                    breaker.record_failure(1_000);
+                } else {
+                    breaker.record_success();
+                }
+            }
+
+            assert_eq!(breaker.state(1_000), CircuitState::Closed);
+        }
+
+        #[test]
+        fn test_half_open_admits_single_probe() {
+            let mut breaker = CircuitBreaker::default().with_cooldown_ms(5_000);
+            for _ in 0..CIRCUIT_BREAKER_MIN_SAMPLES {
+                breaker.record_failure(0);
+            }
+
+            assert_eq!(breaker.state(5_000), CircuitState::HalfOpen);
+            assert!(breaker.allow_request(5_000));
+            assert!(!breaker.allow_request(5_000));
+
+            breaker.record_failure(5_000);
+            assert_eq!(breaker.state(6_000), CircuitState::Open);
+
+            assert!(breaker.allow_request(10_000));
+            assert!(breaker.record_success());
+            assert_eq!(breaker.state(10_000), CircuitState::Closed);
+        }
+
+        #[test]
+        fn test_capture_aspect_ratio_parsing() {
+            let mut config = CaptureConfig::default();
+            let ratio = config.aspect_ratio_value().unwrap();
+            assert!((ratio - 4.0 / 3.0).abs() < f32::EPSILON);
+
+            config.aspect_ratio = "16 : 9".into();
+            assert!((config.aspect_ratio_value().unwrap() - 16.0 / 9.0).abs() < f32::EPSILON);
+
+            for bad in ["", "4", "4:0", "a:b", "-4:3"] {
+                config.aspect_ratio = bad.into();
+                assert_eq!(config.aspect_ratio_value(), None, "{bad}");
+            }
+        }
+
+        #[test]
+        fn test_server_case_merge_keeps_fields_missing_from_response() {
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.distance_meters = Some(250.0);
+            case.description = Some("Limping dog".into());
+            case.photo_url = Some("https://cdn.example/photo.jpg".into());
+
+            let mut response = server_case("case1", CaseStatus::Claimed);
+            response.assigned_rescuer_id = Some(UserId::new("user1"));
+            response.description = Some("Limping dog near the bakery".into());
+            case.merge_from(response);
+
+            assert_eq!(case.status, CaseStatus::Claimed);
+            assert_eq!(case.assigned_rescuer_id, Some(UserId::new("user1")));
+            assert_eq!(
+                case.description.as_deref(),
+                Some("Limping dog near the bakery")
+            );
+            assert_eq!(case.distance_meters, Some(250.0));
+            assert_eq!(
+                case.photo_url.as_deref(),
+                Some("https://cdn.example/photo.jpg")
+            );
+        }
+
+        #[test]
+        fn test_server_case_merge_clears_assignment_from_response() {
+            let mut case = server_case("case1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(UserId::new("user1"));
+
+            case.merge_from(server_case("case1", CaseStatus::Pending));
+
+            assert_eq!(case.status, CaseStatus::Pending);
+            assert_eq!(case.assigned_rescuer_id, None);
+        }
+
+        #[test]
+        fn test_expire_stale_mutations_keeps_fresh_mutations() {
+            let mut model = Model::default();
+            let me = UserId::new("me");
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let claim_mutation = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            model
+                .pending_mutations
+                .get_mut(&claim_mutation)
+                .unwrap()
+                .created_at_ms = 1_000;
+            let transition_mutation = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Claimed,
+                Some(me.clone()),
+                CaseStatus::EnRoute,
+            );
+            model
+                .pending_mutations
+                .get_mut(&transition_mutation)
+                .unwrap()
+                .created_at_ms = 20_000;
+            model.cases[0].status = CaseStatus::EnRoute;
+            model.cases[0].assigned_rescuer_id = Some(me.clone());
+
+            let expired = model.expire_stale_mutations(1_000 + OPTIMISTIC_MUTATION_TIMEOUT_MS + 1);
+
+            assert_eq!(expired, vec![CaseId::new("case1")]);
+            assert!(!model.pending_mutations.contains_key(&claim_mutation));
+            let fresh = &model.pending_mutations[&transition_mutation];
+            assert_eq!(fresh.original_status, CaseStatus::Pending);
+            assert!(fresh.original_assignee.is_none());
+            assert_eq!(model.cases[0].status, CaseStatus::EnRoute);
+            assert_eq!(model.cases[0].assigned_rescuer_id, Some(me));
+
+            model.expire_stale_mutations(20_000 + OPTIMISTIC_MUTATION_TIMEOUT_MS + 1);
+            assert!(model.pending_mutations.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+            assert!(model.cases[0].assigned_rescuer_id.is_none());
+        }
+
+        #[test]
+        fn test_resolve_redirect_location() {
+            let base = Some("https://uploads.example/bucket/original?sig=1");
+            assert_eq!(
+                resolve_redirect_location(base, "https://other.example/photo").as_deref(),
+                Some("https://other.example/photo")
+            );
+            assert_eq!(
+                resolve_redirect_location(base, "/moved").as_deref(),
+                Some("https://uploads.example/moved")
+            );
+            assert_eq!(
+                resolve_redirect_location(base, "retry").as_deref(),
+                Some("https://uploads.example/bucket/retry")
+            );
+            assert_eq!(resolve_redirect_location(None, "/moved"), None);
+        }
+    }
+
+    mod zoom_tests {
+        use super::*;
+
+        #[test]
+        fn test_zoom_for_radius() {
+            assert_eq!(zoom_for_radius(500), 16.0);
+            assert_eq!(zoom_for_radius(1000), 16.0);
+            assert_eq!(zoom_for_radius(2000), 15.0);
+            assert_eq!(zoom_for_radius(5000), 14.0);
+            assert_eq!(zoom_for_radius(10000), 13.0);
+            assert_eq!(zoom_for_radius(20000), 12.0);
+            assert_eq!(zoom_for_radius(50000), 11.0);
+            assert_eq!(zoom_for_radius(100000), FALLBACK_ZOOM);
+        }
+
+        #[test]
+        fn test_fit_zoom_nearby_pair() {
+            let a = ValidatedCoordinate::new(51.5000, -0.1200).unwrap();
+            let b = ValidatedCoordinate::new(51.5050, -0.1200).unwrap();
+
+            let (center, zoom) = fit_zoom(a, b);
+
+            assert!((center.lat() - 51.5025).abs() < 1e-6);
+            assert!((center.lon() - -0.1200).abs() < 1e-6);
+            assert_eq!(zoom, 16.0);
+        }
+
+        #[test]
+        fn test_fit_zoom_far_pair() {
+            let london = ValidatedCoordinate::new(51.5074, -0.1278).unwrap();
+            let paris = ValidatedCoordinate::new(48.8566, 2.3522).unwrap();
+
+            let (center, zoom) = fit_zoom(london, paris);
+
+            assert!(center.lat() > 48.8566 && center.lat() < 51.5074);
+            assert!(center.lon() > -0.1278 && center.lon() < 2.3522);
+            let expected = 11.0 - (haversine_distance(london, paris) / 50_000.0).log2();
+            assert!((zoom - expected).abs() < 1e-9);
+            assert!(zoom < zoom_for_distance(50_000.0));
+            assert!((MIN_ZOOM..=MAX_ZOOM).contains(&zoom));
+        }
+
+        #[test]
+        fn test_fit_zoom_identical_points() {
+            let p = ValidatedCoordinate::new(40.7128, -74.0060).unwrap();
+
+            let (center, zoom) = fit_zoom(p, p);
+
+            assert!((center.lat() - p.lat()).abs() < 1e-9);
+            assert!((center.lon() - p.lon()).abs() < 1e-9);
+            assert_eq!(zoom, zoom_for_radius(0));
+        }
+
+        #[test]
+        fn test_fit_zoom_across_antimeridian() {
+            let a = ValidatedCoordinate::new(0.0, 179.99).unwrap();
+            let b = ValidatedCoordinate::new(0.0, -179.99).unwrap();
+
+            let (center, _) = fit_zoom(a, b);
+
+            assert!(center.lon().abs() > 179.9);
+        }
+
+        #[test]
+        fn test_zoom_for_distance_boundaries() {
+            assert_eq!(zoom_for_distance(0.0), 16.0);
+            assert_eq!(zoom_for_distance(1000.0), 16.0);
+            assert_eq!(zoom_for_distance(1000.5), 15.0);
+            assert_eq!(zoom_for_distance(50_000.0), 11.0);
+            assert!(zoom_for_distance(50_001.0) < 11.0);
+            assert_eq!(zoom_for_distance(100_000.0), 10.0);
+            assert_eq!(zoom_for_distance(200_000.0), 9.0);
+            assert_eq!(zoom_for_distance(20_000_000.0), MIN_ZOOM);
+            assert_eq!(zoom_for_distance(-5.0), 16.0);
+            assert_eq!(zoom_for_distance(f64::NAN), FALLBACK_ZOOM);
+            assert_eq!(zoom_for_distance(f64::INFINITY), FALLBACK_ZOOM);
+        }
+    }
+
+    mod event_tests {
+        use super::*;
+
+        #[test]
+        fn test_event_default() {
+            let event = Event::default();
+            assert!(matches!(event, Event::Noop));
+        }
+
+        #[test]
+        fn test_event_name() {
+            assert_eq!(Event::Noop.name(), "noop");
+            assert_eq!(Event::AppStarted.name(), "app_started");
+            assert_eq!(Event::LoginRequested.name(), "login_requested");
+            assert_eq!(Event::RefreshRequested.name(), "refresh_requested");
+        }
+
+        #[test]
+        fn test_event_is_user_initiated() {
+            assert!(!Event::Noop.is_user_initiated());
+            assert!(!Event::AppStarted.is_user_initiated());
+            assert!(Event::LoginRequested.is_user_initiated());
+            assert!(Event::RefreshRequested.is_user_initiated());
+            assert!(Event::CapturePhotoRequested.is_user_initiated());
+            assert!(Event::ClaimRequested {
+                case_id: "test".into()
+            }
+            .is_user_initiated());
+        }
+    }
+
+    mod toast_tests {
+        use super::*;
+
+        #[test]
+        fn test_toast_message_new() {
+            let toast = ToastMessage::new("Test message", ToastKind::Info);
+
+            assert_eq!(toast.message, "Test message");
+            assert_eq!(toast.kind, ToastKind::Info);
+            assert_eq!(toast.duration_ms, 3000);
+        }
+
+        #[test]
+        fn test_toast_kind_duration() {
+            assert_eq!(ToastKind::Info.default_duration_ms(), 3000);
+            assert_eq!(ToastKind::Success.default_duration_ms(), 2000);
+            assert_eq!(ToastKind::Warning.default_duration_ms(), 4000);
+            assert_eq!(ToastKind::Error.default_duration_ms(), 5000);
+        }
+
+        #[test]
+        fn test_toast_is_expired() {
+            let toast = ToastMessage::new("Test", ToastKind::Info);
+            let created = toast.created_at_ms;
+
+            assert!(!toast.is_expired(created));
+            assert!(!toast.is_expired(created + 2999));
+            assert!(toast.is_expired(created + 3001));
+        }
+    }
+
+    mod permission_state_tests {
+        use super::*;
+
+        #[test]
+        fn test_permission_state() {
+            assert!(PermissionState::Unknown.is_unknown());
+            assert!(!PermissionState::Unknown.is_granted());
+            assert!(!PermissionState::Unknown.is_denied());
+
+            assert!(PermissionState::Granted.is_granted());
+            assert!(!PermissionState::Granted.is_unknown());
+            assert!(!PermissionState::Granted.is_denied());
+
+            assert!(PermissionState::Denied.is_denied());
+            assert!(!PermissionState::Denied.is_granted());
+            assert!(!PermissionState::Denied.is_unknown());
+
+            assert!(PermissionState::Restricted.is_denied());
+        }
+    }
+
+    mod feed_view_tests {
+        use super::*;
+
+        #[test]
+        fn test_feed_view_toggle() {
+            assert_eq!(FeedView::Map.toggle(), FeedView::List);
+            assert_eq!(FeedView::List.toggle(), FeedView::Map);
+        }
+
+        #[test]
+        fn test_feed_view_as_str() {
+            assert_eq!(FeedView::Map.as_str(), "map");
+            assert_eq!(FeedView::List.as_str(), "list");
+        }
+    }
+
+    mod id_tests {
+        use super::*;
+
+        #[test]
+        fn test_user_id() {
+            let id = UserId::new("user123");
+            assert_eq!(id.as_str(), "user123");
+            assert_eq!(id.to_string(), "user123");
+        }
+
+        #[test]
+        fn test_case_id_generate() {
+            let id1 = CaseId::generate();
+            let id2 = CaseId::generate();
+            assert_ne!(id1.0, id2.0);
+            assert!(!id1.0.is_empty());
+        }
+
+        #[test]
+        fn test_local_op_id_generate() {
+            let id1 = LocalOpId::generate();
+            let id2 = LocalOpId::generate();
+            assert_ne!(id1.0, id2.0);
+        }
+
+        #[test]
+        fn test_idempotency_key_generate() {
+            let key1 = IdempotencyKey::generate();
+            let key2 = IdempotencyKey::generate();
+            assert_ne!(key1.0, key2.0);
+        }
+
+        #[test]
+        fn test_op_id_generate() {
+            let id1 = OpId::generate();
+            let id2 = OpId::generate();
+            assert_ne!(id1.0, id2.0);
+        }
+    }
+
+    mod unix_time_tests {
+        use super::*;
+
+        #[test]
+        fn test_unix_time_now() {
+            let time1 = UnixTimeMs::now();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            let time2 = UnixTimeMs::now();
+
+            assert!(time2.0 > time1.0);
+        }
+
+        #[test]
+        fn test_unix_time_operations() {
+            let time = UnixTimeMs(1000);
+
+            assert_eq!(time.as_millis(), 1000);
+            assert_eq!(time.as_secs(), 1);
+            assert_eq!(time.add_millis(500).0, 1500);
+        }
+
+        #[test]
+        fn test_unix_time_comparison() {
+            let earlier = UnixTimeMs(1000);
+            let later = UnixTimeMs(2000);
+
+            assert!(earlier.is_before(later));
+            assert!(later.is_after(earlier));
+            assert_eq!(later.elapsed_since(earlier), 1000);
+        }
+    }
+
+    mod lat_lon_tests {
+        use super::*;
+
+        #[test]
+        fn test_lat_lon_new() {
+            let loc = LatLon::new(51.5074, -0.1278);
+            assert_eq!(loc.lat, 51.5074);
+            assert_eq!(loc.lon, -0.1278);
+        }
+
+        #[test]
+        fn test_lat_lon_validate() {
+            let valid = LatLon::new(51.5074, -0.1278);
+            assert!(valid.validate().is_ok());
+
+            let invalid = LatLon::new(91.0, 0.0);
+            assert!(invalid.validate().is_err());
+        }
+
+        #[test]
+        fn test_lat_lon_conversion() {
+            let coord = ValidatedCoordinate::new(51.5074, -0.1278).unwrap();
+            let lat_lon: LatLon = coord.into();
+
+            assert_eq!(lat_lon.lat, 51.5074);
+            assert_eq!(lat_lon.lon, -0.1278);
+        }
+    }
+
+    mod app_tests {
+        use super::*;
+        use crate::capabilities::Effect as AppEffect;
+        use crux_core::testing::AppTester;
+
+        fn persist_count(effects: &[AppEffect]) -> usize {
+            effects
+                .iter()
+                .filter(|e| matches!(e, AppEffect::Crypto(_)))
+                .count()
+        }
+
+        const LONDON: (f64, f64) = (51.5, -0.12);
+        const NULL_ISLAND: (f64, f64) = (0.0, 0.0);
+
+        /// Foreground `Ready` model signed in as `user_id`, centred on `center`.
+        fn ready_model_at(
+            center: (f64, f64),
+            user_id: Option<&str>,
+            cases: Vec<ServerCase>,
+        ) -> Model {
+            let mut model = Model::default();
+            model.state = AppState::Ready;
+            model.user_id = user_id.map(UserId::new);
+            model.area_center = Some(ValidatedCoordinate::new(center.0, center.1).unwrap());
+            model.cases = cases;
+            model
+        }
+
+        fn ready_model(cases: Vec<ServerCase>) -> Model {
+            ready_model_at(LONDON, Some("user1"), cases)
+        }
+
+        fn cases_at_distances(distances: &[f64]) -> Vec<ServerCase> {
+            distances
+                .iter()
+                .enumerate()
+                .map(|(i, &distance)| case_at_distance(&format!("case{i}"), distance))
+                .collect()
+        }
+
+        #[test]
+        fn test_dirty_updates_within_window_persist_once() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user1"));
+            model.network_online = false;
+
+            let mut persisted = 0;
+            for _ in 0..3 {
+                let update = app.update(Event::RetryFailedOperations, &mut model);
+                persisted += persist_count(&update.effects);
+            }
+
+            assert_eq!(persisted, 1);
+            assert!(model.store_dirty);
+        }
+
+        #[test]
+        fn test_backgrounding_forces_immediate_persist() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user1"));
+            model.mark_store_persisted(get_current_time_ms());
+
+            let update = app.update(Event::AppBackgrounded, &mut model);
+
+            assert_eq!(persist_count(&update.effects), 1);
+            assert!(!model.store_dirty);
+        }
+
+        #[test]
+        fn test_persist_without_user_keeps_store_dirty() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.mark_store_dirty();
+
+            let update = app.update(Event::AppBackgrounded, &mut model);
+
+            assert_eq!(persist_count(&update.effects), 0);
+            assert!(model.store_dirty);
+            assert!(model.last_persisted_at_ms.is_none());
+        }
+
+        #[test]
+        fn test_offline_create_sequence_preserves_invariants() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let events = vec![
+                Event::LoginCompleted {
+                    jwt: "aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".into(),
+                    user_id: "user1".into(),
+                },
+                Event::LocationPinDropped {
+                    lat: 51.5,
+                    lng: -0.12,
+                },
+                Event::RadiusSelected { meters: 2000 },
+                Event::NetworkStatusChanged { online: false },
+                Event::CreateCaseRequested(CreateCasePayload {
+                    location: (51.5, -0.12),
+                    description: Some("Injured dog".into()),
+                    landmark_hint: None,
+                    wound_severity: Some(3),
+                }),
+                Event::RetryFailedOperations,
+                Event::TimerTick,
+            ];
+
+            for event in events {
+                let _ = app.update(event, &mut model);
+                model.check_invariants().unwrap();
+            }
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+        }
+
+        #[test]
+        fn test_login_with_malformed_token_fails() {
+            let app = AppTester::<App, AppEffect>::default();
+
+            for jwt in ["", "   ", "single-segment"] {
+                let mut model = Model::default();
+                model.state = AppState::Authenticating;
+
+                let _ = app.update(
+                    Event::LoginCompleted {
+                        jwt: jwt.into(),
+                        user_id: "user1".into(),
+                    },
+                    &mut model,
+                );
+
+                assert_eq!(model.state, AppState::Unauthenticated);
+                assert!(model.jwt_token.is_none());
+                assert!(model.user_id.is_none());
+                assert_eq!(
+                    model.active_error.as_ref().map(|e| e.kind),
+                    Some(ErrorKind::Authentication)
+                );
+            }
+        }
+
+        #[test]
+        fn test_login_with_well_formed_token_succeeds() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(
+                Event::LoginCompleted {
+                    jwt: "aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".into(),
+                    user_id: "user1".into(),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.state, AppState::OnboardingLocation);
+            assert!(model.jwt_token.is_some());
+            assert!(model.active_error.is_none());
+        }
+
+        fn has_effect(effects: &[AppEffect], pred: fn(&AppEffect) -> bool) -> bool {
+            effects.iter().any(pred)
+        }
+
+        #[test]
+        fn test_startup_with_known_user_restores_state() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user1"));
+
+            let update = app.update(Event::AppStarted, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+            assert!(model.restore_in_flight);
+        }
+
+        #[test]
+        fn test_startup_without_user_skips_restore() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let update = app.update(Event::AppStarted, &mut model);
+
+            let kv_reads = update
+                .effects
+                .iter()
+                .filter(|e| matches!(e, AppEffect::Kv(_)))
+                .count();
+            assert_eq!(
+                kv_reads, 2,
+                "only the preferences and store key reads are issued"
+            );
+            assert!(!model.restore_in_flight);
+        }
+
+        #[test]
+        fn test_refresh_waits_for_restore_to_finish() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.restore_in_flight = true;
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.refresh_after_restore);
+
+            let update = app.update(
+                Event::StateDecryptionFailed {
+                    error: AppError::new(ErrorKind::Crypto, "bad key"),
+                },
+                &mut model,
+            );
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.restore_in_flight);
+            assert!(!model.refresh_after_restore);
+        }
+
+        fn restoring_model() -> Model {
+            let mut model = ready_model(vec![]);
+            model.restore_in_flight = true;
+            model.refresh_after_restore = true;
+            model.is_refreshing = true;
+            model
+        }
+
+        #[test]
+        fn test_missing_stored_state_finishes_restore() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = restoring_model();
+
+            let update = app.update(
+                Event::RestoreStateResponse {
+                    result: Box::new(Err(crate::capabilities::KvError::NotFound)),
+                },
+                &mut model,
+            );
+
+            assert!(model.store_restored);
+            assert!(!model.restore_in_flight);
+            assert!(!model.refresh_after_restore);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_stored_state_load_failure_finishes_restore() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = restoring_model();
+
+            let update = app.update(
+                Event::RestoreStateResponse {
+                    result: Box::new(Err(crate::capabilities::KvError::QuotaExceeded {
+                        used: 1,
+                        limit: 1,
+                    })),
+                },
+                &mut model,
+            );
+
+            assert!(!model.store_restored);
+            assert!(!model.restore_in_flight);
+            assert!(!model.refresh_after_restore);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_selecting_case_marks_it_viewed() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let _ = app.update(
+                Event::CaseSelected {
+                    case_id: "case1".into(),
+                },
+                &mut model,
+            );
+
+            assert!(model.viewed_case_ids.contains("case1"));
+            assert_eq!(
+                model.offline_store.viewed_case_ids,
+                vec!["case1".to_string()]
+            );
+            assert!(!model.is_case_new("case1", model.cases[0].created_at_ms_utc.0));
+        }
+
+        #[test]
+        fn test_rerun_detection_without_staged_photo_is_noop() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let update = app.update(Event::RerunDetection, &mut model);
+
+            assert!(model.staged_photo.is_none());
+            assert!(model.active_error.is_none());
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+        }
+
+        #[test]
+        fn test_set_highlight_radius_is_capped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(Event::SetHighlightRadius { meters: 250 }, &mut model);
+            assert_eq!(model.highlight_radius_m, 250);
+
+            let _ = app.update(Event::SetHighlightRadius { meters: u32::MAX }, &mut model);
+            assert_eq!(model.highlight_radius_m, MAX_RADIUS_M);
+        }
+
+        #[test]
+        fn test_golden_refresh_returns_three_cases() {
+            use crate::capabilities::testing::FakeServer;
+            use crate::capabilities::HttpOutput;
+            use crate::testing::respond_from;
+
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(37.77, -122.42).unwrap());
+            let nearby = |id: &str, status: CaseStatus| {
+                let mut case = server_case(id, status);
+                case.location = LatLon::new(37.77, -122.42);
+                case
+            };
+
+            let server = FakeServer::new().route(
+                "/api/v1/cases",
+                Ok(HttpOutput::ok_json(&ListCasesResponse {
+                    cases: vec![
+                        nearby("case1", CaseStatus::Pending),
+                        nearby("case2", CaseStatus::Claimed),
+                        nearby("case3", CaseStatus::EnRoute),
+                    ],
+                    next_cursor: Some("cursor-2".into()),
+                    total_count: Some(3),
+                })),
+            );
+
+            let request = app.update(Event::RefreshRequested, &mut model);
+            assert!(model.is_refreshing);
+            let updates = respond_from(&app, &server, request, &mut model);
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["case1", "case2", "case3"]);
+            assert_eq!(model.cases_cursor.as_deref(), Some("cursor-2"));
+            assert!(!model.is_refreshing);
+            assert!(model.offline_store.last_cases_refresh_ms.is_some());
+            assert!(updates
+                .iter()
+                .any(|u| has_effect(&u.effects, |e| matches!(e, AppEffect::Render(_)))));
+        }
+
+        #[test]
+        fn test_fake_server_routes_by_path() {
+            use crate::capabilities::testing::FakeServer;
+            use crate::capabilities::HttpOutput;
+
+            let response = FakeServer::new().respond("/api/v1/unknown?x=1").unwrap();
+            assert_eq!(response.status(), 404);
+
+            let server =
+                FakeServer::new().route("/api/v1/cases", Ok(HttpOutput::from_status(204, "")));
+            let response = server
+                .respond("https://api.example/api/v1/cases?lat=1")
+                .unwrap();
+            assert_eq!(response.status(), 204);
+        }
+
+        #[test]
+        fn test_refresh_429_sets_cooldown_and_blocks_actions() {
+            use crate::capabilities::testing::FakeServer;
+            use crate::capabilities::HttpOutput;
+            use crate::testing::respond_from;
+
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(37.77, -122.42).unwrap());
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let server = FakeServer::new().route(
+                "/api/v1/cases",
+                Ok(HttpOutput::from_status(429, "slow down").with_header("Retry-After", "5")),
+            );
+            let request = app.update(Event::RefreshRequested, &mut model);
+            let _ = respond_from(&app, &server, request, &mut model);
+
+            let remaining = model
+                .rate_limit_remaining_ms("list_cases", get_current_time_ms())
+                .unwrap();
+            assert!(remaining > 4_000 && remaining <= 5_000);
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.is_refreshing);
+            assert!(model
+                .active_toast
+                .as_ref()
+                .unwrap()
+                .message
+                .starts_with("Please wait"));
+
+            let _ = app.update(
+                Event::ClaimRequested {
+                    case_id: "case1".into(),
+                },
+                &mut model,
+            );
+            assert!(model.pending_claims.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_actions_resume_after_cooldown_elapses() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(37.77, -122.42).unwrap());
+            model.set_rate_limited("list_cases", get_current_time_ms().saturating_sub(1), 0);
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.is_refreshing);
+            assert!(model.rate_limited_until_ms.is_empty());
+        }
+
+        #[test]
+        fn test_create_case_rate_limit_does_not_block_refresh() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(37.77, -122.42).unwrap());
+            model.set_rate_limited("create_case", get_current_time_ms(), 60_000);
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.is_refreshing);
+            assert!(model.active_toast.is_none());
+            assert!(model
+                .rate_limit_remaining_ms("create_case", get_current_time_ms())
+                .is_some());
+        }
+
+        #[test]
+        fn test_wrong_crypto_variant_is_internal_error() {
+            use crate::capabilities::CryptoOutput;
+
+            match App::decryption_result_event(Ok(CryptoOutput::Hash(vec![1, 2]))) {
+                Event::StateDecryptionFailed { error } => {
+                    assert_eq!(error.kind, ErrorKind::Internal);
+                    assert_eq!(
+                        error.context.get("actual").map(String::as_str),
+                        Some("Hash")
+                    );
+                }
+                other => panic!("unexpected event {other:?}"),
+            }
+
+            match App::encryption_result_event("key".into(), Ok(CryptoOutput::Decrypted(vec![]))) {
+                Event::PersistenceFailed { error } => {
+                    assert_eq!(error.kind, ErrorKind::Internal);
+                    assert_eq!(
+                        error.context.get("expected").map(String::as_str),
+                        Some("Encrypted")
+                    );
+                }
+                other => panic!("unexpected event {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_genuine_crypto_failure_is_crypto_error() {
+            use crate::capabilities::CryptoError;
+
+            let result = Err(CryptoError::KeyNotFound {
+                key_id: "key".into(),
+            });
+
+            match App::decryption_result_event(result) {
+                Event::StateDecryptionFailed { error } => assert_eq!(error.kind, ErrorKind::Crypto),
+                other => panic!("unexpected event {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_wrong_crypto_variant_is_not_user_facing() {
+            use crate::capabilities::CryptoOutput;
+
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.restore_in_flight = true;
+
+            let event = App::decryption_result_event(Ok(CryptoOutput::Random(vec![])));
+            let _ = app.update(event, &mut model);
+
+            assert!(model.active_error.is_none());
+            assert!(!model.restore_in_flight);
+        }
+
+        fn refresh_with_cases(
+            app: &AppTester<App, AppEffect>,
+            model: &mut Model,
+            cases: Vec<ServerCase>,
+        ) {
+            use crate::capabilities::testing::FakeServer;
+            use crate::capabilities::HttpOutput;
+            use crate::testing::respond_from;
+
+            let server = FakeServer::new().route(
+                "/api/v1/cases",
+                Ok(HttpOutput::ok_json(&ListCasesResponse {
+                    cases,
+                    next_cursor: None,
+                    total_count: None,
+                })),
+            );
+            if model.area_center.is_none() {
+                model.area_center = ValidatedCoordinate::new(0.0, 0.0).ok();
+            }
+            let request = app.update(Event::RefreshRequested, model);
+            let _ = respond_from(app, &server, request, model);
+        }
+
+        #[test]
+        fn test_refresh_dropping_selected_case_clears_with_toast() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases = vec![server_case("case1", CaseStatus::Pending)];
+            model.selected_case_id = Some(CaseId::new("case1"));
+
+            refresh_with_cases(
+                &app,
+                &mut model,
+                vec![server_case("case2", CaseStatus::Pending)],
+            );
+
+            assert!(model.selected_case_id.is_none());
+            assert!(!model.selected_case_unavailable);
+            assert_eq!(
+                model.active_toast.as_ref().unwrap().message,
+                "This case was removed"
+            );
+        }
+
+        #[test]
+        fn test_refresh_dropping_selected_case_keeps_unavailable_detail() {
+            let app = AppTester::<App, AppEffect>::default();
+            let cases = vec![server_case("case1", CaseStatus::Pending)];
+            let mut model = ready_model_at(NULL_ISLAND, None, cases);
+            model.removed_selection_behavior = RemovedSelectionBehavior::KeepUnavailable;
+            model.selected_case_id = Some(CaseId::new("case1"));
+
+            refresh_with_cases(&app, &mut model, vec![]);
+
+            assert_eq!(model.selected_case_id, Some(CaseId::new("case1")));
+            assert!(model.selected_case_unavailable);
+            match app.view(&model).state {
+                ViewState::Ready {
+                    selected_detail,
+                    selected_unavailable,
+                    ..
+                } => {
+                    assert!(selected_detail.is_none());
+                    assert!(selected_unavailable);
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_refresh_keeping_selected_case_leaves_selection() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases = vec![server_case("case1", CaseStatus::Pending)];
+            model.selected_case_id = Some(CaseId::new("case1"));
+
+            refresh_with_cases(
+                &app,
+                &mut model,
+                vec![server_case("case1", CaseStatus::Claimed)],
+            );
+
+            assert_eq!(model.selected_case_id, Some(CaseId::new("case1")));
+            assert!(model.active_toast.is_none());
+        }
+
+        #[test]
+        fn test_dismiss_failed_operation_event_persists() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user1"));
+
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            entry.mark_permanently_failed(OutboxEntryError::server_error(400, None));
+            let op_id = entry.op_id.0.clone();
+            model.offline_store.push_outbox(entry).unwrap();
+
+            let update = app.update(Event::DismissFailedOperation { op_id }, &mut model);
+
+            assert!(model.offline_store.outbox.is_empty());
+            assert_eq!(persist_count(&update.effects), 1);
+        }
+
+        #[test]
+        fn test_poor_location_accuracy_routes_to_pin_drop() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::OnboardingLocation;
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 51.5,
+                    lng: -0.12,
+                    accuracy: Some(5_000.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.state, AppState::PinDrop);
+            assert_eq!(model.location_accuracy_m, Some(5_000.0));
+            assert!(model.area_center.is_some());
+            assert_eq!(
+                model.active_toast.as_ref().unwrap().kind,
+                ToastKind::Warning
+            );
+        }
+
+        #[test]
+        fn test_good_location_accuracy_proceeds_to_radius() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::OnboardingLocation;
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 51.5,
+                    lng: -0.12,
+                    accuracy: Some(12.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.state, AppState::OnboardingRadius);
+            assert_eq!(model.location_accuracy_m, Some(12.0));
+            assert!(model.active_toast.is_none());
+        }
+
+        #[test]
+        fn test_list_limit_truncates_to_nearest() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model_at(
+                NULL_ISLAND,
+                None,
+                cases_at_distances(&[900.0, 100.0, 500.0, 300.0, 700.0]),
+            );
+            model.list_limit = Some(2);
+
+            match app.view(&model).state {
+                ViewState::Ready {
+                    list_items,
+                    list_total_count,
+                    list_showing_text,
+                    ..
+                } => {
+                    let ids: Vec<&str> = list_items.iter().map(|i| i.id.as_str()).collect();
+                    assert_eq!(ids, vec!["case1", "case3"]);
+                    assert_eq!(list_total_count, 5);
+                    assert_eq!(list_showing_text.as_deref(), Some("Showing 2 of 5"));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_list_without_limit_shows_everything() {
+            let app = AppTester::<App, AppEffect>::default();
+            let model = ready_model_at(
+                NULL_ISLAND,
+                None,
+                cases_at_distances(&[900.0, 100.0, 500.0]),
+            );
+
+            match app.view(&model).state {
+                ViewState::Ready {
+                    list_items,
+                    list_total_count,
+                    list_showing_text,
+                    ..
+                } => {
+                    assert_eq!(list_items.len(), 3);
+                    assert_eq!(list_total_count, 3);
+                    assert!(list_showing_text.is_none());
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_radius_selected_prompts_push_for_new_user() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::OnboardingRadius;
+            model.network_online = false;
+
+            let update = app.update(Event::RadiusSelected { meters: 2000 }, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Push(_)
+            )));
+            assert!(model.offline_store.last_push_prompt_ms.is_some());
+        }
+
+        #[test]
+        fn test_radius_selected_skips_push_prompt_after_recent_denial() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::OnboardingRadius;
+            model.network_online = false;
+
+            let _ = app.update(Event::PushPermissionResult { granted: false }, &mut model);
+            assert!(model.offline_store.push_denied_at_ms.is_some());
+
+            let update = app.update(Event::RadiusSelected { meters: 2000 }, &mut model);
+
+            assert_eq!(model.state, AppState::Ready);
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Push(_)
+            )));
+        }
+
+        fn claim_blocked_reason_for(model: &Model) -> Option<ClaimBlockedReason> {
+            let app = AppTester::<App, AppEffect>::default();
+            match app.view(model).state {
+                ViewState::Ready {
+                    selected_detail: Some(detail),
+                    ..
+                } => {
+                    assert_eq!(detail.can_claim, detail.claim_blocked_reason.is_none());
+                    detail.claim_blocked_reason
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        fn detail_model(case: ServerCase, user_id: Option<&str>) -> Model {
+            let mut model = ready_model_at(NULL_ISLAND, user_id, vec![case]);
+            model.selected_case_id = Some(model.cases[0].id.clone());
+            model
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_available_case() {
+            let model = detail_model(server_case("case1", CaseStatus::Pending), Some("me"));
+            assert_eq!(claim_blocked_reason_for(&model), None);
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_not_authenticated() {
+            let model = detail_model(server_case("case1", CaseStatus::Pending), None);
+            assert_eq!(
+                claim_blocked_reason_for(&model),
+                Some(ClaimBlockedReason::NotAuthenticated)
+            );
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_not_claimable_status() {
+            for status in [
+                CaseStatus::Claimed,
+                CaseStatus::EnRoute,
+                CaseStatus::Arrived,
+                CaseStatus::Resolved,
+                CaseStatus::Cancelled,
+                CaseStatus::Expired,
+            ] {
+                let model = detail_model(server_case("case1", status), Some("me"));
+                assert_eq!(
+                    claim_blocked_reason_for(&model),
+                    Some(ClaimBlockedReason::NotClaimable),
+                    "{status:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_claimed_by_other() {
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.assigned_rescuer_id = Some(UserId::new("someone-else"));
+            let model = detail_model(case, Some("me"));
+            assert_eq!(
+                claim_blocked_reason_for(&model),
+                Some(ClaimBlockedReason::AlreadyClaimedByOther)
+            );
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_claimed_by_me() {
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.assigned_rescuer_id = Some(UserId::new("me"));
+            let model = detail_model(case, Some("me"));
+            assert_eq!(
+                claim_blocked_reason_for(&model),
+                Some(ClaimBlockedReason::AlreadyClaimedByMe)
+            );
+        }
+
+        #[test]
+        fn test_claim_blocked_reason_claim_in_flight() {
+            let mut model = detail_model(server_case("case1", CaseStatus::Pending), Some("me"));
+            model.pending_claims.insert(
+                CaseId::new("case1"),
+                PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None),
+            );
+            assert_eq!(
+                claim_blocked_reason_for(&model),
+                Some(ClaimBlockedReason::ClaimInFlight)
+            );
+        }
+
+        fn jpeg_with_dimensions(width: u32, height: u32) -> Vec<u8> {
+            let mut data = Vec::new();
+            image::DynamicImage::new_rgb8(width, height)
+                .write_to(
+                    &mut std::io::Cursor::new(&mut data),
+                    image::ImageFormat::Jpeg,
+                )
+                .unwrap();
+            data
+        }
+
+        fn tiny_jpeg() -> Vec<u8> {
+            jpeg_with_dimensions(8, 8)
+        }
+
+        #[test]
+        fn test_camera_heic_photo_reports_unsupported_format() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(
+                Event::CameraResult(Box::new(Ok(crate::capabilities::CameraOutput::Photo {
+                    data: tiny_jpeg(),
+                    mime_type: "image/heic".into(),
+                }))),
+                &mut model,
+            );
+
+            let error = model.active_error.expect("expected an error");
+            assert_eq!(error.kind, ErrorKind::ImageFormatUnsupported);
+            assert!(error.message.contains("HEIC"));
+            assert!(model.staged_photo.is_none());
+        }
+
+        #[test]
+        fn test_camera_jpeg_photo_is_processed() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(
+                Event::CameraResult(Box::new(Ok(crate::capabilities::CameraOutput::Photo {
+                    data: tiny_jpeg(),
+                    mime_type: "image/jpeg".into(),
+                }))),
+                &mut model,
+            );
+
+            assert!(model.active_error.is_none());
+            assert!(model.staged_photo.is_some());
+        }
+
+        fn update_draft(description: &str, location: Option<(f64, f64)>) -> Event {
+            Event::UpdateDraft {
+                location,
+                description: Some(description.into()),
+                landmark_hint: None,
+                wound_severity: Some(2),
+            }
+        }
+
+        #[test]
+        fn test_editing_draft_does_not_touch_outbox() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(update_draft("Limping cat", None), &mut model);
+            let _ = app.update(
+                update_draft("Limping cat near bins", Some((51.5, -0.12))),
+                &mut model,
+            );
+
+            let draft = model.draft().expect("draft should exist");
+            assert_eq!(draft.description.as_deref(), Some("Limping cat near bins"));
+            assert_eq!(draft.location, Some((51.5, -0.12)));
+            assert!(model.offline_store.outbox.is_empty());
+            assert!(model.offline_store.pending_local_cases.is_empty());
+        }
+
+        #[test]
+        fn test_submitting_draft_enqueues_case_and_clears_draft() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.network_online = false;
+
+            let _ = app.update(update_draft("Limping cat", Some((51.5, -0.12))), &mut model);
+            let _ = app.update(Event::SubmitDraft, &mut model);
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+            assert_eq!(model.offline_store.outbox.len(), 1);
+            assert_eq!(
+                model.offline_store.pending_local_cases[0]
+                    .description
+                    .as_deref(),
+                Some("Limping cat")
+            );
+            assert!(model.draft().is_none());
+        }
+
+        #[test]
+        fn test_submitting_draft_without_location_keeps_draft() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(update_draft("Limping cat", None), &mut model);
+            let _ = app.update(Event::SubmitDraft, &mut model);
+
+            assert_eq!(
+                model.active_error.map(|e| e.kind),
+                Some(ErrorKind::Validation)
+            );
+            assert!(model.draft().is_some());
+            assert!(model.offline_store.outbox.is_empty());
+        }
+
+        #[test]
+        fn test_discard_draft_clears_persisted_copy() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(update_draft("Limping cat", None), &mut model);
+            let _ = app.update(Event::DiscardDraft, &mut model);
+
+            assert!(model.draft().is_none());
+            assert!(model.store_dirty);
+        }
+
+        #[test]
+        fn test_update_draft_leaves_omitted_fields_unchanged() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(update_draft("Limping cat", Some((51.5, -0.12))), &mut model);
+            let _ = app.update(
+                Event::UpdateDraft {
+                    location: None,
+                    description: None,
+                    landmark_hint: Some("By the bins".into()),
+                    wound_severity: None,
+                },
+                &mut model,
+            );
+
+            let draft = model.draft().expect("draft should exist");
+            assert_eq!(draft.description.as_deref(), Some("Limping cat"));
+            assert_eq!(draft.location, Some((51.5, -0.12)));
+            assert_eq!(draft.landmark_hint.as_deref(), Some("By the bins"));
+            assert_eq!(draft.wound_severity, Some(2));
+        }
+
+        #[test]
+        fn test_draft_photo_counts_toward_image_memory_cap() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            capture_jpeg(&mut model, tiny_jpeg());
+            let staged_bytes = model.total_image_memory_bytes();
+
+            let _ = app.update(update_draft("Limping cat", None), &mut model);
+            let draft_bytes = model
+                .draft()
+                .and_then(|d| d.photo_data.as_ref())
+                .map_or(0, Vec::len);
+            assert!(draft_bytes > 0);
+            assert_eq!(model.total_image_memory_bytes(), staged_bytes + draft_bytes);
+
+            model.image_memory_cap_bytes = staged_bytes;
+            model.set_draft(None);
+            let _ = app.update(update_draft("Limping cat", None), &mut model);
+
+            assert_eq!(
+                model.active_error.map(|e| e.kind),
+                Some(ErrorKind::QuotaExceeded)
+            );
+            assert!(model.draft().is_none());
+        }
+
+        #[test]
+        fn test_submitting_draft_with_own_photo_keeps_staged_photo() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.network_online = false;
+            model.set_draft(Some(CaseDraft {
+                location: Some((51.5, -0.12)),
+                photo_data: Some(vec![1, 2, 3]),
+                photo_mime_type: Some("image/jpeg".into()),
+                ..CaseDraft::default()
+            }));
+            capture_jpeg(&mut model, tiny_jpeg());
+
+            let _ = app.update(Event::SubmitDraft, &mut model);
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+            assert_eq!(
+                model.offline_store.pending_local_cases[0].photo_data,
+                Some(vec![1, 2, 3])
+            );
+            assert!(model.staged_photo.is_some());
+            assert!(model.draft().is_none());
+        }
+
+        #[test]
+        fn test_draft_survives_store_round_trip() {
+            let mut model = Model::default();
+            model.set_draft(Some(CaseDraft {
+                description: Some("Limping cat".into()),
+                ..CaseDraft::default()
+            }));
+
+            let bytes = serde_json::to_vec(&model.offline_store).unwrap();
+            let store: OfflineStore = serde_json::from_slice(&bytes).unwrap();
+
+            assert_eq!(store.draft.as_ref(), model.draft());
+        }
+
+        #[test]
+        fn test_pins_expose_same_distance_as_list() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model_at(NULL_ISLAND, None, vec![]);
+            let mut near = server_case("near", CaseStatus::Pending);
+            near.location = LatLon::new(0.0, 0.01);
+            let mut reported = server_case("reported", CaseStatus::Pending);
+            reported.distance_meters = Some(250.0);
+            model.cases = vec![near, reported];
+
+            match app.view(&model).state {
+                ViewState::Ready {
+                    pins, list_items, ..
+                } => {
+                    for pin in &pins {
+                        let item = list_items.iter().find(|i| i.id == pin.id).unwrap();
+                        let distance = pin.distance_meters.expect("pin distance");
+                        assert!((distance - item.distance_meters).abs() < f64::EPSILON);
+                    }
+                    let reported_pin = pins.iter().find(|p| p.id == "reported").unwrap();
+                    assert_eq!(reported_pin.distance_meters, Some(250.0));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        fn onboarding_model_for_auto_select() -> Model {
+            let mut model = ready_model_at(
+                NULL_ISLAND,
+                None,
+                cases_at_distances(&[900.0, 100.0, 300.0, 500.0]),
+            );
+            model.state = AppState::OnboardingRadius;
+            model.network_online = false;
+            model.cases[1].assigned_rescuer_id = Some(UserId::new("someone-else"));
+            model.cases[3].status = CaseStatus::Claimed;
+            model
+        }
+
+        #[test]
+        fn test_auto_select_nearest_selects_closest_claimable_on_ready() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = onboarding_model_for_auto_select();
+
+            let _ = app.update(Event::SetAutoSelectNearest { enabled: true }, &mut model);
+            let _ = app.update(Event::RadiusSelected { meters: 2000 }, &mut model);
+
+            assert_eq!(model.state, AppState::Ready);
+            assert_eq!(model.selected_case_id, Some(CaseId::new("case2")));
+            assert!(!model.auto_select_pending);
+        }
+
+        #[test]
+        fn test_auto_select_disabled_leaves_selection_empty() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = onboarding_model_for_auto_select();
+
+            let _ = app.update(Event::SetAutoSelectNearest { enabled: false }, &mut model);
+            let _ = app.update(Event::RadiusSelected { meters: 2000 }, &mut model);
+
+            assert_eq!(model.state, AppState::Ready);
+            assert!(model.selected_case_id.is_none());
+        }
+
+        #[test]
+        fn test_auto_select_waits_for_cases_to_load() {
+            let mut model = Model::default();
+            model.auto_select_nearest = true;
+            model.area_center = Some(ValidatedCoordinate::new(0.0, 0.0).unwrap());
+
+            model.request_auto_select();
+            assert!(model.auto_select_pending);
+            assert!(model.selected_case_id.is_none());
+
+            model.cases = cases_at_distances(&[400.0, 200.0]);
+            assert!(model.apply_pending_auto_select());
+            assert_eq!(model.selected_case_id, Some(CaseId::new("case1")));
+        }
+
+        #[test]
+        fn test_logout_rolls_back_optimistic_claim_before_clearing() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("me"));
+            model.cases.push(server_case("case1", CaseStatus::Pending));
+
+            let mutation_id = model.store_optimistic_mutation(
+                CaseId::new("case1"),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+            let mut claim = PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None);
+            claim.mutation_id = mutation_id;
+            model.pending_claims.insert(CaseId::new("case1"), claim);
+            model.cases[0].status = CaseStatus::Claimed;
+
+            let _ = app.update(Event::LogoutRequested, &mut model);
+
+            assert!(model.pending_mutations.is_empty());
+            assert!(model.pending_claims.is_empty());
+            assert!(model.cases.is_empty());
+            assert_eq!(model.state, AppState::Unauthenticated);
+        }
+
+        #[test]
+        fn test_deselect_keeps_in_flight_claim() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.selected_case_id = Some(CaseId::new("case1"));
+            model.pending_claims.insert(
+                CaseId::new("case1"),
+                PendingClaim::new(CaseId::new("case1"), CaseStatus::Pending, None),
+            );
+
+            let _ = app.update(Event::CaseDeselected, &mut model);
+
+            assert!(model.selected_case_id.is_none());
+            assert!(model.pending_claims.contains_key(&CaseId::new("case1")));
+        }
+
+        #[test]
+        fn test_view_diff_toast_only_change() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model_at(NULL_ISLAND, None, cases_at_distances(&[100.0, 200.0]));
+
+            let before = app.view(&model);
+            model.show_toast("Saved", ToastKind::Success);
+            let after = app.view(&model);
+
+            let diff = view_diff(&before, &after);
+            assert_eq!(
+                diff,
+                ViewDiff {
+                    toast_changed: true,
+                    ..ViewDiff::default()
+                }
+            );
+        }
+
+        #[test]
+        fn test_view_diff_case_list_change_reports_state() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model_at(NULL_ISLAND, None, cases_at_distances(&[100.0, 200.0]));
+
+            let before = app.view(&model);
+            model.cases.push(server_case("case9", CaseStatus::Pending));
+            let after = app.view(&model);
+
+            let diff = view_diff(&before, &after);
+            assert!(diff.state_changed);
+            assert!(!diff.toast_changed);
+            assert!(!diff.error_changed);
+            assert!(view_diff(&after, &after).is_empty());
+        }
+
+        #[test]
+        fn test_promote_operation_persists_new_order() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("user1"));
+            for i in 0..2 {
+                model
+                    .offline_store
+                    .push_outbox(OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                        token: format!("t{i}"),
+                    }))
+                    .unwrap();
+            }
+            let newest = model.offline_store.outbox[1].op_id.clone();
+
+            let update = app.update(
+                Event::PromoteOperation {
+                    op_id: newest.0.clone(),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.offline_store.outbox[0].op_id, newest);
+            assert_eq!(persist_count(&update.effects), 1);
+        }
+
+        fn capture_jpeg(model: &mut Model, data: Vec<u8>) {
+            let app = AppTester::<App, AppEffect>::default();
+            let _ = app.update(
+                Event::CameraResult(Box::new(Ok(crate::capabilities::CameraOutput::Photo {
+                    data,
+                    mime_type: "image/jpeg".into(),
+                }))),
+                model,
+            );
+        }
+
+        #[test]
+        fn test_lower_decode_limit_rejects_image_higher_limit_accepts() {
+            let photo = jpeg_with_dimensions(512, 512);
+
+            let mut constrained = Model::default();
+            constrained.set_preference(Preference::MaxImageAlloc { bytes: 64 * 1024 });
+            capture_jpeg(&mut constrained, photo.clone());
+
+            let error = constrained
+                .active_error
+                .expect("expected decode limit error");
+            assert_eq!(error.kind, ErrorKind::ImageDimensionsTooLarge);
+            assert!(constrained.staged_photo.is_none());
+
+            let mut roomy = Model::default();
+            assert_eq!(roomy.max_image_alloc, MAX_IMAGE_ALLOC);
+            capture_jpeg(&mut roomy, photo);
+
+            assert!(roomy.active_error.is_none());
+            assert!(roomy.staged_photo.is_some());
+        }
+
+        #[test]
+        fn test_location_teleport_keeps_previous_area_center() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::Ready;
+            let home = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            model.area_center = Some(home);
+            model.last_gps_fix = Some(home);
+            model.last_location_fix_ms = Some(get_current_time_ms());
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 48.85,
+                    lng: 2.35,
+                    accuracy: Some(10.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.area_center, Some(home));
+            assert_eq!(model.location_jump_count, 1);
+        }
+
+        fn arrived_case_model(require_evidence: bool) -> Model {
+            let cases = vec![server_case("case-1", CaseStatus::Arrived)];
+            let mut model = ready_model_at(NULL_ISLAND, Some("rescuer"), cases);
+            model.require_resolution_evidence = require_evidence;
+            model
+        }
+
+        #[test]
+        fn test_resolve_without_evidence_blocked_by_policy() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(true);
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: Some("   ".into()),
+                    outcome: Some(ResolutionOutcome::Rescued),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Arrived);
+            assert_eq!(
+                model.active_error.as_ref().unwrap().kind,
+                ErrorKind::Validation
+            );
+            assert!(model.pending_mutations.is_empty());
+        }
+
+        #[test]
+        fn test_resolve_with_notes_allowed_under_policy() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(true);
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: Some("Dog taken to the vet".into()),
+                    outcome: Some(ResolutionOutcome::Rescued),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Resolved);
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_resolve_without_evidence_allowed_without_policy() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(false);
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: None,
+                    outcome: Some(ResolutionOutcome::Rescued),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Resolved);
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_resolve_with_staged_photo_allowed_under_policy() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(true);
+            assert!(!model.has_resolution_evidence(None));
+
+            capture_jpeg(&mut model, tiny_jpeg());
+            assert!(model.staged_photo.is_some());
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: None,
+                    outcome: Some(ResolutionOutcome::Rescued),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Resolved);
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_resolution_evidence_policy_is_persisted() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(false);
+
+            let update = app.update(
+                Event::SetResolutionEvidencePolicy { required: true },
+                &mut model,
+            );
+
+            assert!(model.preferences().require_resolution_evidence);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+
+            let mut restored = Model::default();
+            restored.apply_preferences(&model.preferences());
+            assert!(restored.require_resolution_evidence);
+        }
+
+        fn restored_model_with_cases() -> Model {
+            let app = AppTester::<App, AppEffect>::default();
+            let cases = vec![server_case("case-1", CaseStatus::Pending)];
+            let mut model = ready_model_at(LONDON, Some("rescuer"), cases);
+
+            let mut store = OfflineStore::new();
+            store.last_cases_refresh_ms = Some(get_current_time_ms());
+            let data = serde_cbor::to_vec(&store).unwrap();
+            let _ = app.update(Event::StateDecrypted { data }, &mut model);
+            model
+        }
+
+        #[test]
+        fn test_restored_model_with_cached_cases_is_offline_ready() {
+            let app = AppTester::<App, AppEffect>::default();
+            let model = restored_model_with_cases();
+            let refreshed_at = model.offline_store.last_cases_refresh_ms.unwrap();
+
+            let readiness = model.offline_readiness(refreshed_at + 5_000);
+
+            assert!(readiness.ready);
+            assert!(readiness.store_restored);
+            assert_eq!(readiness.cache_age_ms, Some(5_000));
+            assert!(app.view(&model).offline_readiness.ready);
+        }
+
+        #[test]
+        fn test_model_without_location_is_not_offline_ready() {
+            let mut model = restored_model_with_cases();
+            model.area_center = None;
+
+            let readiness = model.offline_readiness(model.view_timestamp_ms);
+
+            assert!(!readiness.ready);
+            assert!(!readiness.area_set);
+            assert!(readiness.cases_cached);
+        }
+
+        #[test]
+        fn test_failed_decryption_is_not_offline_ready() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = restored_model_with_cases();
+
+            let _ = app.update(
+                Event::StateDecryptionFailed {
+                    error: AppError::new(ErrorKind::Crypto, "bad key"),
+                },
+                &mut model,
+            );
+
+            let readiness = model.offline_readiness(model.view_timestamp_ms);
+            assert!(!readiness.store_restored);
+            assert!(!readiness.ready);
+        }
+
+        #[test]
+        fn test_restore_collapses_duplicate_local_ids() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.user_id = Some(UserId::new("rescuer"));
+
+            let mut store = OfflineStore::new();
+            let mut case = LocalCase::new(LatLon::new(1.0, 1.0), None, None);
+            case.updated_at_ms_utc = UnixTimeMs(1_000);
+            let mut newer = case.clone();
+            newer.updated_at_ms_utc = UnixTimeMs(5_000);
+            store.pending_local_cases = vec![case, newer];
+            let data = serde_cbor::to_vec(&store).unwrap();
+
+            let _ = app.update(Event::StateDecrypted { data }, &mut model);
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+            assert_eq!(
+                model.offline_store.pending_local_cases[0].updated_at_ms_utc,
+                UnixTimeMs(5_000)
+            );
+        }
+
+        #[test]
+        fn test_foregrounding_twice_within_cooldown_refreshes_once() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            let first = app.update(Event::AppForegrounded, &mut model);
+            model.is_refreshing = false;
+            model.offline_store.update_last_refresh();
+            let second = app.update(Event::AppForegrounded, &mut model);
+
+            assert!(has_effect(&first.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!has_effect(&second.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_foregrounding_after_cooldown_refreshes_again() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.offline_store.last_cases_refresh_ms =
+                Some(get_current_time_ms() - DEFAULT_FOREGROUND_REFRESH_COOLDOWN_MS - 1_000);
+
+            let update = app.update(Event::AppForegrounded, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_throttled_foreground_still_renders() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.offline_store.update_last_refresh();
+
+            let update = app.update(Event::AppForegrounded, &mut model);
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+        }
+
+        #[test]
+        fn test_foreground_cooldown_is_tunable_at_runtime() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::ForegroundRefreshCooldown { ms: 0 },
+                },
+                &mut model,
+            );
+            model.offline_store.update_last_refresh();
+
+            let update = app.update(Event::AppForegrounded, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_manual_refresh_ignores_foreground_cooldown() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.offline_store.update_last_refresh();
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_status_filter_hides_other_cases_in_list() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![
+                server_case("a", CaseStatus::Pending),
+                server_case("b", CaseStatus::Claimed),
+            ]);
+
+            let _ = app.update(
+                Event::SetStatusFilter {
+                    status: Some("claimed".into()),
+                },
+                &mut model,
+            );
+
+            match app.view(&model).state {
+                ViewState::Ready {
+                    list_items,
+                    pins,
+                    list_total_count,
+                    empty_feed_reason,
+                    ..
+                } => {
+                    assert_eq!(list_items.len(), 1);
+                    assert_eq!(list_items[0].id, "b");
+                    assert_eq!(pins.len(), 1);
+                    assert_eq!(list_total_count, 1);
+                    assert_eq!(empty_feed_reason, None);
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_invalid_status_filter_is_rejected() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            let _ = app.update(
+                Event::SetStatusFilter {
+                    status: Some("bogus".into()),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.status_filter, None);
+            assert_eq!(
+                model.active_error.as_ref().unwrap().kind,
+                ErrorKind::Validation
+            );
+        }
+
+        fn uploading_case_model() -> (Model, String) {
+            let mut model = ready_model(vec![]);
+            let mut case = LocalCase::new(LatLon::new(51.5, -0.12), None, None);
+            case.photo_data = Some(tiny_jpeg());
+            case.server_id = Some(CaseId::new("server-1"));
+            case.photo_upload_url = Some("https://uploads.example/original".into());
+            case.mark_uploading_photo();
+            let local_id = case.local_id.0.clone();
+            model.offline_store.pending_local_cases.push(case);
+            (model, local_id)
+        }
+
+        #[test]
+        fn test_upload_redirect_retries_against_location() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, local_id) = uploading_case_model();
+
+            let update = app.update(
+                Event::PhotoUploadResponse {
+                    local_id,
+                    redirected: false,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        302,
+                        Vec::new(),
+                    )
+                    .with_header("Location", "https://uploads.example/redirected"))),
+                },
+                &mut model,
+            );
+
+            let case = &model.offline_store.pending_local_cases[0];
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(
+                case.photo_upload_url.as_deref(),
+                Some("https://uploads.example/redirected")
+            );
+            assert_eq!(case.status, LocalCaseStatus::UploadingPhoto);
+        }
+
+        #[test]
+        fn test_upload_redirect_keeps_presigned_headers_and_resolves_relative_location() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, local_id) = uploading_case_model();
+            model.offline_store.pending_local_cases[0]
+                .photo_upload_headers
+                .insert("x-amz-signature".into(), "sig".into());
+
+            let update = app.update(
+                Event::PhotoUploadResponse {
+                    local_id,
+                    redirected: false,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        307,
+                        Vec::new(),
+                    )
+                    .with_header("Location", "/bucket/photo"))),
+                },
+                &mut model,
+            );
+
+            let request = update
+                .effects
+                .iter()
+                .find_map(|e| match e {
+                    AppEffect::Http(req) => Some(req),
+                    _ => None,
+                })
+                .expect("redirected upload");
+            assert_eq!(
+                request.operation.url,
+                "https://uploads.example/bucket/photo"
+            );
+            assert!(request
+                .operation
+                .headers
+                .iter()
+                .any(|h| h.name == "x-amz-signature" && h.value == "sig"));
+        }
+
+        #[test]
+        fn test_upload_redirect_is_followed_only_once() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, local_id) = uploading_case_model();
+
+            let update = app.update(
+                Event::PhotoUploadResponse {
+                    local_id,
+                    redirected: true,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        302,
+                        Vec::new(),
+                    )
+                    .with_header("Location", "https://uploads.example/loop"))),
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(
+                model.offline_store.pending_local_cases[0].status,
+                LocalCaseStatus::Failed
+            );
+        }
+
+        #[test]
+        fn test_api_redirect_is_flagged_as_configuration_error() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.is_refreshing = true;
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        301,
+                        Vec::new(),
+                    )
+                    .with_header("Location", "https://elsewhere.example/api/v1/cases"))),
+                },
+                &mut model,
+            );
+
+            let error = model.active_error.as_ref().unwrap();
+            assert_eq!(error.kind, ErrorKind::Configuration);
+            assert_eq!(error.http_status(), Some(301));
+            assert!(!error.is_retryable());
+            assert!(!model.is_refreshing);
+        }
+
+        fn panned_map_model(mode: MapFollowMode) -> Model {
+            let mut model = ready_model(vec![]);
+            model.map_center = Some(ValidatedCoordinate::new(51.6, -0.2).unwrap());
+            model.map_follow_mode = mode;
+            model
+        }
+
+        #[test]
+        fn test_location_update_recenters_map_in_follow_mode() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = panned_map_model(MapFollowMode::Follow);
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 51.501,
+                    lng: -0.12,
+                    accuracy: Some(10.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.map_center, model.area_center);
+        }
+
+        #[test]
+        fn test_location_update_keeps_map_in_free_mode() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = panned_map_model(MapFollowMode::Free);
+            let panned = model.map_center;
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 51.501,
+                    lng: -0.12,
+                    accuracy: Some(10.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.map_center, panned);
+            assert_ne!(model.area_center, panned);
+        }
+
+        #[test]
+        fn test_manual_pan_switches_to_free_mode() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = panned_map_model(MapFollowMode::Follow);
+
+            let _ = app.update(
+                Event::MapMoved {
+                    center: MapCenter {
+                        lat: 51.55,
+                        lng: -0.15,
+                    },
+                    zoom: ZoomLevel::new(12.0),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.map_follow_mode, MapFollowMode::Free);
+
+            let _ = app.update(
+                Event::SetMapFollowMode {
+                    mode: MapFollowMode::Follow,
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.map_follow_mode, MapFollowMode::Follow);
+            assert_eq!(model.map_center, model.area_center);
+        }
+
+        fn case_detail_for(model: &Model) -> CaseDetail {
+            let app = AppTester::<App, AppEffect>::default();
+            match app.view(model).state {
+                ViewState::Ready {
+                    selected_detail: Some(detail),
+                    ..
+                } => detail,
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_case_assigned_to_me_offers_resume_instead_of_claim() {
+            let mut case = server_case("case1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(UserId::new("me"));
+            let model = detail_model(case, Some("me"));
+
+            let detail = case_detail_for(&model);
+
+            assert_eq!(detail.claim_state, ClaimState::ClaimedByMe);
+            assert!(detail.can_resume);
+            assert!(!detail.can_claim);
+        }
+
+        #[test]
+        fn test_case_assigned_to_other_does_not_offer_resume() {
+            let mut case = server_case("case1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(UserId::new("someone-else"));
+            let model = detail_model(case, Some("me"));
+
+            let detail = case_detail_for(&model);
+
+            assert!(!detail.can_resume);
+            assert_eq!(detail.claim_state, ClaimState::NotClaimable);
+        }
+
+        #[test]
+        fn test_resume_reconciles_lost_claim() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut case = server_case("case1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(UserId::new("me"));
+            let mut model = detail_model(case, Some("me"));
+            let case_id = CaseId::new("case1");
+            model.pending_claims.insert(
+                case_id.clone(),
+                PendingClaim::new(case_id.clone(), CaseStatus::Pending, None),
+            );
+            model.store_optimistic_mutation(
+                case_id.clone(),
+                CaseStatus::Pending,
+                None,
+                CaseStatus::Claimed,
+            );
+
+            let update = app.update(
+                Event::ResumeCaseRequested {
+                    case_id: "case1".into(),
+                },
+                &mut model,
+            );
+
+            assert!(model.pending_claims.is_empty());
+            assert!(model.pending_mutations.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(case_detail_for(&model).can_resume);
+        }
+
+        #[test]
+        fn test_resume_ignores_case_not_assigned_to_me() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = detail_model(server_case("case1", CaseStatus::Pending), Some("me"));
+
+            let update = app.update(
+                Event::ResumeCaseRequested {
+                    case_id: "case1".into(),
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+        }
+
+        #[test]
+        fn test_list_items_use_configured_utc_offset_for_day_bucket() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            let mut case = server_case("case1", CaseStatus::Pending);
+            case.created_at_ms_utc =
+                UnixTimeMs(get_current_time_ms().saturating_sub(3 * 24 * 60 * 60 * 1000));
+            model.cases = vec![case];
+
+            let update = app.update(Event::SetUtcOffset { minutes: 10_000 }, &mut model);
+
+            assert_eq!(model.utc_offset_minutes, MAX_UTC_OFFSET_MINUTES);
+            assert_eq!(
+                model.preferences().utc_offset_minutes,
+                MAX_UTC_OFFSET_MINUTES
+            );
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+            match app.view(&model).state {
+                ViewState::Ready { list_items, .. } => {
+                    assert_eq!(list_items[0].day_bucket, DayBucket::Earlier);
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_push_before_login_is_deferred_and_replayed_on_ready() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::Unauthenticated;
+            model.network_online = false;
+            model.cases.push(server_case("case-1", CaseStatus::Pending));
+
+            let _ = app.update(
+                Event::PushReceived(PushPayload::CaseResolved {
+                    case_id: "case-1".into(),
+                }),
+                &mut model,
+            );
+
+            assert_eq!(model.pending_pushes.len(), 1);
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+
+            let events = vec![
+                Event::LoginCompleted {
+                    jwt: "aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".into(),
+                    user_id: "user1".into(),
+                },
+                Event::LocationPinDropped {
+                    lat: 51.5,
+                    lng: -0.12,
+                },
+            ];
+            for event in events {
+                let _ = app.update(event, &mut model);
+            }
+            assert_eq!(model.pending_pushes.len(), 1);
+
+            let _ = app.update(Event::OnboardingComplete, &mut model);
+
+            assert_eq!(model.state, AppState::Ready);
+            assert!(model.pending_pushes.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Resolved);
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_pending_pushes_are_bounded() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::Loading;
+
+            for i in 0..MAX_PENDING_PUSHES + 5 {
+                let _ = app.update(
+                    Event::PushReceived(PushPayload::CaseResolved {
+                        case_id: format!("case-{i}"),
+                    }),
+                    &mut model,
+                );
+            }
+
+            assert_eq!(model.pending_pushes.len(), MAX_PENDING_PUSHES);
+            match &model.pending_pushes[0] {
+                PushPayload::CaseResolved { case_id } => assert_eq!(case_id, "case-5"),
+                other => panic!("unexpected payload {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_logout_discards_pending_pushes() {
+            let mut model = Model::default();
+            let _ = model.defer_push(PushPayload::CaseResolved {
+                case_id: "case-1".into(),
+            });
+
+            let app = AppTester::<App, AppEffect>::default();
+            let _ = app.update(Event::LogoutRequested, &mut model);
+
+            assert!(model.pending_pushes.is_empty());
+        }
+
+        #[test]
+        fn test_upload_response_after_logout_is_dropped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, local_id) = uploading_case_model();
+
+            let _ = app.update(Event::LogoutConfirmed, &mut model);
+            assert!(model.cancelled_request_ids.contains(&local_id));
+
+            let update = app.update(
+                Event::PhotoUploadResponse {
+                    local_id,
+                    redirected: false,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        200,
+                        Vec::new(),
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.offline_store.pending_local_cases.is_empty());
+            assert!(model.cancelled_request_ids.is_empty());
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_create_response_after_logout_is_dropped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            let op_id = entry.op_id.clone();
+            model.offline_store.push_outbox(entry).unwrap();
+            model.offline_store.mark_entry_in_flight(&op_id);
+
+            let _ = app.update(Event::LogoutConfirmed, &mut model);
+
+            let update = app.update(
+                Event::CreateCaseResponse {
+                    op_id: op_id.0.clone(),
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        201,
+                        b"{}".to_vec(),
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.offline_store.outbox.is_empty());
+            assert!(!model.cancelled_request_ids.contains(&op_id.0));
+        }
+
+        #[test]
+        fn test_create_response_status_follows_shared_retry_classification() {
+            let app = AppTester::<App, AppEffect>::default();
+
+            for (status, expected) in [
+                (408, RetryState::Failed),
+                (503, RetryState::Failed),
+                (422, RetryState::PermanentlyFailed),
+            ] {
+                let mut model = ready_model(vec![]);
+                let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+                let op_id = entry.op_id.clone();
+                model.offline_store.push_outbox(entry).unwrap();
+                model.offline_store.mark_entry_in_flight(&op_id);
+
+                let _ = app.update(
+                    Event::CreateCaseResponse {
+                        op_id: op_id.0.clone(),
+                        result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                            status,
+                            Vec::new(),
+                        ))),
+                    },
+                    &mut model,
+                );
+
+                assert_eq!(
+                    outbox_entry(&model, &op_id.0).retry_state,
+                    expected,
+                    "{status}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_onboarding_views_report_step_progress() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(51.5, -0.12).unwrap());
+
+            model.state = AppState::OnboardingLocation;
+            match app.view(&model).state {
+                ViewState::OnboardingLocation {
+                    onboarding_step, ..
+                } => {
+                    assert_eq!(onboarding_step, Some((1, ONBOARDING_STEP_COUNT)));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+
+            model.state = AppState::PinDrop;
+            match app.view(&model).state {
+                ViewState::PinDrop {
+                    onboarding_step, ..
+                } => {
+                    assert_eq!(onboarding_step, Some((1, ONBOARDING_STEP_COUNT)));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+
+            model.state = AppState::OnboardingRadius;
+            match app.view(&model).state {
+                ViewState::OnboardingRadius {
+                    onboarding_step, ..
+                } => {
+                    assert_eq!(onboarding_step, Some((2, ONBOARDING_STEP_COUNT)));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_non_onboarding_states_have_no_step() {
+            for state in [
+                AppState::Loading,
+                AppState::Unauthenticated,
+                AppState::CameraCapture,
+                AppState::Ready,
+            ] {
+                assert_eq!(state.onboarding_step(), None, "{state:?}");
+            }
+        }
+
+        #[test]
+        fn test_double_tapped_report_creates_one_local_case() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+
+            for _ in 0..2 {
+                let _ = app.update(
+                    Event::CreateCaseRequested(CreateCasePayload {
+                        location: (51.5, -0.12),
+                        description: Some("Injured dog".into()),
+                        landmark_hint: None,
+                        wound_severity: Some(3),
+                    }),
+                    &mut model,
+                );
+            }
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+            assert!(model.active_error.is_none());
+        }
+
+        #[test]
+        fn test_distinct_reports_create_separate_local_cases() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+
+            for location in [(51.5, -0.12), (51.501, -0.121)] {
+                let _ = app.update(
+                    Event::CreateCaseRequested(CreateCasePayload {
+                        location,
+                        description: None,
+                        landmark_hint: None,
+                        wound_severity: None,
+                    }),
+                    &mut model,
+                );
+            }
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 2);
+        }
+
+        #[test]
+        fn test_reports_with_different_descriptions_are_not_duplicates() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+
+            for description in ["Injured dog", "  injured   DOG ", "Cat with a limp"] {
+                let _ = app.update(
+                    Event::CreateCaseRequested(CreateCasePayload {
+                        location: (51.5, -0.12),
+                        description: Some(description.into()),
+                        landmark_hint: None,
+                        wound_severity: None,
+                    }),
+                    &mut model,
+                );
+            }
+
+            assert_eq!(model.offline_store.pending_local_cases.len(), 2);
+        }
+
+        fn case_at_distance(id: &str, distance_m: f64) -> ServerCase {
+            let mut case = server_case(id, CaseStatus::Pending);
+            case.distance_meters = Some(distance_m);
+            case
+        }
+
+        #[test]
+        fn test_refresh_drops_cases_beyond_radius_tolerance() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.area_radius_m = 1000;
+            model.set_preference(Preference::DropOutOfRadiusCases { enabled: true });
+            model.is_refreshing = true;
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ListCasesResponse {
+                            cases: vec![
+                                case_at_distance("inside", 800.0),
+                                case_at_distance("edge", 1050.0),
+                                case_at_distance("far", 5000.0),
+                            ],
+                            next_cursor: None,
+                            total_count: Some(3),
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["inside", "edge"]);
+        }
+
+        #[test]
+        fn test_out_of_radius_cases_are_counted_but_kept_when_dropping_is_off() {
+            let mut model = Model::default();
+            model.area_radius_m = 1000;
+            model.set_preference(Preference::DropOutOfRadiusCases { enabled: false });
+            let mut cases = vec![
+                case_at_distance("inside", 800.0),
+                case_at_distance("far", 1200.0),
+                server_case("unknown", CaseStatus::Pending),
+            ];
+
+            assert_eq!(model.screen_out_of_radius(&mut cases), 1);
+            assert_eq!(cases.len(), 3);
+
+            model.set_preference(Preference::DropOutOfRadiusCases { enabled: true });
+            assert_eq!(model.screen_out_of_radius(&mut cases), 1);
+            let ids: Vec<&str> = cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["inside", "unknown"]);
+        }
+
+        #[test]
+        fn test_radius_tolerance_preference_widens_the_cutoff() {
+            let mut model = Model::default();
+            model.area_radius_m = 1000;
+            model.set_preference(Preference::RadiusTolerance { fraction: 0.5 });
+            let mut cases = vec![case_at_distance("edge", 1200.0)];
+
+            assert_eq!(model.screen_out_of_radius(&mut cases), 0);
+
+            model.set_preference(Preference::RadiusTolerance { fraction: -1.0 });
+            assert!(model.radius_tolerance_fraction.abs() < f64::EPSILON);
+            assert_eq!(model.screen_out_of_radius(&mut cases), 1);
+        }
+
+        #[test]
+        fn test_warmup_vision_without_detector_is_noop() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            let update = app.update(Event::WarmupVision, &mut model);
+
+            assert!(update.effects.is_empty());
+            assert_eq!(model.state, AppState::Ready);
+        }
+
+        fn open_circuit_model(cooldown_ms: u64) -> (Model, OpId) {
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+            model.circuit_breaker = CircuitBreaker::default().with_cooldown_ms(cooldown_ms);
+            for _ in 0..CIRCUIT_BREAKER_MIN_SAMPLES {
+                model.circuit_breaker.record_failure(get_current_time_ms());
+            }
+
+            let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "t".into() });
+            let op_id = entry.op_id.clone();
+            model.offline_store.push_outbox(entry).unwrap();
+            (model, op_id)
+        }
+
+        #[test]
+        fn test_flush_is_paused_while_circuit_open() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, _) = open_circuit_model(60_000);
+
+            let update = app.update(Event::OutboxFlushRequested, &mut model);
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.offline_store.outbox[0].is_in_flight());
+            assert!(!model.server_reachable(get_current_time_ms()));
+        }
+
+        #[test]
+        fn test_successful_probe_closes_circuit() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, _) = open_circuit_model(0);
+
+            let update = app.update(Event::OutboxFlushRequested, &mut model);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+
+            let _ = app.update(
+                Event::FcmSyncResponse {
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        200,
+                        Vec::new(),
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert_eq!(
+                model.circuit_breaker.state(get_current_time_ms()),
+                CircuitState::Closed
+            );
+            assert!(model.server_reachable(get_current_time_ms()));
+        }
+
+        #[test]
+        fn test_resolve_without_outcome_is_rejected() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(false);
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: None,
+                    outcome: None,
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Arrived);
+            assert_eq!(
+                model.active_error.as_ref().unwrap().kind,
+                ErrorKind::Validation
+            );
+            assert!(model.pending_mutations.is_empty());
+        }
+
+        #[test]
+        fn test_resolved_outcome_is_shown_on_case_detail() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = arrived_case_model(false);
+            model.area_center = Some(ValidatedCoordinate::new(0.0, 0.0).unwrap());
+            model.selected_case_id = Some(CaseId::new("case-1"));
+
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "resolved".into(),
+                    notes: None,
+                    outcome: Some(ResolutionOutcome::Rescued),
+                },
+                &mut model,
+            );
+
+            let detail = case_detail_for(&model);
+            assert_eq!(detail.status, CaseStatus::Resolved);
+            assert_eq!(detail.resolution_outcome, Some(ResolutionOutcome::Rescued));
+        }
+
+        fn claiming_model() -> (AppTester<App, AppEffect>, Model, String) {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![server_case("case-1", CaseStatus::Pending)]);
+
+            let _ = app.update(
+                Event::ClaimRequested {
+                    case_id: "case-1".into(),
+                },
+                &mut model,
+            );
+            let mutation_id = model.pending_claims[&CaseId::new("case-1")]
+                .mutation_id
+                .clone();
+            (app, model, mutation_id)
+        }
+
+        #[test]
+        fn test_push_during_claim_does_not_clobber_optimistic_state() {
+            let (app, mut model, mutation_id) = claiming_model();
+
+            let _ = app.update(
+                Event::PushReceived(PushPayload::CaseClaimed {
+                    case_id: "case-1".into(),
+                    claimed_by: "other".into(),
+                }),
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert_eq!(
+                model.cases[0].assigned_rescuer_id,
+                Some(UserId::new("user1"))
+            );
+            assert!(model.deferred_push_patches.contains_key("case-1"));
+
+            let _ = app.update(
+                Event::ClaimResponse {
+                    case_id: "case-1".into(),
+                    mutation_id,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        409,
+                        Vec::new(),
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert_eq!(
+                model.cases[0].assigned_rescuer_id,
+                Some(UserId::new("other"))
+            );
+            assert!(model.deferred_push_patches.is_empty());
+        }
+
+        #[test]
+        fn test_push_without_pending_mutation_applies_immediately() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![server_case("case-1", CaseStatus::Pending)]);
+
+            let _ = app.update(
+                Event::PushReceived(PushPayload::CaseCancelled {
+                    case_id: "case-1".into(),
+                    reason: None,
+                }),
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Cancelled);
+            assert!(model.deferred_push_patches.is_empty());
+        }
+
+        #[test]
+        fn test_deferred_push_patches_merge_newest_fields() {
+            let (_, mut model, _) = claiming_model();
+
+            assert!(!model.apply_push_update(
+                "case-1",
+                CasePatch {
+                    status: Some(CaseStatus::Claimed),
+                    assigned_rescuer_id: Some(UserId::new("other")),
+                },
+            ));
+            assert!(!model.apply_push_update("case-1", CasePatch::status(CaseStatus::EnRoute)));
+
+            assert_eq!(
+                model.deferred_push_patches["case-1"],
+                CasePatch {
+                    status: Some(CaseStatus::EnRoute),
+                    assigned_rescuer_id: Some(UserId::new("other")),
+                }
+            );
+        }
+
+        fn page_case(id: &str, status: CaseStatus, updated_at_ms: u64) -> ServerCase {
+            let mut case = server_case(id, status);
+            case.updated_at_ms_utc = UnixTimeMs(updated_at_ms);
+            case
+        }
+
+        fn list_page(cases: Vec<ServerCase>, next_cursor: Option<&str>) -> Event {
+            Event::LoadMoreResponse {
+                generation: 0,
+                result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                    &ListCasesResponse {
+                        cases,
+                        next_cursor: next_cursor.map(str::to_string),
+                        total_count: None,
+                    },
+                ))),
+            }
+        }
+
+        #[test]
+        fn test_overlapping_load_more_pages_keep_unique_freshest_cases() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases = vec![
+                page_case("case-1", CaseStatus::Pending, 1_000),
+                page_case("case-2", CaseStatus::Pending, 1_000),
+            ];
+
+            let _ = app.update(
+                list_page(
+                    vec![
+                        page_case("case-2", CaseStatus::Claimed, 2_000),
+                        page_case("case-3", CaseStatus::Pending, 1_000),
+                    ],
+                    Some("cursor-3"),
+                ),
+                &mut model,
+            );
+            let _ = app.update(
+                list_page(
+                    vec![
+                        page_case("case-3", CaseStatus::Pending, 500),
+                        page_case("case-4", CaseStatus::Pending, 1_000),
+                    ],
+                    None,
+                ),
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["case-1", "case-2", "case-3", "case-4"]);
+            assert_eq!(model.cases[1].status, CaseStatus::Claimed);
+            assert_eq!(model.cases[2].updated_at_ms_utc, UnixTimeMs(1_000));
+        }
+
+        #[test]
+        fn test_preferences_round_trip_through_json() {
+            let mut model = Model::default();
+            model.set_preference(Preference::HighlightRadius { meters: 750 });
+            model.set_preference(Preference::AutoRefreshInterval { ms: Some(30_000) });
+            model.set_preference(Preference::AutoSelectNearest { enabled: true });
+
+            let bytes = serde_json::to_vec(&model.preferences()).unwrap();
+            let restored: UserPreferences = serde_json::from_slice(&bytes).unwrap();
+
+            let mut fresh = Model::default();
+            fresh.apply_preferences(&restored);
+            assert_eq!(fresh.preferences(), model.preferences());
+            assert_eq!(fresh.highlight_radius_m, 750);
+            assert_eq!(fresh.auto_refresh_interval_ms, Some(30_000));
+            assert!(fresh.auto_select_nearest);
+        }
+
+        #[test]
+        fn test_preferences_missing_fields_use_defaults() {
+            let prefs: UserPreferences =
+                serde_json::from_str(r#"{"highlight_radius_m":300}"#).unwrap();
+            assert_eq!(prefs.highlight_radius_m, 300);
+            assert_eq!(prefs.auto_refresh_interval_ms, None);
+            assert!(!prefs.auto_select_nearest);
+        }
+
+        #[test]
+        fn test_set_preference_persists_without_touching_offline_store() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.store_dirty = false;
+            let store_before = serde_json::to_value(&model.offline_store).unwrap();
+
+            let update = app.update(
+                Event::SetPreference {
+                    preference: Preference::AutoRefreshInterval { ms: Some(45_000) },
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.auto_refresh_interval_ms, Some(45_000));
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Crypto(_)
+            )));
+            assert!(!model.store_dirty);
+            assert_eq!(
+                serde_json::to_value(&model.offline_store).unwrap(),
+                store_before
+            );
+        }
+
+        #[test]
+        fn test_loaded_preferences_are_applied() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            let prefs = UserPreferences {
+                highlight_radius_m: 900,
+                auto_refresh_interval_ms: None,
+                auto_select_nearest: true,
+                list_sort: ListSortMode::SeverityThenDistance,
+                distance_unit: DistanceUnit::Imperial,
+                ..UserPreferences::default()
+            };
+            let bytes = serde_json::to_vec(&prefs).unwrap();
+
+            let _ = app.update(
+                Event::PreferencesLoaded {
+                    result: Box::new(Ok(bytes)),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.preferences(), prefs);
+        }
+
+        #[test]
+        fn test_missing_preferences_keep_defaults() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(
+                Event::PreferencesLoaded {
+                    result: Box::new(Err(crate::capabilities::KvError::NotFound)),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.preferences(), UserPreferences::default());
+        }
+
+        fn deleted_keys(effects: &[AppEffect]) -> Vec<String> {
+            effects
+                .iter()
+                .filter_map(|e| match e {
+                    AppEffect::Kv(req) => match &req.operation {
+                        crux_kv::KeyValueOperation::Delete { key } => Some(key.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_purge_deletes_every_known_key() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            let update = app.update(Event::PurgeAllLocalData, &mut model);
+
+            let keys = deleted_keys(&update.effects);
+            assert_eq!(keys.len(), 3, "{keys:?}");
+            assert!(keys.iter().any(|k| k == PREFERENCES_KV_KEY));
+            assert!(keys.iter().any(|k| k == STORE_KEYS_KV_KEY));
+            assert!(keys.iter().any(|k| k.starts_with("offline_store_v")));
+        }
+
+        #[test]
+        fn test_purge_resets_model_to_defaults() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, _) = uploading_case_model();
+            model.selected_case_id = Some(CaseId::new("case-1"));
+            model.cases.push(server_case("case-1", CaseStatus::Pending));
+            model.set_preference(Preference::HighlightRadius { meters: 800 });
+
+            let _ = app.update(Event::PurgeAllLocalData, &mut model);
+
+            let defaults = Model::default();
+            assert_eq!(model.state, AppState::Unauthenticated);
+            assert!(model.user_id.is_none());
+            assert!(model.jwt_token.is_none());
+            assert!(model.cases.is_empty());
+            assert!(model.selected_case_id.is_none());
+            assert!(model.staged_photo.is_none());
+            assert!(model.offline_store.pending_local_cases.is_empty());
+            assert!(model.offline_store.outbox.is_empty());
+            assert_eq!(model.preferences(), defaults.preferences());
+        }
+
+        #[test]
+        fn test_purge_without_user_still_deletes_preferences() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let update = app.update(Event::PurgeAllLocalData, &mut model);
+
+            assert_eq!(
+                deleted_keys(&update.effects),
+                vec![
+                    PREFERENCES_KV_KEY.to_string(),
+                    STORE_KEYS_KV_KEY.to_string()
+                ]
+            );
+        }
+
+        #[test]
+        fn test_logout_keeps_preferences() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.set_preference(Preference::HighlightRadius { meters: 800 });
+
+            let update = app.update(Event::LogoutRequested, &mut model);
+
+            assert!(deleted_keys(&update.effects).is_empty());
+            assert_eq!(model.highlight_radius_m, 800);
+        }
+
+        fn backed_off_entry() -> OutboxEntry {
+            let mut entry = OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                token: "token".into(),
+            });
+            entry.retry_state = RetryState::Failed;
+            entry.attempt_count = 4;
+            entry.next_retry_at = Some(entry.updated_at.add_millis(120_000));
+            entry
+        }
+
+        fn outbox_entry<'a>(model: &'a Model, op_id: &str) -> &'a OutboxEntry {
+            model
+                .offline_store
+                .outbox
+                .iter()
+                .find(|e| e.op_id.0 == op_id)
+                .unwrap()
+        }
+
+        fn outbox_with_sibling() -> (Model, String, String) {
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+            let mut done = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "a".into() });
+            done.mark_in_flight();
+            let done_id = done.op_id.0.clone();
+            let sibling = backed_off_entry();
+            let sibling_id = sibling.op_id.0.clone();
+            model.offline_store.outbox.push(done);
+            model.offline_store.outbox.push(sibling);
+            (model, done_id, sibling_id)
+        }
+
+        #[test]
+        fn test_success_resets_recent_failed_siblings() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, done_id, sibling_id) = outbox_with_sibling();
+            let tuning = OutboxTuning {
+                reset_siblings_on_success: true,
+                ..OutboxTuning::default()
+            };
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::OutboxTuning { tuning },
+                },
+                &mut model,
+            );
+
+            let _ = app.update(Event::OutboxEntryCompleted { op_id: done_id }, &mut model);
+
+            let sibling = outbox_entry(&model, &sibling_id);
+            assert_eq!(sibling.attempt_count, 0);
+            assert!(sibling.next_retry_at.is_none());
+            assert_eq!(sibling.retry_state, RetryState::Failed);
+        }
+
+        #[test]
+        fn test_success_leaves_siblings_by_default() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, done_id, sibling_id) = outbox_with_sibling();
+
+            let _ = app.update(Event::OutboxEntryCompleted { op_id: done_id }, &mut model);
+
+            let sibling = outbox_entry(&model, &sibling_id);
+            assert_eq!(sibling.attempt_count, 4);
+            assert!(sibling.next_retry_at.is_some());
+        }
+
+        #[test]
+        fn test_sibling_reset_skips_failures_outside_window() {
+            let mut model = Model::default();
+            model.outbox_tuning.reset_siblings_on_success = true;
+            let mut stale = backed_off_entry();
+            stale.updated_at = UnixTimeMs(1_000);
+            let stale_id = stale.op_id.0.clone();
+            let mut limited = OutboxEntry::new(OutboxIntent::SyncFcmToken { token: "b".into() });
+            limited.mark_rate_limited(60_000);
+            let limited_id = limited.op_id.0.clone();
+            model.offline_store.outbox.push(stale);
+            model.offline_store.outbox.push(limited);
+
+            assert_eq!(model.reset_sibling_backoff(get_current_time_ms()), 0);
+            assert_eq!(outbox_entry(&model, &stale_id).attempt_count, 4);
+            assert!(outbox_entry(&model, &limited_id).next_retry_at.is_some());
+        }
+
+        fn case_north_of(id: &str, center: ValidatedCoordinate, meters: f64) -> ServerCase {
+            let mut case = server_case(id, CaseStatus::Pending);
+            let degrees = meters / 111_195.0;
+            case.location = LatLon::new(center.lat() + degrees, center.lon());
+            case
+        }
+
+        fn pruning_model() -> (Model, ValidatedCoordinate) {
+            let mut model = ready_model(vec![]);
+            model.area_radius_m = 1000;
+            model.set_preference(Preference::RadiusTolerance { fraction: 0.1 });
+            let center = model.area_center.unwrap();
+            (model, center)
+        }
+
+        #[test]
+        fn test_prune_keeps_everything_when_dropping_is_off() {
+            let (mut model, center) = pruning_model();
+            model.set_preference(Preference::DropOutOfRadiusCases { enabled: false });
+            model.cases = vec![case_north_of("far", center, 4000.0)];
+
+            assert_eq!(model.prune_out_of_radius(), 0);
+            assert_eq!(model.cases.len(), 1);
+        }
+
+        #[test]
+        fn test_prune_keeps_cases_inside_tolerance_margin() {
+            let (mut model, center) = pruning_model();
+            model.cases = vec![
+                case_north_of("inside", center, 950.0),
+                case_north_of("margin", center, 1080.0),
+                case_north_of("beyond", center, 1150.0),
+                case_north_of("far", center, 4000.0),
+            ];
+
+            assert_eq!(model.prune_out_of_radius(), 2);
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["inside", "margin"]);
+        }
+
+        #[test]
+        fn test_prune_never_drops_my_cases() {
+            let (mut model, center) = pruning_model();
+            let mut mine = case_north_of("mine", center, 3000.0);
+            mine.status = CaseStatus::EnRoute;
+            mine.assigned_rescuer_id = model.user_id.clone();
+            model.cases = vec![mine, case_north_of("theirs", center, 3000.0)];
+
+            model.prune_out_of_radius();
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["mine"]);
+        }
+
+        #[test]
+        fn test_prune_without_center_is_noop() {
+            let mut model = Model::default();
+            model.cases = vec![server_case("a", CaseStatus::Pending)];
+
+            assert_eq!(model.prune_out_of_radius(), 0);
+            assert_eq!(model.cases.len(), 1);
+        }
+
+        #[test]
+        fn test_refresh_prunes_stale_cases_beyond_radius() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, center) = pruning_model();
+            model.is_refreshing = true;
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ListCasesResponse {
+                            cases: vec![
+                                case_north_of("near", center, 500.0),
+                                case_north_of("stale", center, 2500.0),
+                            ],
+                            next_cursor: None,
+                            total_count: Some(2),
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["near"]);
+        }
+
+        #[test]
+        fn test_refresh_keeps_my_far_away_case() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, center) = pruning_model();
+            model.is_refreshing = true;
+            let mut mine = case_north_of("mine", center, 3000.0);
+            mine.status = CaseStatus::Claimed;
+            mine.assigned_rescuer_id = model.user_id.clone();
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ListCasesResponse {
+                            cases: vec![mine, case_north_of("theirs", center, 3000.0)],
+                            next_cursor: None,
+                            total_count: Some(2),
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["mine"]);
+        }
+
+        #[test]
+        fn test_new_case_push_prunes_cached_cases() {
+            let app = AppTester::<App, AppEffect>::default();
+            let (mut model, center) = pruning_model();
+            model.cases = vec![
+                case_north_of("near", center, 200.0),
+                case_north_of("drifted", center, 1500.0),
+            ];
+            model.jwt_token = Some("aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".into());
+
+            let _ = app.update(
+                Event::PushReceived(PushPayload::NewCase {
+                    case_id: "new".into(),
+                    lat: center.lat(),
+                    lng: center.lon(),
+                    severity: None,
+                }),
+                &mut model,
+            );
+
+            assert_eq!(model.cases.len(), 1);
+            assert_eq!(model.cases[0].id.0, "near");
+        }
+
+        fn sort_mode_model() -> Model {
+            let mut model = ready_model_at(
+                NULL_ISLAND,
+                None,
+                cases_at_distances(&[300.0, 100.0, 200.0, 400.0]),
+            );
+            let severities = [Some(2), None, Some(5), Some(5)];
+            let created = [4_000, 1_000, 2_000, 3_000];
+            for ((case, severity), created_ms) in
+                model.cases.iter_mut().zip(severities).zip(created)
+            {
+                case.wound_severity = severity;
+                case.created_at_ms_utc = UnixTimeMs(created_ms);
+            }
+            model
+        }
+
+        fn sorted_ids(app: &AppTester<App, AppEffect>, model: &Model) -> Vec<String> {
+            match app.view(model).state {
+                ViewState::Ready { list_items, .. } => {
+                    list_items.into_iter().map(|i| i.id).collect()
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_list_sort_by_distance() {
+            let app = AppTester::<App, AppEffect>::default();
+            let model = sort_mode_model();
+
+            assert_eq!(
+                sorted_ids(&app, &model),
+                vec!["case1", "case2", "case0", "case3"]
+            );
+        }
+
+        #[test]
+        fn test_list_sort_by_severity_then_distance() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = sort_mode_model();
+
+            let _ = app.update(
+                Event::SetListSortMode {
+                    mode: ListSortMode::SeverityThenDistance,
+                },
+                &mut model,
+            );
+
+            assert_eq!(
+                sorted_ids(&app, &model),
+                vec!["case2", "case3", "case0", "case1"]
+            );
+        }
+
+        #[test]
+        fn test_list_sort_by_newest() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = sort_mode_model();
+
+            let _ = app.update(
+                Event::SetListSortMode {
+                    mode: ListSortMode::Newest,
+                },
+                &mut model,
+            );
+
+            assert_eq!(
+                sorted_ids(&app, &model),
+                vec!["case0", "case3", "case2", "case1"]
+            );
+        }
+
+        #[test]
+        fn test_list_sort_mode_is_exposed_and_persisted() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = sort_mode_model();
+
+            let update = app.update(
+                Event::SetListSortMode {
+                    mode: ListSortMode::Newest,
+                },
+                &mut model,
+            );
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+            assert_eq!(model.preferences().list_sort, ListSortMode::Newest);
+            match app.view(&model).state {
+                ViewState::Ready { list_sort, .. } => assert_eq!(list_sort, ListSortMode::Newest),
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_map_markers_follow_clustering_flag() {
+            let app = AppTester::<App, AppEffect>::default();
+            let cases = vec![
+                server_case("a", CaseStatus::Pending),
+                server_case("b", CaseStatus::Claimed),
+            ];
+            let mut model = ready_model(cases);
+
+            match app.view(&model).state {
+                ViewState::Ready { map_markers, .. } => assert!(map_markers.is_none()),
+                other => panic!("unexpected view state {other:?}"),
+            }
+
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::Clustering { enabled: true },
+                },
+                &mut model,
+            );
+            assert!(model.preferences().clustering_enabled);
+            match app.view(&model).state {
+                ViewState::Ready {
+                    map_markers: Some(markers),
+                    pins,
+                    ..
+                } => {
+                    assert_eq!(pins.len(), 2);
+                    assert!(matches!(markers.as_slice(), [MapMarker::Cluster(c)] if c.count == 2));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        fn create_with_severity(model: &mut Model, wound_severity: Option<u8>) {
+            let app = AppTester::<App, AppEffect>::default();
+            let _ = app.update(
+                Event::CreateCaseRequested(CreateCasePayload {
+                    location: (51.5, -0.12),
+                    description: None,
+                    landmark_hint: None,
+                    wound_severity,
+                }),
+                model,
+            );
+        }
+
+        #[test]
+        fn test_create_case_rejects_out_of_range_severity() {
+            for severity in [0, 6] {
+                let mut model = ready_model(vec![]);
+                model.network_online = false;
+
+                create_with_severity(&mut model, Some(severity));
+
+                let error = model.active_error.as_ref().expect("validation error");
+                assert_eq!(error.kind, ErrorKind::Validation);
+                assert_eq!(
+                    error.context.get("wound_severity"),
+                    Some(&severity.to_string())
+                );
+                assert!(model.offline_store.pending_local_cases.is_empty());
+                assert!(model.offline_store.outbox.is_empty());
+            }
+        }
+
+        #[test]
+        fn test_create_case_accepts_valid_or_missing_severity() {
+            for severity in [Some(3), None] {
+                let mut model = ready_model(vec![]);
+                model.network_online = false;
+
+                create_with_severity(&mut model, severity);
+
+                assert!(model.active_error.is_none());
+                assert_eq!(model.offline_store.pending_local_cases.len(), 1);
+                assert_eq!(
+                    model.offline_store.pending_local_cases[0].wound_severity,
+                    severity
+                );
+            }
+        }
+
+        #[test]
+        fn test_full_outbox_dispatches_capacity_reached() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+            for i in 0..MAX_OUTBOX_ENTRIES {
+                let entry = OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: format!("token-{i}"),
+                });
+                model.offline_store.push_outbox(entry).unwrap();
+            }
+
+            let update = app.update(
+                Event::CreateCaseRequested(CreateCasePayload {
+                    location: (51.5, -0.12),
+                    description: None,
+                    landmark_hint: None,
+                    wound_severity: None,
+                }),
+                &mut model,
+            );
+
+            assert_eq!(
+                model.outbox_capacity_reached,
+                Some(OutboxKind::OutboxEntries)
+            );
+            assert_eq!(
+                model.active_error.as_ref().map(|e| e.kind),
+                Some(ErrorKind::QuotaExceeded)
+            );
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+            match app.view(&model).state {
+                ViewState::Ready {
+                    outbox_capacity_reached,
+                    ..
+                } => {
+                    assert_eq!(outbox_capacity_reached, Some(OutboxKind::OutboxEntries));
+                }
+                other => panic!("unexpected view state {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_outbox_full_error_names_the_queue() {
+            let error = AppError::from(OutboxError::Full {
+                max: MAX_OUTBOX_ENTRIES,
+                kind: OutboxKind::LocalCases,
+            });
+
+            assert_eq!(
+                error.context.get("outbox_kind").map(String::as_str),
+                Some("local_cases")
+            );
+        }
+
+        fn dead_create(status: Option<u16>) -> (LocalCase, OutboxEntry) {
+            let mut case = LocalCase::new(LatLon::new(51.5, -0.12), None, None);
+            case.status = LocalCaseStatus::PermanentlyFailed;
+            case.retry_count = 5;
+            case.sync_error = Some("gave up".into());
+            let mut entry = OutboxEntry::new(OutboxIntent::CreateCase {
+                local_id: case.local_id.clone(),
+                location: case.location,
+                description: None,
+                landmark_hint: None,
+                wound_severity: None,
+                has_photo: false,
+                created_at_ms_utc: UnixTimeMs::now(),
+            });
+            entry.attempt_count = 5;
+            let error = match status {
+                Some(code) => OutboxEntryError::server_error(code, None),
+                None => OutboxEntryError::network_error("offline"),
+            };
+            entry.mark_permanently_failed(error);
+            (case, entry)
+        }
+
+        fn model_with_dead_ops() -> Model {
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+            for status in [None, Some(422)] {
+                let (case, entry) = dead_create(status);
+                model.offline_store.pending_local_cases.push(case);
+                model.offline_store.outbox.push(entry);
+            }
+            model
+        }
+
+        #[test]
+        fn test_force_retry_skips_client_errors_by_default() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = model_with_dead_ops();
+
+            let update = app.update(
+                Event::ForceRetryPermanentlyFailed {
+                    include_client_errors: false,
+                },
+                &mut model,
+            );
+
+            let outbox = &model.offline_store.outbox;
+            assert_eq!(outbox[0].retry_state, RetryState::Pending);
+            assert_eq!(outbox[0].attempt_count, 0);
+            assert!(outbox[0].next_retry_at.is_none());
+            assert_eq!(outbox[1].retry_state, RetryState::PermanentlyFailed);
+
+            let cases = &model.offline_store.pending_local_cases;
+            assert_eq!(cases[0].status, LocalCaseStatus::PendingUpload);
+            assert_eq!(cases[0].retry_count, 0);
+            assert_eq!(cases[1].status, LocalCaseStatus::PermanentlyFailed);
+            assert!(persist_count(&update.effects) > 0);
+        }
+
+        #[test]
+        fn test_force_retry_can_include_client_errors() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = model_with_dead_ops();
+
+            let _ = app.update(
+                Event::ForceRetryPermanentlyFailed {
+                    include_client_errors: true,
+                },
+                &mut model,
+            );
+
+            assert!(model
+                .offline_store
+                .outbox
+                .iter()
+                .all(|e| e.retry_state == RetryState::Pending && e.attempt_count == 0));
+            assert!(model
+                .offline_store
+                .pending_local_cases
+                .iter()
+                .all(|c| c.status == LocalCaseStatus::PendingUpload && c.retry_count == 0));
+        }
+
+        #[test]
+        fn test_output_formats_round_trip() {
+            let img = image::DynamicImage::new_rgba8(16, 12);
+            for (format, expected) in [
+                (OutputImageFormat::Webp, image::ImageFormat::WebP),
+                (OutputImageFormat::Jpeg, image::ImageFormat::Jpeg),
+            ] {
+                let bytes = format.encode(&img, 70).unwrap();
+                assert_eq!(image::guess_format(&bytes).unwrap(), expected);
+                let decoded = image::load_from_memory(&bytes).unwrap();
+                assert_eq!((decoded.width(), decoded.height()), (16, 12));
+            }
+        }
+
+        #[test]
+        fn test_jpeg_output_format_stages_jpeg_photo() {
+            let mut model = Model::default();
+            model.set_preference(Preference::PhotoOutputFormat {
+                format: OutputImageFormat::Jpeg,
+            });
+
+            capture_jpeg(&mut model, jpeg_with_dimensions(32, 32));
+
+            let staged = model.staged_photo.as_ref().expect("staged photo");
+            assert_eq!(staged.mime_type, "image/jpeg");
+            assert_eq!(
+                image::guess_format(&staged.processed_data).unwrap(),
+                image::ImageFormat::Jpeg
+            );
+        }
+
+        #[test]
+        fn test_default_output_format_stays_webp() {
+            let mut model = Model::default();
+
+            capture_jpeg(&mut model, jpeg_with_dimensions(32, 32));
+
+            let staged = model.staged_photo.as_ref().expect("staged photo");
+            assert_eq!(staged.mime_type, PROCESSED_IMAGE_MIME_TYPE);
+            assert_eq!(
+                image::guess_format(&staged.processed_data).unwrap(),
+                image::ImageFormat::WebP
+            );
+        }
+
+        #[test]
+        fn test_jpeg_quality_affects_encoded_size() {
+            let img = image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(64, 64, |x, y| {
+                let channel = |v: u32| u8::try_from(v * 4).unwrap();
+                image::Rgb([channel(x), channel(y), channel(x ^ y)])
+            }));
+
+            let low = OutputImageFormat::Jpeg.encode(&img, 10).unwrap();
+            let high = OutputImageFormat::Jpeg.encode(&img, 95).unwrap();
+
+            assert!(low.len() < high.len());
+        }
+
+        /// Splices a big-endian EXIF APP1 segment carrying only the orientation tag
+        /// directly after the JPEG SOI marker.
+        fn jpeg_with_orientation(width: u32, height: u32, orientation: u16) -> Vec<u8> {
+            let plain = jpeg_with_dimensions(width, height);
+            let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+            exif.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+            exif.extend_from_slice(&orientation.to_be_bytes());
+            exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+            let segment_len = u16::try_from(exif.len() + 2).unwrap();
+
+            let mut data = plain[..2].to_vec();
+            data.extend_from_slice(&[0xFF, 0xE1]);
+            data.extend_from_slice(&segment_len.to_be_bytes());
+            data.extend_from_slice(&exif);
+            data.extend_from_slice(&plain[2..]);
+            data
+        }
+
+        #[test]
+        fn test_exif_rotated_photo_is_staged_upright() {
+            let mut model = Model::default();
+
+            capture_jpeg(&mut model, jpeg_with_orientation(40, 20, 6));
+
+            let staged = model.staged_photo.as_ref().expect("staged photo");
+            assert_eq!((staged.width, staged.height), (20, 40));
+            let processed = image::load_from_memory(&staged.processed_data).unwrap();
+            assert_eq!((processed.width(), processed.height()), (20, 40));
+        }
+
+        #[test]
+        fn test_photo_without_orientation_is_unchanged() {
+            let mut model = Model::default();
+
+            capture_jpeg(&mut model, jpeg_with_orientation(40, 20, 1));
+            let tagged = model.staged_photo.take().expect("staged photo");
+            capture_jpeg(&mut model, jpeg_with_dimensions(40, 20));
+            let plain = model.staged_photo.as_ref().expect("staged photo");
+
+            assert_eq!((tagged.width, tagged.height), (40, 20));
+            assert_eq!((plain.width, plain.height), (40, 20));
+        }
+
+        #[test]
+        fn test_processed_photo_carries_no_exif_orientation() {
+            let mut model = Model::default();
+            model.capture_config.output_format = OutputImageFormat::Jpeg;
+
+            capture_jpeg(&mut model, jpeg_with_orientation(40, 20, 6));
+
+            let staged = model.staged_photo.as_ref().expect("staged photo");
+            let reader = image::ImageReader::new(std::io::Cursor::new(&staged.processed_data))
+                .with_guessed_format()
+                .unwrap();
+            let mut decoder = reader.into_decoder().unwrap();
+            assert_eq!(
+                image::ImageDecoder::orientation(&mut decoder).unwrap(),
+                image::metadata::Orientation::NoTransforms
+            );
+        }
+
+        #[test]
+        fn test_identical_photo_is_processed_once() {
+            let mut model = Model::default();
+            model.photo_cache = Some(ProcessedPhotoCache::default());
+            let photo = jpeg_with_dimensions(48, 32);
+
+            capture_jpeg(&mut model, photo.clone());
+            let first = model.staged_photo.take().expect("first staged photo");
+            assert!(!first.processed_data.is_empty());
+
+            // Swap the cached output for a marker: a second pipeline run would
+            // re-encode the photo and overwrite it.
+            let marker = StagedPhoto {
+                processed_data: vec![0xAB],
+                ..first.clone()
+            };
+            let settings = model.photo_processing_fingerprint();
+            model
+                .photo_cache
+                .as_mut()
+                .unwrap()
+                .insert(&marker, &settings);
+
+            capture_jpeg(&mut model, photo.clone());
+            let second = model.staged_photo.as_ref().expect("second staged photo");
+
+            let cache = model.photo_cache.as_ref().unwrap();
+            assert_eq!(cache.misses(), 1);
+            assert_eq!(cache.hits(), 1);
+            assert_eq!(second.processed_data, vec![0xAB]);
+            assert_eq!(second.original_data, photo);
+        }
+
+        #[test]
+        fn test_photo_cache_is_off_by_default() {
+            let mut model = Model::default();
+            assert!(model.photo_cache.is_none());
+
+            capture_jpeg(&mut model, jpeg_with_dimensions(48, 32));
+
+            assert!(model.staged_photo.is_some());
+            assert!(model.photo_cache.is_none());
+        }
+
+        #[test]
+        fn test_photo_cache_evicts_least_recently_used() {
+            let mut cache = ProcessedPhotoCache::new(2);
+            let staged = |original: Vec<u8>| StagedPhoto {
+                original_data: original,
+                processed_data: vec![1],
+                cropped_data: None,
+                thumbnail_data: None,
+                width: 1,
+                height: 1,
+                mime_type: PROCESSED_IMAGE_MIME_TYPE.into(),
+                detection_count: 0,
+                top_confidence: 0.0,
+                detections: vec![],
+            };
+
+            cache.insert(&staged(vec![1]), "");
+            cache.insert(&staged(vec![2]), "");
+            assert!(cache.get(&[1], "").is_some());
+            cache.insert(&staged(vec![3]), "");
+
+            assert_eq!(cache.len(), 2);
+            assert!(cache.get(&[2], "").is_none());
+            assert!(cache.get(&[1], "").is_some());
+            assert!(cache.get(&[3], "").is_some());
+        }
+
+        #[test]
+        fn test_photo_cache_misses_after_settings_change() {
+            let mut model = Model::default();
+            model.photo_cache = Some(ProcessedPhotoCache::default());
+            let photo = jpeg_with_dimensions(48, 32);
+
+            capture_jpeg(&mut model, photo.clone());
+            model.capture_config.quality = model.capture_config.quality.saturating_sub(10);
+            capture_jpeg(&mut model, photo);
+
+            let cache = model.photo_cache.as_ref().unwrap();
+            assert_eq!(cache.misses(), 2);
+            assert_eq!(cache.hits(), 0);
+        }
+
+        #[test]
+        fn test_staged_photo_has_smaller_thumbnail() {
+            let mut model = Model::default();
+
+            capture_jpeg(&mut model, jpeg_with_dimensions(800, 600));
+
+            let photo = model.staged_photo.as_ref().expect("staged photo");
+            let thumbnail = photo.thumbnail_data.as_ref().expect("thumbnail");
+            assert!(thumbnail.len() < photo.processed_data.len());
+            let decoded = image::load_from_memory(thumbnail).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (256, 192));
+            assert_ne!(photo.best_data_for_upload(), thumbnail.as_slice());
+        }
+
+        #[test]
+        fn test_local_case_exposes_thumbnail_offline() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            capture_jpeg(&mut model, jpeg_with_dimensions(320, 240));
+            let thumbnail = model
+                .staged_photo
+                .as_ref()
+                .unwrap()
+                .thumbnail_data
+                .clone()
+                .unwrap();
+
+            let _ = app.update(
+                Event::CreateCaseRequested(CreateCasePayload {
+                    location: (51.5, -0.12),
+                    description: Some("Injured cat".into()),
+                    landmark_hint: None,
+                    wound_severity: None,
+                }),
+                &mut model,
+            );
+
+            let local = &model.offline_store.pending_local_cases[0];
+            assert_eq!(local.thumbnail_data.as_ref(), Some(&thumbnail));
+
+            match app.view(&model).state {
+                ViewState::Ready { list_items, .. } => {
+                    let item = list_items.iter().find(|item| item.is_local).unwrap();
+                    assert!(item.has_photo);
+                    assert!(item
+                        .thumbnail_url
+                        .as_deref()
+                        .unwrap()
+                        .starts_with("data:image/webp;base64,"));
+                }
+                other => panic!("expected Ready, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_refresh_stores_etag_from_response() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.is_refreshing = true;
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ListCasesResponse {
+                            cases: vec![server_case("case1", CaseStatus::Pending)],
+                            next_cursor: None,
+                            total_count: Some(1),
+                        },
+                    )
+                    .with_header("ETag", "\"v1\""))),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases_etag.as_deref(), Some("\"v1\""));
+        }
+
+        #[test]
+        fn test_refresh_not_modified_preserves_cases() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases = vec![
+                server_case("case1", CaseStatus::Pending),
+                server_case("case2", CaseStatus::Claimed),
+            ];
+            model.cases_etag = Some("\"v1\"".into());
+            model.is_refreshing = true;
+            assert!(model.offline_store.last_cases_refresh_ms.is_none());
+
+            let update = app.update(
+                Event::RefreshResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        304,
+                        Vec::new(),
+                    ))),
+                },
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["case1", "case2"]);
+            assert!(!model.is_refreshing);
+            assert!(model.offline_store.last_cases_refresh_ms.is_some());
+            assert!(model.active_error.is_none());
+            assert_eq!(model.cases_etag.as_deref(), Some("\"v1\""));
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+        }
+
+        fn refresh_response(generation: u64, ids: &[&str]) -> Event {
+            Event::RefreshResponse {
+                generation,
+                result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                    &ListCasesResponse {
+                        cases: ids.iter().map(|id| case_at_distance(id, 100.0)).collect(),
+                        next_cursor: None,
+                        total_count: None,
+                    },
+                ))),
+            }
+        }
+
+        #[test]
+        fn test_superseded_refresh_response_is_dropped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+
+            let _ = app.update(Event::RefreshRequested, &mut model);
+            let first = model.refresh_generation;
+            // A second refresh can start from another trigger while the first is in flight.
+            model.is_refreshing = false;
+            let _ = app.update(Event::RefreshRequested, &mut model);
+            let second = model.refresh_generation;
+            assert!(second > first);
+
+            let _ = app.update(refresh_response(second, &["fresh"]), &mut model);
+            let _ = app.update(refresh_response(first, &["stale"]), &mut model);
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["fresh"]);
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_refresh_in_flight_at_logout_does_not_block_next_refresh() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+
+            let _ = app.update(Event::RefreshRequested, &mut model);
+            let stale = model.refresh_generation;
+            let _ = app.update(Event::LogoutRequested, &mut model);
+            let _ = app.update(refresh_response(stale, &["stale"]), &mut model);
+
+            model.user_id = Some(UserId::new("user2"));
+            model.state = AppState::Ready;
+            let update = app.update(Event::RefreshRequested, &mut model);
+
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.cases.is_empty());
+        }
+
+        #[test]
+        fn test_stale_refresh_does_not_clear_refreshing_flag() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.refresh_generation = 3;
+            model.is_refreshing = true;
+
+            let _ = app.update(refresh_response(2, &["stale"]), &mut model);
+
+            assert!(model.is_refreshing);
+            assert!(model.cases.is_empty());
+        }
+
+        #[test]
+        fn test_load_more_from_previous_generation_is_dropped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.cases = vec![server_case("current", CaseStatus::Pending)];
+            model.refresh_generation = 1;
+
+            let _ = app.update(
+                Event::LoadMoreResponse {
+                    generation: 0,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ListCasesResponse {
+                            cases: vec![server_case("old_page", CaseStatus::Pending)],
+                            next_cursor: None,
+                            total_count: None,
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            let ids: Vec<&str> = model.cases.iter().map(|c| c.id.0.as_str()).collect();
+            assert_eq!(ids, vec!["current"]);
+        }
+
+        fn jwt_expiring_in(ms: i64) -> String {
+            use base64::Engine as _;
+            let exp = (i64::try_from(get_current_time_ms()).unwrap() + ms) / 1000;
+            let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(format!("{{\"exp\":{exp}}}"));
+            format!("eyJhbGciOiJIUzI1NiJ9.{payload}.c2lnbmF0dXJl")
+        }
+
+        #[test]
+        fn test_expired_token_defers_outbox_flush_until_refreshed() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+            model.jwt_token = Some(jwt_expiring_in(-60_000));
+            model
+                .offline_store
+                .push_outbox(OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: "fcm".into(),
+                }))
+                .unwrap();
+
+            let update = app.update(Event::OutboxFlushRequested, &mut model);
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.token_refresh_pending);
+            assert!(app.view(&model).token_refresh_needed);
+
+            let again = app.update(Event::OutboxFlushRequested, &mut model);
+            assert!(!has_effect(&again.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+
+            let refreshed = app.update(
+                Event::TokenRefreshed {
+                    jwt: jwt_expiring_in(3_600_000),
+                },
+                &mut model,
+            );
+            assert!(!model.token_refresh_pending);
+            assert!(!app.view(&model).token_refresh_needed);
+            assert!(has_effect(&refreshed.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_near_expiry_token_defers_refresh() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+            model.jwt_token = Some(jwt_expiring_in(5_000));
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.is_refreshing);
+            assert!(model.refresh_deferred_for_token);
+
+            let refreshed = app.update(
+                Event::TokenRefreshed {
+                    jwt: jwt_expiring_in(3_600_000),
+                },
+                &mut model,
+            );
+            assert!(has_effect(&refreshed.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(model.is_refreshing);
+            assert!(!model.refresh_deferred_for_token);
+        }
+
+        #[test]
+        fn test_valid_token_sends_refresh() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+            model.jwt_token = Some(jwt_expiring_in(3_600_000));
+
+            let update = app.update(Event::RefreshRequested, &mut model);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.token_refresh_pending);
+        }
+
+        #[test]
+        fn test_claim_response_without_distance_keeps_prior_distance() {
+            let (app, mut model, mutation_id) = claiming_model();
+            model.cases[0].distance_meters = Some(420.0);
+
+            let mut claimed = server_case("case-1", CaseStatus::Claimed);
+            claimed.assigned_rescuer_id = Some(UserId::new("user1"));
+            assert_eq!(claimed.distance_meters, None);
+
+            let _ = app.update(
+                Event::ClaimResponse {
+                    case_id: "case-1".into(),
+                    mutation_id,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ClaimCaseResponse {
+                            success: true,
+                            case: Some(claimed),
+                            message: None,
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert_eq!(model.cases[0].distance_meters, Some(420.0));
+        }
+
+        fn offline_model_with_case(status: CaseStatus) -> (AppTester<App, AppEffect>, Model) {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = false;
+            let mut case = server_case("case-1", status);
+            if status != CaseStatus::Pending {
+                case.assigned_rescuer_id = Some(UserId::new("user1"));
+            }
+            model.cases = vec![case];
+            (app, model)
+        }
+
+        fn queued_state(model: &Model) -> Option<RetryState> {
+            model
+                .offline_store
+                .queued_case_action(&CaseId::new("case-1"))
+                .map(|entry| entry.retry_state)
+        }
+
+        #[test]
+        fn test_offline_claim_is_queued_and_flushed_when_online() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Pending);
+
+            let update = app.update(
+                Event::ClaimRequested {
+                    case_id: "case-1".into(),
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            let entry = model
+                .offline_store
+                .queued_case_action(&CaseId::new("case-1"))
+                .unwrap();
+            assert!(matches!(entry.intent, OutboxIntent::ClaimCase { .. }));
+            assert_eq!(queued_state(&model), Some(RetryState::Pending));
+
+            let _ = app.update(Event::NetworkStatusChanged { online: true }, &mut model);
+            assert_eq!(queued_state(&model), Some(RetryState::InFlight));
+
+            let mutation_id = model.pending_claims[&CaseId::new("case-1")]
+                .mutation_id
+                .clone();
+            let mut claimed = server_case("case-1", CaseStatus::Claimed);
+            claimed.assigned_rescuer_id = Some(UserId::new("user1"));
+            let _ = app.update(
+                Event::ClaimResponse {
+                    case_id: "case-1".into(),
+                    mutation_id,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::ok_json(
+                        &ClaimCaseResponse {
+                            success: true,
+                            case: Some(claimed),
+                            message: None,
+                        },
+                    ))),
+                },
+                &mut model,
+            );
+
+            assert_eq!(queued_state(&model), None);
+            assert!(model.pending_claims.is_empty());
+            assert!(model.pending_mutations.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_offline_transition_is_queued_and_flushed_when_online() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Claimed);
+
+            let update = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "en_route".into(),
+                    notes: None,
+                    outcome: None,
+                },
+                &mut model,
+            );
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(model.cases[0].status, CaseStatus::EnRoute);
+            let entry = model
+                .offline_store
+                .queued_case_action(&CaseId::new("case-1"))
+                .unwrap();
+            assert!(matches!(
+                entry.intent,
+                OutboxIntent::TransitionCase {
+                    next_status: CaseStatus::EnRoute,
+                    ..
+                }
+            ));
+
+            let _ = app.update(Event::NetworkStatusChanged { online: true }, &mut model);
+            assert_eq!(queued_state(&model), Some(RetryState::InFlight));
+        }
+
+        #[test]
+        fn test_queued_claim_failure_follows_shared_retry_classification() {
+            for (status, expected) in [
+                (408, RetryState::Failed),
+                (429, RetryState::Failed),
+                (404, RetryState::PermanentlyFailed),
+            ] {
+                let (app, mut model) = offline_model_with_case(CaseStatus::Pending);
+                let _ = app.update(
+                    Event::ClaimRequested {
+                        case_id: "case-1".into(),
+                    },
+                    &mut model,
+                );
+                let _ = app.update(Event::NetworkStatusChanged { online: true }, &mut model);
+                let mutation_id = model.pending_claims[&CaseId::new("case-1")]
+                    .mutation_id
+                    .clone();
+
+                let _ = app.update(
+                    Event::ClaimResponse {
+                        case_id: "case-1".into(),
+                        mutation_id,
+                        result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                            status,
+                            Vec::new(),
+                        ))),
+                    },
+                    &mut model,
+                );
+
+                assert_eq!(queued_state(&model), Some(expected), "{status}");
+            }
+        }
+
+        #[test]
+        fn test_timer_tick_keeps_offline_queued_claim() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Pending);
+            let _ = app.update(
+                Event::ClaimRequested {
+                    case_id: "case-1".into(),
+                },
+                &mut model,
+            );
+
+            for claim in model.pending_claims.values_mut() {
+                claim.created_at_ms = 0;
+            }
+            for mutation in model.pending_mutations.values_mut() {
+                mutation.created_at_ms = 0;
+            }
+            let _ = app.update(Event::TimerTick, &mut model);
+
+            assert_eq!(model.cases[0].status, CaseStatus::Claimed);
+            assert!(model.has_pending_mutation("case-1"));
+            assert_eq!(queued_state(&model), Some(RetryState::Pending));
+        }
+
+        fn transition_idempotency_keys(effects: &[AppEffect]) -> Vec<String> {
+            effects
+                .iter()
+                .filter_map(|e| match e {
+                    AppEffect::Http(req) if req.operation.url.contains("/transition") => req
+                        .operation
+                        .headers
+                        .iter()
+                        .find(|h| h.name.eq_ignore_ascii_case("Idempotency-Key"))
+                        .map(|h| h.value.clone()),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_transition_sends_mutation_idempotency_key() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
+            let mut case = server_case("case-1", CaseStatus::Claimed);
+            case.assigned_rescuer_id = Some(UserId::new("user1"));
+            model.cases = vec![case];
+
+            let update = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "en_route".into(),
+                    notes: None,
+                    outcome: None,
+                },
+                &mut model,
+            );
+
+            let mutation = model.pending_mutations.values().next().unwrap();
+            assert_eq!(
+                transition_idempotency_keys(&update.effects),
+                vec![mutation.idempotency_key.0.clone()]
+            );
+        }
+
+        #[test]
+        fn test_retried_transition_reuses_idempotency_key() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Claimed);
+            let _ = app.update(
+                Event::TransitionRequested {
+                    case_id: "case-1".into(),
+                    next_status: "en_route".into(),
+                    notes: None,
+                    outcome: None,
+                },
+                &mut model,
+            );
+
+            let mut keys = Vec::new();
+            let first = app.update(Event::NetworkStatusChanged { online: true }, &mut model);
+            keys.extend(transition_idempotency_keys(&first.effects));
+
+            for _ in 0..2 {
+                let mutation_id = model
+                    .pending_mutations
+                    .values()
+                    .next()
+                    .map_or_else(String::new, |m| m.mutation_id.clone());
+                let _ = app.update(
+                    Event::TransitionResponse {
+                        case_id: "case-1".into(),
+                        mutation_id,
+                        result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                            503,
+                            Vec::new(),
+                        ))),
+                    },
+                    &mut model,
+                );
+                assert_eq!(queued_state(&model), Some(RetryState::Failed));
+
+                let retry = app.update(Event::RetryFailedOperations, &mut model);
+                keys.extend(transition_idempotency_keys(&retry.effects));
+            }
+
+            assert_eq!(keys.len(), 3);
+            assert!(keys.iter().all(|key| key == &keys[0]));
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_discarding_failed_claim_restores_server_status() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Pending);
+            let _ = app.update(
+                Event::ClaimRequested {
+                    case_id: "case-1".into(),
+                },
+                &mut model,
+            );
+            let entry = model.offline_store.outbox.last_mut().unwrap();
+            entry.mark_failed(OutboxEntryError::server_error(503, None));
+            let op_id = entry.op_id.0.clone();
+
+            let update = app.update(Event::DiscardOperation { op_id }, &mut model);
+
+            assert!(model.offline_store.outbox.is_empty());
+            assert!(model.pending_claims.is_empty());
+            assert_eq!(model.cases[0].status, CaseStatus::Pending);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+            assert_eq!(persist_count(&update.effects), 1);
+            assert!(!model.store_dirty);
+        }
+
+        #[test]
+        fn test_discarding_unknown_operation_shows_no_toast() {
+            let (app, mut model) = offline_model_with_case(CaseStatus::Pending);
+
+            let _ = app.update(
+                Event::DiscardOperation {
+                    op_id: "missing".into(),
+                },
+                &mut model,
+            );
+
+            assert!(model.active_toast.is_none());
+        }
+
+        fn pan_and_wait(app: &AppTester<App, AppEffect>, model: &mut Model, lat: f64, lng: f64) {
+            let _ = app.update(
+                Event::MapMoved {
+                    center: MapCenter { lat, lng },
+                    zoom: ZoomLevel::new(13.0),
+                },
+                model,
+            );
+            model.last_map_move_at_ms = model
+                .last_map_move_at_ms
+                .map(|t| t.saturating_sub(model.map_settle_debounce_ms));
+        }
+
+        #[test]
+        fn test_map_settles_after_quiet_period() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            for lng in [-0.13, -0.14, -0.15] {
+                let update = app.update(
+                    Event::MapMoved {
+                        center: MapCenter { lat: 51.5, lng },
+                        zoom: ZoomLevel::new(13.0),
+                    },
+                    &mut model,
+                );
+                assert!(!has_effect(&update.effects, |e| matches!(
+                    e,
+                    AppEffect::Render(_)
+                )));
+            }
+
+            let early = app.update(Event::TimerTick, &mut model);
+            assert!(!has_effect(&early.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+            assert!(model.last_map_move_at_ms.is_some());
+
+            pan_and_wait(&app, &mut model, 51.5, -0.16);
+            let settled = app.update(Event::TimerTick, &mut model);
+            assert!(has_effect(&settled.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+            assert!(model.last_map_move_at_ms.is_none());
+
+            let idle = app.update(Event::TimerTick, &mut model);
+            assert!(!has_effect(&idle.effects, |e| matches!(
+                e,
+                AppEffect::Render(_)
+            )));
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_map_settle_refreshes_only_past_drift_threshold() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::MapDriftRefresh {
+                        meters: Some(5_000),
+                    },
+                },
+                &mut model,
+            );
+
+            pan_and_wait(&app, &mut model, 51.51, -0.12);
+            let nearby = app.update(Event::TimerTick, &mut model);
+            assert!(!has_effect(&nearby.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(
+                model.area_center,
+                ValidatedCoordinate::new(51.5, -0.12).ok()
+            );
+
+            pan_and_wait(&app, &mut model, 51.6, -0.12);
+            let distant = app.update(Event::TimerTick, &mut model);
+            assert!(has_effect(&distant.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(model.area_center, model.map_center);
+            model.check_invariants().unwrap();
+        }
+
+        #[test]
+        fn test_map_settle_offline_keeps_area_center() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.map_drift_refresh_m = Some(5_000);
+            model.network_online = false;
+
+            pan_and_wait(&app, &mut model, 51.6, -0.12);
+            let update = app.update(Event::TimerTick, &mut model);
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(
+                model.area_center,
+                ValidatedCoordinate::new(51.5, -0.12).ok()
+            );
+        }
+
+        #[test]
+        fn test_map_settle_never_refreshes_without_threshold() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+
+            pan_and_wait(&app, &mut model, 52.5, -0.12);
+            let update = app.update(Event::TimerTick, &mut model);
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert_eq!(
+                model.area_center,
+                ValidatedCoordinate::new(51.5, -0.12).ok()
+            );
+        }
+
+        fn model_with_case_at(lat: f64, lon: f64) -> Model {
+            let mut case = server_case("case-1", CaseStatus::Pending);
+            case.location = LatLon::new(lat, lon);
+            ready_model(vec![case])
         }
 
         #[test]
-        fn test_outbox_entry_rate_limited() {
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test".into(),
-            };
-            let mut entry = OutboxEntry::new(intent);
-
-            entry.mark_rate_limited(60_000);
+        fn test_selecting_case_on_map_frames_user_and_case() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut nearby = model_with_case_at(51.505, -0.12);
+            let mut distant = model_with_case_at(51.8, -0.12);
+
+            for model in [&mut nearby, &mut distant] {
+                let _ = app.update(
+                    Event::CaseSelected {
+                        case_id: "case-1".into(),
+                    },
+                    model,
+                );
+            }
 
-            assert_eq!(entry.retry_state, RetryState::RateLimited);
-            assert!(entry.next_retry_at.is_some());
+            let nearby_center = nearby.map_center.unwrap();
+            assert!((nearby_center.lat() - 51.5025).abs() < 1e-6);
+            assert_eq!(nearby.map_zoom, 16.0);
+            assert!(distant.map_zoom < nearby.map_zoom);
         }
 
         #[test]
-        fn test_outbox_entry_is_ready_for_retry() {
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test".into(),
-            };
-            let mut entry = OutboxEntry::new(intent);
+        fn test_selecting_case_in_list_view_leaves_map_alone() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = model_with_case_at(51.8, -0.12);
+            model.feed_view = FeedView::List;
+
+            let _ = app.update(
+                Event::CaseSelected {
+                    case_id: "case-1".into(),
+                },
+                &mut model,
+            );
 
-            assert!(entry.is_ready_for_retry(0));
+            assert!(model.map_center.is_none());
+            assert_eq!(model.map_zoom, DEFAULT_MAP_ZOOM);
+        }
 
-            entry.mark_in_flight();
-            assert!(!entry.is_ready_for_retry(0));
+        #[test]
+        fn test_frame_case_unknown_case_is_noop() {
+            let mut model = ready_model(vec![]);
 
-            entry.mark_completed();
-            assert!(!entry.is_ready_for_retry(0));
+            assert!(!model.frame_case(&CaseId::new("missing")));
+            assert!(model.map_center.is_none());
         }
 
         #[test]
-        fn test_offline_store_push_outbox() {
-            let mut store = OfflineStore::new();
+        fn test_case_detail_carries_localized_status_text() {
+            let mut model = ready_model(vec![server_case("case-1", CaseStatus::Pending)]);
+            model.selected_case_id = Some(CaseId::new("case-1"));
 
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test".into(),
-            };
-            let entry = OutboxEntry::new(intent);
-            let op_id = entry.op_id.clone();
+            assert_eq!(case_detail_for(&model).status_text, "Pending");
 
-            assert!(store.push_outbox(entry).is_ok());
-            assert_eq!(store.outbox.len(), 1);
+            model.set_preference(Preference::Locale { locale: Locale::Es });
 
-            let duplicate_entry = OutboxEntry {
-                op_id: op_id.clone(),
-                idempotency_key: IdempotencyKey::generate(),
-                intent: OutboxIntent::SyncFcmToken {
-                    token: "test2".into(),
-                },
-                created_at: UnixTimeMs::now(),
-                updated_at: UnixTimeMs::now(),
-                retry_state: RetryState::Pending,
-                attempt_count: 0,
-                last_attempt_at: None,
-                next_retry_at: None,
-                last_error: None,
-            };
-            assert!(matches!(
-                store.push_outbox(duplicate_entry),
-                Err(OutboxError::DuplicateOpId(_))
-            ));
+            assert_eq!(case_detail_for(&model).status_text, "Pendiente");
         }
 
         #[test]
-        fn test_offline_store_pending_count() {
-            let mut store = OfflineStore::new();
+        fn test_imperial_preference_changes_detail_distance_text() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut case = server_case("case-1", CaseStatus::Pending);
+            case.location = LatLon::new(51.5, -0.12);
+            let mut model = ready_model(vec![case]);
+            model.selected_case_id = Some(CaseId::new("case-1"));
+
+            assert_eq!(case_detail_for(&model).distance_text, "0 m");
+
+            let update = app.update(
+                Event::SetPreference {
+                    preference: Preference::DistanceUnit {
+                        unit: DistanceUnit::Imperial,
+                    },
+                },
+                &mut model,
+            );
 
-            assert_eq!(store.pending_sync_count(), 0);
+            assert_eq!(case_detail_for(&model).distance_text, "0 ft");
+            assert_eq!(model.preferences().distance_unit, DistanceUnit::Imperial);
+            assert!(has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Kv(_)
+            )));
+        }
 
-            let intent = OutboxIntent::SyncFcmToken {
-                token: "test".into(),
-            };
-            store.push_outbox(OutboxEntry::new(intent)).unwrap();
+        #[test]
+        fn test_refresh_response_after_logout_is_dropped() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.network_online = true;
 
-            assert_eq!(store.pending_sync_count(), 1);
+            let _ = app.update(Event::RefreshRequested, &mut model);
+            let generation = model.refresh_generation;
+            let _ = app.update(Event::LogoutRequested, &mut model);
+            let _ = app.update(refresh_response(generation, &["old_user"]), &mut model);
 
-            store.outbox[0].mark_completed();
-            assert_eq!(store.pending_sync_count(), 0);
+            assert!(model.cases.is_empty());
+            model.check_invariants().unwrap();
         }
-    }
-
-    mod error_tests {
-        use super::*;
 
         #[test]
-        fn test_app_error_new() {
-            let error = AppError::new(ErrorKind::Network, "Test error");
+        fn test_logout_with_pending_work_waits_for_confirmation() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model
+                .offline_store
+                .push_outbox(OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: "t".into(),
+                }))
+                .unwrap();
 
-            assert_eq!(error.kind, ErrorKind::Network);
-            assert_eq!(error.severity, ErrorSeverity::Transient);
-            assert_eq!(error.message, "Test error");
-            assert!(error.internal_message.is_none());
-            assert!(error.is_retryable());
+            let _ = app.update(Event::LogoutRequested, &mut model);
+
+            assert!(model.is_authenticated());
+            assert_eq!(model.offline_store.outbox.len(), 1);
+            assert!(app.view(&model).logout_needs_confirmation);
+
+            let _ = app.update(Event::LogoutConfirmed, &mut model);
+
+            assert_eq!(model.state, AppState::Unauthenticated);
+            assert!(model.offline_store.outbox.is_empty());
+            assert!(!app.view(&model).logout_needs_confirmation);
         }
 
         #[test]
-        fn test_app_error_with_internal() {
-            let error = AppError::new(ErrorKind::Network, "User message")
-                .with_internal("Internal details");
+        fn test_logout_cancelled_keeps_session() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model
+                .offline_store
+                .push_outbox(OutboxEntry::new(OutboxIntent::SyncFcmToken {
+                    token: "t".into(),
+                }))
+                .unwrap();
 
-            assert_eq!(error.internal_message, Some("Internal details".into()));
+            let _ = app.update(Event::LogoutRequested, &mut model);
+            let _ = app.update(Event::LogoutCancelled, &mut model);
+
+            assert!(model.is_authenticated());
+            assert!(!model.logout_confirmation_pending);
+            assert_eq!(model.offline_store.outbox.len(), 1);
         }
 
         #[test]
-        fn test_app_error_with_context() {
-            let error = AppError::new(ErrorKind::Validation, "Invalid input")
-                .with_context("field", "email")
-                .with_context("value", "invalid@");
+        fn test_cancelled_request_ids_are_bounded() {
+            let mut model = Model::default();
+            let mut ids = Vec::new();
+            for _ in 0..MAX_CANCELLED_REQUEST_IDS + 5 {
+                let mutation = OptimisticMutation::new(
+                    CaseId::new("c"),
+                    CaseStatus::Pending,
+                    None,
+                    CaseStatus::Claimed,
+                );
+                ids.push(mutation.mutation_id.clone());
+                model
+                    .pending_mutations
+                    .insert(mutation.mutation_id.clone(), mutation);
+                model.cancel_in_flight_requests();
+                model.pending_mutations.clear();
+            }
 
-            assert_eq!(error.context.get("field"), Some(&"email".to_string()));
-            assert_eq!(error.context.get("value"), Some(&"invalid@".to_string()));
+            assert_eq!(model.cancelled_request_ids.len(), MAX_CANCELLED_REQUEST_IDS);
+            assert!(!model.take_cancelled_request(&ids[0]));
+            assert!(model.take_cancelled_request(&ids[ids.len() - 1]));
         }
 
         #[test]
-        fn test_app_error_from_http_status() {
-            assert_eq!(
-                AppError::from_http_status(400, None).kind,
-                ErrorKind::Validation
-            );
-            assert_eq!(
-                AppError::from_http_status(401, None).kind,
-                ErrorKind::Authentication
-            );
-            assert_eq!(
-                AppError::from_http_status(403, None).kind,
-                ErrorKind::Authorization
-            );
-            assert_eq!(
-                AppError::from_http_status(404, None).kind,
-                ErrorKind::NotFound
-            );
-            assert_eq!(
-                AppError::from_http_status(409, None).kind,
-                ErrorKind::Conflict
-            );
-            assert_eq!(
-                AppError::from_http_status(429, None).kind,
-                ErrorKind::RateLimited
+        fn test_first_fix_is_accepted_after_pin_drop_elsewhere() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.state = AppState::OnboardingLocation;
+            model.area_center = Some(ValidatedCoordinate::new(48.85, 2.35).unwrap());
+            model.last_location_fix_ms = Some(get_current_time_ms());
+
+            let _ = app.update(
+                Event::LocationReceived {
+                    lat: 51.5,
+                    lng: -0.12,
+                    accuracy: Some(10.0),
+                },
+                &mut model,
             );
+
             assert_eq!(
-                AppError::from_http_status(500, None).kind,
-                ErrorKind::Internal
+                model.area_center,
+                Some(ValidatedCoordinate::new(51.5, -0.12).unwrap())
             );
+            assert_eq!(model.state, AppState::OnboardingRadius);
         }
 
         #[test]
-        fn test_error_kind_retryable() {
-            assert!(ErrorKind::Network.is_retryable());
-            assert!(ErrorKind::Timeout.is_retryable());
-            assert!(ErrorKind::RateLimited.is_retryable());
-            assert!(ErrorKind::Conflict.is_retryable());
-            assert!(!ErrorKind::Authentication.is_retryable());
-            assert!(!ErrorKind::Validation.is_retryable());
-            assert!(!ErrorKind::Internal.is_retryable());
+        fn test_gps_fix_is_not_compared_against_dropped_pin() {
+            let mut model = Model::default();
+            let home = ValidatedCoordinate::new(51.5, -0.12).unwrap();
+            let _ = model.accept_location_fix(home, 1_000);
+            model.area_center = Some(ValidatedCoordinate::new(48.85, 2.35).unwrap());
+
+            assert!(model.accept_location_fix(home, 2_000).is_some());
+            assert_eq!(model.location_jump_count, 0);
         }
 
         #[test]
-        fn test_user_facing_message() {
-            let network_error = AppError::new(ErrorKind::Network, "Connection failed");
-            assert!(network_error.user_facing_message().contains("internet"));
+        fn test_purge_after_logout_deletes_store_key() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            let _ = app.update(
+                Event::LoginCompleted {
+                    jwt: jwt_expiring_in(3_600_000),
+                    user_id: "user1".into(),
+                },
+                &mut model,
+            );
+            let _ = app.update(Event::LogoutRequested, &mut model);
+            assert!(model.user_id.is_none());
 
-            let validation_error = AppError::new(ErrorKind::Validation, "Email is invalid");
-            assert_eq!(validation_error.user_facing_message(), "Email is invalid");
+            let update = app.update(Event::PurgeAllLocalData, &mut model);
 
-            let internal_error = AppError::new(ErrorKind::Internal, "Database error");
-            assert!(internal_error.user_facing_message().contains("unexpected"));
+            let keys = deleted_keys(&update.effects);
+            assert!(
+                keys.iter().any(|k| k.starts_with("offline_store_v")),
+                "{keys:?}"
+            );
+            let prefs = serde_json::to_string(&model.preferences()).unwrap();
+            assert!(!prefs.contains("offline_store_v"));
+            model.check_invariants().unwrap();
         }
-    }
-
-    mod local_case_tests {
-        use super::*;
 
         #[test]
-        fn test_local_case_new() {
-            let location = LatLon::new(51.5074, -0.1278);
-            let case = LocalCase::new(location, Some("Test description".into()), Some(3));
+        fn test_purge_after_restart_deletes_remembered_store_keys() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            let bytes = serde_json::to_vec(&vec!["offline_store_v1_abc"]).unwrap();
+            let _ = app.update(
+                Event::StoreKeysLoaded {
+                    result: Box::new(Ok(bytes)),
+                },
+                &mut model,
+            );
 
-            assert!(!case.local_id.0.is_empty());
-            assert_eq!(case.location, location);
-            assert_eq!(case.description, Some("Test description".into()));
-            assert_eq!(case.wound_severity, Some(3));
-            assert_eq!(case.status, LocalCaseStatus::PendingUpload);
-            assert!(case.server_id.is_none());
-            assert!(case.photo_data.is_none());
+            let update = app.update(Event::PurgeAllLocalData, &mut model);
+
+            assert!(deleted_keys(&update.effects).contains(&"offline_store_v1_abc".to_string()));
         }
 
         #[test]
-        fn test_local_case_mark_synced() {
-            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
-            case.photo_data = Some(vec![1, 2, 3]);
+        fn test_decode_limit_preference_is_capped_and_persisted() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
 
-            case.mark_synced(CaseId::new("server123"));
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::MaxImageAlloc { bytes: usize::MAX },
+                },
+                &mut model,
+            );
+            assert_eq!(model.max_image_alloc, MAX_IMAGE_ALLOC);
 
-            assert_eq!(case.status, LocalCaseStatus::Synced);
-            assert_eq!(case.server_id, Some(CaseId::new("server123")));
-            assert!(case.sync_error.is_none());
-            assert!(case.photo_data.is_none());
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::MaxImageAlloc {
+                        bytes: 32 * 1024 * 1024,
+                    },
+                },
+                &mut model,
+            );
+            let mut restored = Model::default();
+            restored.apply_preferences(&model.preferences());
+            assert_eq!(restored.max_image_alloc, 32 * 1024 * 1024);
         }
 
         #[test]
-        fn test_local_case_mark_failed() {
-            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
+        fn test_local_case_keeps_staged_photo_mime_type() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            let _ = app.update(
+                Event::SetPreference {
+                    preference: Preference::PhotoOutputFormat {
+                        format: OutputImageFormat::Jpeg,
+                    },
+                },
+                &mut model,
+            );
+            capture_jpeg(&mut model, jpeg_with_dimensions(64, 48));
+            // Switching formats afterwards must not change what the queued photo claims to be.
+            model.set_preference(Preference::PhotoOutputFormat {
+                format: OutputImageFormat::Webp,
+            });
 
-            case.mark_failed("Connection timeout");
+            let _ = app.update(
+                Event::CreateCaseRequested(CreateCasePayload {
+                    location: (51.5, -0.12),
+                    description: None,
+                    landmark_hint: None,
+                    wound_severity: None,
+                }),
+                &mut model,
+            );
 
-            assert_eq!(case.status, LocalCaseStatus::Failed);
-            assert_eq!(case.sync_error, Some("Connection timeout".into()));
-            assert_eq!(case.retry_count, 1);
+            let local = &model.offline_store.pending_local_cases[0];
+            assert_eq!(local.photo_mime_type.as_deref(), Some("image/jpeg"));
+            match app.view(&model).state {
+                ViewState::Ready { list_items, .. } => {
+                    let item = list_items.iter().find(|item| item.is_local).unwrap();
+                    assert!(item
+                        .thumbnail_url
+                        .as_deref()
+                        .unwrap()
+                        .starts_with("data:image/jpeg;base64,"));
+                }
+                other => panic!("expected Ready, got {other:?}"),
+            }
         }
 
         #[test]
-        fn test_local_case_permanent_failure() {
-            let mut case = LocalCase::new(LatLon::new(0.0, 0.0), None, None);
-
-            for _ in 0..=MAX_RETRY_ATTEMPTS {
-                case.mark_failed("Error");
-            }
+        fn test_zero_drift_threshold_disables_settle_refresh() {
+            let mut model = Model::default();
+            model.set_preference(Preference::MapDriftRefresh { meters: Some(0) });
 
-            assert_eq!(case.status, LocalCaseStatus::PermanentlyFailed);
+            assert_eq!(model.map_drift_refresh_m, None);
+            assert_eq!(model.preferences().map_drift_refresh_m, None);
         }
 
+        #[cfg(feature = "strict_responses")]
         #[test]
-        fn test_local_case_description_preview() {
-            let case = LocalCase::new(
-                LatLon::new(0.0, 0.0),
-                Some("This is a very long description that should be truncated".into()),
-                None,
+        fn test_strict_responses_feature_rejects_drifted_refresh() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![server_case("kept", CaseStatus::Pending)]);
+
+            let _ = app.update(
+                Event::RefreshResponse {
+                    generation: model.refresh_generation,
+                    result: Box::new(Ok(crate::capabilities::HttpOutput::from_status(
+                        200,
+                        br#"{"cases":[],"extra":true}"#.to_vec(),
+                    ))),
+                },
+                &mut model,
             );
 
-            let preview = case.description_preview(20);
-            assert_eq!(preview.len(), 20);
-            assert!(preview.ends_with("..."));
+            assert_eq!(model.cases.len(), 1);
+            assert_eq!(model.cases[0].id, CaseId::new("kept"));
         }
 
         #[test]
-        fn test_local_case_description_preview_short() {
-            let case = LocalCase::new(LatLon::new(0.0, 0.0), Some("Short".into()), None);
-
-            let preview = case.description_preview(20);
-            assert_eq!(preview, "Short");
+        fn test_local_case_mime_type_is_normalized() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+            model.network_online = false;
+            model.set_draft(Some(CaseDraft {
+                location: Some((51.5, -0.12)),
+                photo_data: Some(vec![1, 2, 3]),
+                photo_mime_type: Some("IMAGE/JPG".into()),
+                ..CaseDraft::default()
+            }));
+
+            let _ = app.update(Event::SubmitDraft, &mut model);
+
+            let local = &model.offline_store.pending_local_cases[0];
+            assert_eq!(local.photo_mime_type.as_deref(), Some("image/jpeg"));
         }
-    }
-
-    mod model_tests {
-        use super::*;
 
         #[test]
-        fn test_model_default() {
-            let model = Model::default();
+        fn test_timeouts_are_tracked_for_every_endpoint() {
+            use crate::capabilities::{HttpError, HttpOutput};
 
-            assert_eq!(model.state, AppState::Loading);
-            assert!(model.user_id.is_none());
-            assert!(model.area_center.is_none());
-            assert_eq!(model.area_radius_m, DEFAULT_RADIUS_M);
-            assert_eq!(model.map_zoom, DEFAULT_MAP_ZOOM);
-            assert!(model.cases.is_empty());
-            assert!(model.network_online);
-            assert!(!model.is_refreshing);
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = Model::default();
+
+            let _ = app.update(
+                Event::ClaimResponse {
+                    case_id: "case1".into(),
+                    mutation_id: "m1".into(),
+                    result: Box::new(Err(HttpError::Timeout)),
+                },
+                &mut model,
+            );
+            let _ = app.update(
+                Event::FcmSyncResponse {
+                    result: Box::new(Err(HttpError::Timeout)),
+                },
+                &mut model,
+            );
+
+            assert_eq!(model.timeout_tracker.streak("claim_case"), 1);
+            assert_eq!(model.timeout_tracker.streak("sync_fcm_token"), 1);
+            assert_eq!(model.timeout_tracker.streak("create_case"), 0);
+
+            let _ = app.update(
+                Event::FcmSyncResponse {
+                    result: Box::new(Ok(HttpOutput::from_status(200, ""))),
+                },
+                &mut model,
+            );
+            assert_eq!(model.timeout_tracker.streak("sync_fcm_token"), 0);
+            model.check_invariants().unwrap();
         }
 
         #[test]
-        fn test_model_is_authenticated() {
-            let mut model = Model::default();
+        fn test_foreground_refresh_waits_out_rate_limit() {
+            let app = AppTester::<App, AppEffect>::default();
+            let mut model = ready_model(vec![]);
+            model.set_rate_limited("list_cases", get_current_time_ms(), 60_000);
 
-            assert!(!model.is_authenticated());
+            let update = app.update(Event::AppForegrounded, &mut model);
+
+            assert!(!has_effect(&update.effects, |e| matches!(
+                e,
+                AppEffect::Http(_)
+            )));
+            assert!(!model.is_refreshing);
+            assert!(model.active_toast.is_none());
+        }
+    }
+
+    mod jwt_tests {
+        use super::*;
 
-            model.user_id = Some(UserId::new("user123"));
-            assert!(model.is_authenticated());
+        #[test]
+        fn test_validate_jwt_structure_accepts_well_formed() {
+            assert!(validate_jwt_structure("aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl").is_ok());
+            assert!(validate_jwt_structure("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.a-b_c").is_ok());
         }
 
         #[test]
-        fn test_model_show_toast() {
-            let mut model = Model::default();
-
-            model.show_toast("Test message", ToastKind::Success);
+        fn test_validate_jwt_structure_rejects_empty() {
+            assert!(validate_jwt_structure("").is_err());
+            assert!(validate_jwt_structure("   ").is_err());
+        }
 
-            assert!(model.active_toast.is_some());
-            let toast = model.active_toast.as_ref().unwrap();
-            assert_eq!(toast.message, "Test message");
-            assert_eq!(toast.kind, ToastKind::Success);
+        #[test]
+        fn test_validate_jwt_structure_rejects_wrong_segment_count() {
+            assert!(validate_jwt_structure("single").is_err());
+            assert!(validate_jwt_structure("a.b").is_err());
+            assert!(validate_jwt_structure("a.b.c.d").is_err());
+            assert!(validate_jwt_structure("a..c").is_err());
         }
 
         #[test]
-        fn test_model_set_clear_error() {
-            let mut model = Model::default();
+        fn test_validate_jwt_structure_rejects_non_base64url() {
+            assert!(validate_jwt_structure("a.b+c.d").is_err());
+            assert!(validate_jwt_structure(" a.b.c").is_err());
+            assert!(validate_jwt_structure("a.b.c=").is_err());
+        }
 
-            model.set_error(AppError::new(ErrorKind::Network, "Test error"));
-            assert!(model.active_error.is_some());
+        fn jwt_with_exp(exp_secs: u64) -> String {
+            use base64::Engine as _;
+            let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(format!("{{\"sub\":\"user1\",\"exp\":{exp_secs}}}"));
+            format!("eyJhbGciOiJIUzI1NiJ9.{payload}.c2lnbmF0dXJl")
+        }
 
-            model.clear_error();
-            assert!(model.active_error.is_none());
+        #[test]
+        fn test_jwt_expiry_reads_exp_claim() {
+            assert_eq!(
+                jwt_expiry_ms(&jwt_with_exp(1_700_000_000)),
+                Some(1_700_000_000_000)
+            );
+            assert_eq!(jwt_expiry_ms("aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl"), None);
+            assert_eq!(jwt_expiry_ms("not-a-jwt"), None);
         }
 
         #[test]
-        fn test_model_optimistic_mutation() {
+        fn test_token_is_expiring_for_valid_near_expiry_and_expired() {
+            let now_ms = 1_700_000_000_000;
             let mut model = Model::default();
-            model.user_id = Some(UserId::new("user123"));
-
-            let case_id = CaseId::new("case123");
-
-            model.cases.push(ServerCase {
-                id: case_id.clone(),
-                location: LatLon::new(0.0, 0.0),
-                description: None,
-                landmark_hint: None,
-                wound_severity: None,
-                status: CaseStatus::Pending,
-                created_at_ms_utc: UnixTimeMs::now(),
-                updated_at_ms_utc: UnixTimeMs::now(),
-                reporter_id: UserId::new("other"),
-                assigned_rescuer_id: None,
-                photo_url: None,
-                thumbnail_url: None,
-                gemini_diagnosis: None,
-                species_guess: None,
-                distance_meters: None,
-            });
-
-            let mutation_id = model.store_optimistic_mutation(
-                case_id.clone(),
-                CaseStatus::Pending,
-                None,
-                CaseStatus::Claimed,
-            );
+            assert!(!model.token_is_expiring(now_ms, JWT_EXPIRY_SKEW_MS));
 
-            assert!(model.pending_mutations.contains_key(&mutation_id));
+            model.jwt_token = Some(jwt_with_exp(1_700_003_600));
+            assert!(!model.token_is_expiring(now_ms, JWT_EXPIRY_SKEW_MS));
 
-            if let Some(case) = model.cases.iter_mut().find(|c| c.id == case_id) {
-                case.status = CaseStatus::Claimed;
-                case.assigned_rescuer_id = model.user_id.clone();
-            }
+            model.jwt_token = Some(jwt_with_exp(1_700_000_010));
+            assert!(model.token_is_expiring(now_ms, JWT_EXPIRY_SKEW_MS));
+            assert!(!model.token_is_expiring(now_ms, 0));
 
-            let rolled_back = model.rollback_mutation(&mutation_id);
-            assert!(rolled_back);
+            model.jwt_token = Some(jwt_with_exp(1_699_999_000));
+            assert!(model.token_is_expiring(now_ms, 0));
 
-            let case = model.cases.iter().find(|c| c.id == case_id).unwrap();
-            assert_eq!(case.status, CaseStatus::Pending);
-            assert!(case.assigned_rescuer_id.is_none());
+            model.jwt_token = Some("aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".into());
+            assert!(!model.token_is_expiring(now_ms, JWT_EXPIRY_SKEW_MS));
         }
     }
 
-    mod zoom_tests {
+    mod detection_tests {
         use super::*;
 
-        #[test]
-        fn test_zoom_for_radius() {
-            assert_eq!(zoom_for_radius(500), 16.0);
-            assert_eq!(zoom_for_radius(1000), 16.0);
-            assert_eq!(zoom_for_radius(2000), 15.0);
-            assert_eq!(zoom_for_radius(5000), 14.0);
-            assert_eq!(zoom_for_radius(10000), 13.0);
-            assert_eq!(zoom_for_radius(20000), 12.0);
-            assert_eq!(zoom_for_radius(50000), 11.0);
-            assert_eq!(zoom_for_radius(100000), FALLBACK_ZOOM);
+        fn synthetic_detections() -> Vec<crate::vision::Detection> {
+            [0.35, 0.55, 0.72, 0.91]
+                .into_iter()
+                .map(|confidence| crate::vision::Detection {
+                    bbox: [0.1, 0.1, 0.4, 0.4],
+                    confidence,
+                    class_id: 15,
+                })
+                .collect()
         }
-    }
 
-    mod event_tests {
-        use super::*;
+        #[test]
+        fn test_higher_threshold_reduces_detection_count() {
+            let config = crate::vision::DetectorConfig::default();
+            let default_count = config.apply(synthetic_detections()).len();
+            let strict_count = config
+                .with_confidence_threshold(0.8)
+                .apply(synthetic_detections())
+                .len();
+
+            assert_eq!(default_count, 4);
+            assert_eq!(strict_count, 1);
+        }
 
         #[test]
-        fn test_event_default() {
-            let event = Event::default();
-            assert!(matches!(event, Event::Noop));
+        fn test_apply_detections_updates_staged_photo() {
+            let mut photo = StagedPhoto {
+                original_data: vec![1, 2, 3],
+                processed_data: vec![4, 5, 6],
+                cropped_data: Some(vec![7]),
+                thumbnail_data: None,
+                width: 10,
+                height: 10,
+                mime_type: "image/webp".into(),
+                detection_count: 4,
+                top_confidence: 0.91,
+                detections: synthetic_detections(),
+            };
+
+            let config = crate::vision::DetectorConfig::default().with_confidence_threshold(0.95);
+            photo.apply_detections(config.apply(synthetic_detections()), None);
+
+            assert_eq!(photo.detection_count, 0);
+            assert!(!photo.has_detections());
+            assert!(photo.top_confidence.abs() < f32::EPSILON);
+            assert_eq!(photo.best_data_for_upload(), &[4, 5, 6]);
         }
 
         #[test]
-        fn test_event_name() {
-            assert_eq!(Event::Noop.name(), "noop");
-            assert_eq!(Event::AppStarted.name(), "app_started");
-            assert_eq!(Event::LoginRequested.name(), "login_requested");
-            assert_eq!(Event::RefreshRequested.name(), "refresh_requested");
+        fn test_check_image_mime_type_rejects_heic_family() {
+            for mime in [
+                "image/heic",
+                "image/HEIF",
+                "image/heic; codecs=hvc1",
+                "image/heif-sequence",
+            ] {
+                let err = check_image_mime_type(mime).unwrap_err();
+                assert_eq!(err.kind, ErrorKind::ImageFormatUnsupported, "{mime}");
+                assert!(err.message.contains("JPEG"));
+            }
         }
 
         #[test]
-        fn test_event_is_user_initiated() {
-            assert!(!Event::Noop.is_user_initiated());
-            assert!(!Event::AppStarted.is_user_initiated());
-            assert!(Event::LoginRequested.is_user_initiated());
-            assert!(Event::RefreshRequested.is_user_initiated());
-            assert!(Event::CapturePhotoRequested.is_user_initiated());
-            assert!(Event::ClaimRequested {
-                case_id: "test".into()
+        fn test_check_image_mime_type_accepts_decodable_formats() {
+            for mime in ["image/jpeg", "image/png", "image/webp", ""] {
+                assert!(check_image_mime_type(mime).is_ok(), "{mime}");
             }
-            .is_user_initiated());
         }
-    }
 
-    mod toast_tests {
-        use super::*;
+        #[test]
+        fn test_normalize_mime_folds_aliases_and_casing() {
+            for (mime, expected) in [
+                ("image/jpeg", "image/jpeg"),
+                ("image/jpg", "image/jpeg"),
+                ("IMAGE/JPEG", "image/jpeg"),
+                (" image/pjpeg ", "image/jpeg"),
+                ("image/PNG", "image/png"),
+                ("image/x-png", "image/png"),
+                ("image/webp; q=0.9", "image/webp"),
+            ] {
+                assert_eq!(normalize_mime(mime), Some(expected), "{mime}");
+            }
+            assert_eq!(
+                normalize_mime(PROCESSED_IMAGE_MIME_TYPE),
+                Some(PROCESSED_IMAGE_MIME_TYPE)
+            );
+        }
 
         #[test]
-        fn test_toast_message_new() {
-            let toast = ToastMessage::new("Test message", ToastKind::Info);
+        fn test_normalize_mime_rejects_unsupported_types() {
+            for mime in ["image/heic", "image/gif", "application/octet-stream", ""] {
+                assert_eq!(normalize_mime(mime), None, "{mime}");
+            }
+        }
 
-            assert_eq!(toast.message, "Test message");
-            assert_eq!(toast.kind, ToastKind::Info);
-            assert_eq!(toast.duration_ms, 3000);
+        #[test]
+        fn test_low_confidence_detection_does_not_crop() {
+            let detections = vec![crate::vision::Detection {
+                bbox: [0.1, 0.1, 0.4, 0.4],
+                confidence: 0.3,
+                class_id: 15,
+            }];
+
+            assert!(!should_auto_crop(&detections, 0.5));
         }
 
         #[test]
-        fn test_toast_kind_duration() {
-            assert_eq!(ToastKind::Info.default_duration_ms(), 3000);
-            assert_eq!(ToastKind::Success.default_duration_ms(), 2000);
-            assert_eq!(ToastKind::Warning.default_duration_ms(), 4000);
-            assert_eq!(ToastKind::Error.default_duration_ms(), 5000);
+        fn test_confident_detection_crops() {
+            let detections = vec![crate::vision::Detection {
+                bbox: [0.1, 0.1, 0.4, 0.4],
+                confidence: 0.7,
+                class_id: 15,
+            }];
+
+            assert!(should_auto_crop(&detections, 0.5));
+            assert!(!should_auto_crop(&[], 0.0));
         }
 
         #[test]
-        fn test_toast_is_expired() {
-            let toast = ToastMessage::new("Test", ToastKind::Info);
-            let created = toast.created_at_ms;
+        fn test_crop_threshold_is_independent_of_detection_threshold() {
+            let model = Model::default();
+            let kept = model.detector_config.apply(synthetic_detections());
 
-            assert!(!toast.is_expired(created));
-            assert!(!toast.is_expired(created + 2999));
-            assert!(toast.is_expired(created + 3001));
+            assert_eq!(kept.len(), 4);
+            assert!(should_auto_crop(&kept, model.min_crop_confidence));
+            assert!(!should_auto_crop(&kept, 0.95));
         }
-    }
-
-    mod permission_state_tests {
-        use super::*;
 
         #[test]
-        fn test_permission_state() {
-            assert!(PermissionState::Unknown.is_unknown());
-            assert!(!PermissionState::Unknown.is_granted());
-            assert!(!PermissionState::Unknown.is_denied());
+        fn test_crop_threshold_preference_is_clamped() {
+            let mut model = Model::default();
 
-            assert!(PermissionState::Granted.is_granted());
-            assert!(!PermissionState::Granted.is_unknown());
-            assert!(!PermissionState::Granted.is_denied());
+            model.set_preference(Preference::MinCropConfidence { confidence: 0.95 });
+            assert!((model.min_crop_confidence - 0.95).abs() < f32::EPSILON);
 
-            assert!(PermissionState::Denied.is_denied());
-            assert!(!PermissionState::Denied.is_granted());
-            assert!(!PermissionState::Denied.is_unknown());
+            model.set_preference(Preference::MinCropConfidence { confidence: 4.0 });
+            assert!((model.min_crop_confidence - 1.0).abs() < f32::EPSILON);
 
-            assert!(PermissionState::Restricted.is_denied());
+            model.set_preference(Preference::MinCropConfidence {
+                confidence: f32::NAN,
+            });
+            assert!((model.min_crop_confidence - 1.0).abs() < f32::EPSILON);
         }
-    }
-
-    mod feed_view_tests {
-        use super::*;
 
         #[test]
-        fn test_feed_view_toggle() {
-            assert_eq!(FeedView::Map.toggle(), FeedView::List);
-            assert_eq!(FeedView::List.toggle(), FeedView::Map);
-        }
+        fn test_top_confidence_reflects_capped_detections() {
+            let config = crate::vision::DetectorConfig::default()
+                .with_confidence_threshold(0.5)
+                .with_max_detections(1);
+            let kept = config.apply(synthetic_detections());
+
+            let mut photo = StagedPhoto {
+                original_data: vec![],
+                processed_data: vec![],
+                cropped_data: None,
+                thumbnail_data: None,
+                width: 1,
+                height: 1,
+                mime_type: PROCESSED_IMAGE_MIME_TYPE.into(),
+                detection_count: 0,
+                top_confidence: 0.0,
+                detections: vec![],
+            };
+            photo.apply_detections(kept, None);
 
-        #[test]
-        fn test_feed_view_as_str() {
-            assert_eq!(FeedView::Map.as_str(), "map");
-            assert_eq!(FeedView::List.as_str(), "list");
+            assert_eq!(photo.detection_count, 1);
+            assert!((photo.top_confidence - 0.91).abs() < f32::EPSILON);
         }
     }
 
-    mod id_tests {
+    mod cluster_tests {
         use super::*;
 
-        #[test]
-        fn test_user_id() {
-            let id = UserId::new("user123");
-            assert_eq!(id.as_str(), "user123");
-            assert_eq!(id.to_string(), "user123");
+        fn pin(id: &str, severity: Option<u8>) -> CasePin {
+            CasePin {
+                id: id.into(),
+                lat: 51.5,
+                lon: -0.12,
+                status: CaseStatus::Pending,
+                is_mine: false,
+                is_local: false,
+                wound_severity: severity,
+                is_new: false,
+                distance_meters: None,
+            }
         }
 
         #[test]
-        fn test_case_id_generate() {
-            let id1 = CaseId::generate();
-            let id2 = CaseId::generate();
-            assert_ne!(id1.0, id2.0);
-            assert!(!id1.0.is_empty());
+        fn stable_id_hash_is_deterministic() {
+            assert_eq!(stable_id_hash("case-1"), stable_id_hash("case-1"));
+            assert_ne!(stable_id_hash("case-1"), stable_id_hash("case-2"));
+            assert_eq!(stable_id_hash(""), 0xcbf2_9ce4_8422_2325);
         }
 
         #[test]
-        fn test_local_op_id_generate() {
-            let id1 = LocalOpId::generate();
-            let id2 = LocalOpId::generate();
-            assert_ne!(id1.0, id2.0);
+        fn representative_prefers_higher_severity() {
+            let pins = vec![pin("a", Some(2)), pin("b", Some(5)), pin("c", None)];
+
+            assert_eq!(cluster_representative(&pins).unwrap().id, "b");
         }
 
         #[test]
-        fn test_idempotency_key_generate() {
-            let key1 = IdempotencyKey::generate();
-            let key2 = IdempotencyKey::generate();
-            assert_ne!(key1.0, key2.0);
+        fn equal_severity_representative_is_stable_across_calls_and_order() {
+            let pins = vec![
+                pin("case-a", Some(3)),
+                pin("case-b", Some(3)),
+                pin("case-c", Some(3)),
+            ];
+            let first = PinCluster::from_pins(&pins).unwrap().representative_id;
+
+            for _ in 0..10 {
+                assert_eq!(
+                    PinCluster::from_pins(&pins).unwrap().representative_id,
+                    first
+                );
+            }
+
+            let mut reversed = pins.clone();
+            reversed.reverse();
+            assert_eq!(
+                PinCluster::from_pins(&reversed).unwrap().representative_id,
+                first
+            );
         }
 
         #[test]
-        fn test_op_id_generate() {
-            let id1 = OpId::generate();
-            let id2 = OpId::generate();
-            assert_ne!(id1.0, id2.0);
+        fn cluster_from_pins_reports_count_and_centroid() {
+            let mut east = pin("east", None);
+            east.lon = 0.0;
+            let mut west = pin("west", None);
+            west.lon = -0.2;
+
+            let cluster = PinCluster::from_pins(&[east, west]).unwrap();
+
+            assert_eq!(cluster.count, 2);
+            assert!((cluster.lon - -0.1).abs() < 1e-9);
+            assert!(PinCluster::from_pins(&[]).is_none());
         }
-    }
 
-    mod unix_time_tests {
-        use super::*;
+        fn pin_at(id: &str, status: CaseStatus, north_m: f64) -> CasePin {
+            let mut p = pin(id, None);
+            p.status = status;
+            p.lat += north_m / 111_195.0;
+            p
+        }
 
         #[test]
-        fn test_unix_time_now() {
-            let time1 = UnixTimeMs::now();
-            std::thread::sleep(std::time::Duration::from_millis(10));
-            let time2 = UnixTimeMs::now();
+        fn radius_for_zoom_inverts_radius_map() {
+            assert!((radius_for_zoom(14.0) - 5000.0).abs() < 1e-9);
+            assert!((radius_for_zoom(15.0) - 2000.0).abs() < 1e-9);
+            assert!((radius_for_zoom(10.0) - 100_000.0).abs() < 1e-9);
+            assert!((radius_for_zoom(f64::NAN) - radius_for_zoom(FALLBACK_ZOOM)).abs() < 1e-9);
+        }
 
-            assert!(time2.0 > time1.0);
+        #[test]
+        fn single_pin_passes_through() {
+            let only = pin("solo", Some(3));
+
+            assert_eq!(
+                cluster_pins(&[only.clone()], 12.0),
+                vec![MapMarker::Pin(only)]
+            );
         }
 
         #[test]
-        fn test_unix_time_operations() {
-            let time = UnixTimeMs(1000);
+        fn distant_pins_stay_separate() {
+            let pins = vec![
+                pin_at("a", CaseStatus::Pending, 0.0),
+                pin_at("b", CaseStatus::Pending, 1000.0),
+            ];
 
-            assert_eq!(time.as_millis(), 1000);
-            assert_eq!(time.as_secs(), 1);
-            assert_eq!(time.add_millis(500).0, 1500);
+            let markers = cluster_pins(&pins, 16.0);
+
+            assert_eq!(markers.len(), 2);
+            assert!(markers.iter().all(|m| matches!(m, MapMarker::Pin(_))));
         }
 
         #[test]
-        fn test_unix_time_comparison() {
-            let earlier = UnixTimeMs(1000);
-            let later = UnixTimeMs(2000);
+        fn tight_pins_form_one_cluster_with_most_urgent_status() {
+            let pins = vec![
+                pin_at("a", CaseStatus::Claimed, 0.0),
+                pin_at("b", CaseStatus::Resolved, 5.0),
+                pin_at("c", CaseStatus::Pending, 10.0),
+            ];
+
+            match cluster_pins(&pins, 16.0).as_slice() {
+                [MapMarker::Cluster(cluster)] => {
+                    assert_eq!(cluster.count, 3);
+                    assert_eq!(cluster.dominant_status, CaseStatus::Pending);
+                }
+                other => panic!("expected one cluster, got {other:?}"),
+            }
+        }
 
-            assert!(earlier.is_before(later));
-            assert!(later.is_after(earlier));
-            assert_eq!(later.elapsed_since(earlier), 1000);
+        #[test]
+        fn dominant_status_skips_terminal_statuses() {
+            let pins = vec![
+                pin_at("a", CaseStatus::Resolved, 0.0),
+                pin_at("b", CaseStatus::EnRoute, 1.0),
+            ];
+
+            assert_eq!(
+                PinCluster::from_pins(&pins).unwrap().dominant_status,
+                CaseStatus::EnRoute
+            );
         }
     }
 
-    mod lat_lon_tests {
+    mod pin_packing_tests {
         use super::*;
 
+        fn unpack_pins(packed: &[u8]) -> Vec<CasePin> {
+            let read_u32 = |at: usize| u32::from_le_bytes(packed[at..at + 4].try_into().unwrap());
+            let read_f64 = |at: usize| f64::from_le_bytes(packed[at..at + 8].try_into().unwrap());
+
+            assert_eq!(packed[0], PACKED_PIN_FORMAT_VERSION);
+            let count = read_u32(4) as usize;
+            let ids_start = PACKED_PIN_HEADER_LEN + count * PACKED_PIN_RECORD_LEN;
+
+            (0..count)
+                .map(|i| {
+                    let at = PACKED_PIN_HEADER_LEN + i * PACKED_PIN_RECORD_LEN;
+                    let id_offset = ids_start + read_u32(at) as usize;
+                    let id_len = read_u32(at + 4) as usize;
+                    let flags = packed[at + 33];
+
+                    CasePin {
+                        id: String::from_utf8(packed[id_offset..id_offset + id_len].to_vec())
+                            .unwrap(),
+                        lat: read_f64(at + 8),
+                        lon: read_f64(at + 16),
+                        distance_meters: (flags & PACKED_PIN_FLAG_HAS_DISTANCE != 0)
+                            .then(|| read_f64(at + 24)),
+                        status: PACKED_PIN_STATUSES[usize::from(packed[at + 32])],
+                        is_mine: flags & PACKED_PIN_FLAG_MINE != 0,
+                        is_local: flags & PACKED_PIN_FLAG_LOCAL != 0,
+                        is_new: flags & PACKED_PIN_FLAG_NEW != 0,
+                        wound_severity: (flags & PACKED_PIN_FLAG_HAS_SEVERITY != 0)
+                            .then_some(packed[at + 34]),
+                    }
+                })
+                .collect()
+        }
+
         #[test]
-        fn test_lat_lon_new() {
-            let loc = LatLon::new(51.5074, -0.1278);
-            assert_eq!(loc.lat, 51.5074);
-            assert_eq!(loc.lon, -0.1278);
+        fn packed_pins_round_trip_through_reference_unpacker() {
+            let pins = vec![
+                CasePin {
+                    id: "case-1".into(),
+                    lat: 51.507_351,
+                    lon: -0.127_758,
+                    status: CaseStatus::EnRoute,
+                    is_mine: true,
+                    is_local: false,
+                    wound_severity: Some(4),
+                    is_new: true,
+                    distance_meters: Some(1_234.5),
+                },
+                CasePin {
+                    id: "local-\u{e9}".into(),
+                    lat: -33.868_82,
+                    lon: 151.209_29,
+                    status: CaseStatus::Pending,
+                    is_mine: false,
+                    is_local: true,
+                    wound_severity: None,
+                    is_new: false,
+                    distance_meters: None,
+                },
+                CasePin {
+                    id: "case-3".into(),
+                    lat: 0.0,
+                    lon: 0.0,
+                    status: CaseStatus::Expired,
+                    is_mine: false,
+                    is_local: false,
+                    wound_severity: Some(0),
+                    is_new: false,
+                    distance_meters: Some(0.0),
+                },
+            ];
+
+            let packed = pack_pins(&pins);
+
+            assert_eq!(unpack_pins(&packed), pins);
         }
 
         #[test]
-        fn test_lat_lon_validate() {
-            let valid = LatLon::new(51.5074, -0.1278);
-            assert!(valid.validate().is_ok());
+        fn packed_pins_use_fixed_record_layout() {
+            let packed = pack_pins(&[]);
 
-            let invalid = LatLon::new(91.0, 0.0);
-            assert!(invalid.validate().is_err());
+            assert_eq!(packed, vec![PACKED_PIN_FORMAT_VERSION, 0, 0, 0, 0, 0, 0, 0]);
+            assert!(unpack_pins(&packed).is_empty());
         }
 
         #[test]
-        fn test_lat_lon_conversion() {
-            let coord = ValidatedCoordinate::new(51.5074, -0.1278).unwrap();
-            let lat_lon: LatLon = coord.into();
+        fn model_pins_packed_matches_view_pins() {
+            let mut model = Model::default();
+            model.area_center = Some(ValidatedCoordinate::new(0.0, 0.0).unwrap());
+            model.cases = vec![
+                server_case("a", CaseStatus::Claimed),
+                server_case("b", CaseStatus::Pending),
+            ];
 
-            assert_eq!(lat_lon.lat, 51.5074);
-            assert_eq!(lat_lon.lon, -0.1278);
+            let unpacked = unpack_pins(&model.pins_packed());
+
+            assert_eq!(unpacked, App::build_case_pins(&model));
+            assert_eq!(unpacked.len(), 2);
         }
     }
 }
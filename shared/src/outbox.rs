@@ -316,15 +316,22 @@ pub enum ErrorCategory {
 }
 
 impl ErrorCategory {
+    pub fn error_kind(&self) -> crate::ErrorKind {
+        match self {
+            ErrorCategory::Transient | ErrorCategory::NetworkError => crate::ErrorKind::Network,
+            ErrorCategory::RateLimited => crate::ErrorKind::RateLimited,
+            ErrorCategory::ClientError => crate::ErrorKind::Validation,
+            ErrorCategory::ServerError => crate::ErrorKind::Internal,
+            ErrorCategory::Timeout => crate::ErrorKind::Timeout,
+            ErrorCategory::Unknown => crate::ErrorKind::Unknown,
+        }
+    }
+
+    /// Classified through [`crate::classify_retryability`] so the outbox
+    /// agrees with the rest of the app.
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            ErrorCategory::Transient
-                | ErrorCategory::RateLimited
-                | ErrorCategory::ServerError
-                | ErrorCategory::NetworkError
-                | ErrorCategory::Timeout
-        )
+        let kind = self.error_kind();
+        crate::classify_retryability(kind.http_status_hint(), kind).is_retryable()
     }
 }
 
@@ -1821,6 +1828,9 @@ mod tests {
         assert!(ErrorCategory::RateLimited.is_retryable());
         assert!(ErrorCategory::NetworkError.is_retryable());
         assert!(!ErrorCategory::ClientError.is_retryable());
+        assert!(ErrorCategory::ServerError.is_retryable());
+        assert!(ErrorCategory::Timeout.is_retryable());
+        assert!(!ErrorCategory::Unknown.is_retryable());
     }
 
     #[test]
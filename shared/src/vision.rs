@@ -29,6 +29,12 @@ const MAX_NMS_INPUTS: usize = 300;
 /// Inference timeout to prevent hangs
 const INFERENCE_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Minimum class score for a candidate to be kept
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.35;
+
+/// Detections returned per image after NMS
+pub const DEFAULT_MAX_DETECTIONS: usize = 10;
+
 /// COCO animal class IDs (validated against model class count at runtime)
 const ANIMAL_CLASSES: &[u32] = &[14, 15, 16, 17, 18, 19, 20, 21, 22, 23];
 
@@ -120,6 +126,55 @@ pub struct DetectionResult {
     pub postprocess_ms: f64,
 }
 
+// ============================================================================
+// Detector Configuration (caller supplied)
+// ============================================================================
+
+/// Filtering applied to detections before they are returned.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DetectorConfig {
+    /// Candidates scoring below this are discarded before NMS
+    pub confidence_threshold: f32,
+    /// Only the top-N detections by confidence are returned
+    pub max_detections: usize,
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            max_detections: DEFAULT_MAX_DETECTIONS,
+        }
+    }
+}
+
+impl DetectorConfig {
+    #[must_use]
+    pub fn with_confidence_threshold(mut self, threshold: f32) -> Self {
+        self.confidence_threshold = threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_detections(mut self, max: usize) -> Self {
+        self.max_detections = max;
+        self
+    }
+
+    /// Drops detections below the threshold (or non-finite), then keeps the
+    /// `max_detections` most confident, highest first.
+    #[must_use]
+    pub fn apply(&self, detections: Vec<Detection>) -> Vec<Detection> {
+        let mut kept: Vec<Detection> = detections
+            .into_iter()
+            .filter(|d| d.confidence.is_finite() && d.confidence >= self.confidence_threshold)
+            .collect();
+        kept.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        kept.truncate(self.max_detections);
+        kept
+    }
+}
+
 // ============================================================================
 // Model Configuration (extracted at load time)
 // ============================================================================
@@ -146,6 +201,8 @@ struct ModelConfig {
 pub struct YoloDetector {
     session: std::sync::Mutex<Session>,
     config: ModelConfig,
+    detector_config: DetectorConfig,
+    warmed_up: bool,
 }
 
 // Explicit: we handle synchronization via Mutex
@@ -159,7 +216,7 @@ impl YoloDetector {
     ///
     /// Returns error if model cannot be loaded or has unexpected structure.
     #[instrument(skip(model_bytes), fields(model_size = model_bytes.len()))]
-    pub fn new(model_bytes: &[u8]) -> Result<Self, VisionError> {
+    pub fn new(model_bytes: &[u8], detector_config: DetectorConfig) -> Result<Self, VisionError> {
         let session = Session::builder()?.commit_from_memory(model_bytes)?;
 
         // Extract and validate model configuration
@@ -174,6 +231,8 @@ impl YoloDetector {
         Ok(Self {
             session: std::sync::Mutex::new(session),
             config,
+            detector_config,
+            warmed_up: false,
         })
     }
 
@@ -388,6 +447,56 @@ impl YoloDetector {
         ))
     }
 
+    /// Runs one inference on a blank letterbox canvas so the runtime performs
+    /// its lazy allocation and graph optimization before the first real photo.
+    /// Calling it again after a successful warmup is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if inference on the blank input fails.
+    #[instrument(skip(self))]
+    pub fn warmup(&mut self) -> Result<(), VisionError> {
+        if self.warmed_up {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let blank = Array3::<f32>::from_elem(
+            (
+                3,
+                self.config.input_height as usize,
+                self.config.input_width as usize,
+            ),
+            114.0 / 255.0,
+        )
+        .insert_axis(Axis(0));
+        self.run_inference(blank)?;
+        self.warmed_up = true;
+
+        debug!(
+            warmup_ms = start.elapsed().as_secs_f64() * 1000.0,
+            "Detector warmed up"
+        );
+        Ok(())
+    }
+
+    /// The filtering applied to every [`detect`](Self::detect) call.
+    #[must_use]
+    pub fn detector_config(&self) -> DetectorConfig {
+        self.detector_config
+    }
+
+    /// Replaces the filtering used by subsequent [`detect`](Self::detect) calls.
+    pub fn set_detector_config(&mut self, detector_config: DetectorConfig) {
+        self.detector_config = detector_config;
+    }
+
+    /// Whether [`warmup`](Self::warmup) has completed successfully.
+    #[must_use]
+    pub fn is_warmed_up(&self) -> bool {
+        self.warmed_up
+    }
+
     /// Runs detection on an image.
     ///
     /// # Errors
@@ -549,7 +658,7 @@ impl YoloDetector {
             )));
         }
 
-        let conf_threshold: f32 = 0.5;
+        let conf_threshold = self.detector_config.confidence_threshold;
         let iou_threshold: f32 = 0.45;
 
         let mut candidates = Vec::with_capacity(200);
@@ -642,6 +751,7 @@ impl YoloDetector {
 
         // Apply NMS with truncation tracking
         let (detections, truncated) = nms_with_tracking(candidates, iou_threshold);
+        let detections = self.detector_config.apply(detections);
 
         Ok((detections, truncated, candidates_before_nms))
     }
@@ -843,6 +953,89 @@ mod tests {
         assert!(YoloDetector::validate_format(&gif_header).is_err());
     }
 
+    /// Loads the detector from `YOLO_MODEL_PATH`; these tests are skipped when
+    /// no model file is available.
+    #[cfg(feature = "ml")]
+    fn detector_from_env() -> Option<YoloDetector> {
+        let path = std::env::var("YOLO_MODEL_PATH").ok()?;
+        let bytes = std::fs::read(path).ok()?;
+        Some(YoloDetector::new(&bytes, DetectorConfig::default()).expect("model should load"))
+    }
+
+    #[cfg(feature = "ml")]
+    #[test]
+    fn test_warmup_completes() {
+        let Some(mut detector) = detector_from_env() else {
+            return;
+        };
+
+        assert!(!detector.is_warmed_up());
+        detector.warmup().expect("warmup should succeed");
+        assert!(detector.is_warmed_up());
+        detector.warmup().expect("repeat warmup is a no-op");
+    }
+
+    #[cfg(feature = "ml")]
+    #[test]
+    fn test_detection_after_warmup() {
+        let Some(mut detector) = detector_from_env() else {
+            return;
+        };
+        detector.warmup().expect("warmup should succeed");
+
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgb8(64, 48)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let result = detector.detect(&png).expect("detection should succeed");
+        assert!(!result.truncated);
+    }
+
+    fn detection(confidence: f32) -> Detection {
+        Detection {
+            bbox: [0.1, 0.1, 0.5, 0.5],
+            confidence,
+            class_id: 15,
+        }
+    }
+
+    #[test]
+    fn test_detector_config_drops_low_confidence() {
+        let config = DetectorConfig::default();
+        let kept = config.apply(vec![
+            detection(0.9),
+            detection(0.2),
+            detection(f32::NAN),
+            detection(0.35),
+        ]);
+
+        let confidences: Vec<f32> = kept.iter().map(|d| d.confidence).collect();
+        assert_eq!(confidences, vec![0.9, 0.35]);
+    }
+
+    #[test]
+    fn test_detector_config_caps_to_most_confident() {
+        let config = DetectorConfig::default()
+            .with_confidence_threshold(0.0)
+            .with_max_detections(2);
+        let kept = config.apply(vec![
+            detection(0.4),
+            detection(0.8),
+            detection(0.6),
+            detection(0.7),
+        ]);
+
+        let confidences: Vec<f32> = kept.iter().map(|d| d.confidence).collect();
+        assert_eq!(confidences, vec![0.8, 0.7]);
+    }
+
+    #[test]
+    fn test_detector_config_zero_cap_returns_nothing() {
+        let config = DetectorConfig::default().with_max_detections(0);
+        assert!(config.apply(vec![detection(0.99)]).is_empty());
+    }
+
     #[test]
     fn test_animal_class_set() {
         assert!(ANIMAL_CLASS_SET.contains(&14));
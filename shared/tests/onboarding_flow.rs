@@ -17,12 +17,18 @@ fn test_onboarding_flow() {
     assert!(update.effects.iter().any(|e| matches!(e, Effect::Render(_))));
 
     // 3. LoginCompleted
-    let update = app.update(Event::LoginCompleted { 
-        jwt: "xxx".to_string(), 
-        user_id: "123".to_string() 
-    }, &mut model);
+    let update = app.update(
+        Event::LoginCompleted {
+            jwt: "aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".to_string(),
+            user_id: "123".to_string(),
+        },
+        &mut model,
+    );
     assert_eq!(model.state, AppState::OnboardingLocation);
-    assert_eq!(model.jwt, Some("xxx".to_string()));
+    assert_eq!(
+        model.jwt,
+        Some("aGVhZGVy.cGF5bG9hZA.c2lnbmF0dXJl".to_string())
+    );
     assert_eq!(model.user_id, Some("123".to_string()));
     assert!(update.effects.iter().any(|e| matches!(e, Effect::Render(_))));
 